@@ -0,0 +1,424 @@
+//! Team-matching bridge between the NCAA (henrygd) bracket and ESPN live data.
+//!
+//! The henrygd bracket and ESPN's scoreboard use unrelated team IDs and slightly
+//! different school names ("UConn" vs "Connecticut", "St. John's" vs "Saint
+//! John's"). The bridge normalizes names, resolves a table of known aliases and
+//! pairs each ESPN scoreboard game with its bracket slot, so `Game.espn_id` can be
+//! populated and live scores merged into an NCAA-sourced bracket.
+use crate::{Game, Team, Tournament};
+
+/// Known spelling differences between the NCAA and ESPN feeds.
+/// Both sides are already normalized; the right-hand value is the canonical key.
+const ALIASES: &[(&str, &str)] = &[
+    ("uconn", "connecticut"),
+    ("ole miss", "mississippi"),
+    ("unc", "north carolina"),
+    ("nc state", "north carolina state"),
+    ("usc", "southern california"),
+    ("lsu", "louisiana state"),
+    ("byu", "brigham young"),
+    ("vcu", "virginia commonwealth"),
+    ("smu", "southern methodist"),
+    ("tcu", "texas christian"),
+    ("ucf", "central florida"),
+    ("unlv", "nevada las vegas"),
+    ("umbc", "maryland baltimore county"),
+    ("utep", "texas el paso"),
+    ("pitt", "pittsburgh"),
+    ("fau", "florida atlantic"),
+    ("fgcu", "florida gulf coast"),
+    ("fdu", "fairleigh dickinson"),
+    ("etsu", "east tennessee state"),
+    ("siue", "siu edwardsville"),
+    ("uncw", "unc wilmington"),
+    ("unc greensboro", "uncg"),
+    ("unc asheville", "unca"),
+    ("omaha", "nebraska omaha"),
+    ("mcneese", "mcneese state"),
+    ("miami fl", "miami"),
+    ("saint marys ca", "saint marys"),
+    ("saint francis pa", "saint francis"),
+    ("saint francis u", "saint francis"),
+    ("texas aandm cc", "texas aandm corpus christi"),
+    ("little rock", "arkansas little rock"),
+    ("ualr", "arkansas little rock"),
+    ("long island", "liu"),
+    ("app state", "appalachian state"),
+    ("cal st fullerton", "cal state fullerton"),
+    ("csu fullerton", "cal state fullerton"),
+    ("csun", "cal state northridge"),
+    ("american", "american university"),
+    ("loyola il", "loyola chicago"),
+    ("mount saint marys", "mount saint marys md"),
+    ("queens nc", "queens"),
+];
+
+/// Normalize a school name into a comparison key.
+///
+/// Lowercases, drops punctuation, spells out `&` and expands the `St.` prefix
+/// ("saint") and suffix ("state") before resolving [`ALIASES`].
+pub fn normalize_team_name(name: &str) -> String {
+    let cleaned: String = name
+        .to_lowercase()
+        .replace('&', "and")
+        .chars()
+        .filter(|c| *c != '\'' && *c != '.' && *c != '’')
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { ' ' })
+        .collect();
+
+    let mut tokens: Vec<&str> = cleaned.split_whitespace().collect();
+    tokens.retain(|t| *t != "the" && *t != "university" && *t != "univ");
+    let last = tokens.len().saturating_sub(1);
+    let expanded: Vec<&str> = tokens
+        .iter()
+        .enumerate()
+        .map(|(i, t)| match *t {
+            "st" if i == 0 => "saint",
+            "st" if i == last => "state",
+            other => other,
+        })
+        .collect();
+
+    let key = expanded.join(" ");
+    ALIASES
+        .iter()
+        .find(|(alias, _)| *alias == key)
+        .map(|(_, canonical)| canonical.to_string())
+        .unwrap_or(key)
+}
+
+/// All normalized keys a team can be recognized by (name, short name, abbreviation).
+fn team_keys(team: &Team) -> Vec<String> {
+    let mut keys: Vec<String> = [&team.short_name, &team.name, &team.abbrev]
+        .into_iter()
+        .filter(|s| s.chars().count() >= 2)
+        .map(|s| normalize_team_name(s))
+        .filter(|k| !k.is_empty())
+        .collect();
+    keys.sort();
+    keys.dedup();
+    keys
+}
+
+/// Words that, following a school name, make it a different school rather than
+/// a mascot ("Texas" vs "Texas Tech", "Florida" vs "Florida Atlantic").
+const SCHOOL_QUALIFIERS: &[&str] = &[
+    "aandm",
+    "atlantic",
+    "baptist",
+    "central",
+    "christian",
+    "college",
+    "east",
+    "eastern",
+    "el",
+    "gulf",
+    "international",
+    "north",
+    "northern",
+    "poly",
+    "saint",
+    "san",
+    "south",
+    "southern",
+    "state",
+    "tech",
+    "upstate",
+    "west",
+    "western",
+];
+
+/// Score how confidently two teams from different feeds are the same school.
+///
+/// 0 = no match, 1 = one full name is the other's key plus a mascot (ESPN display
+/// names carry the mascot, e.g. "Duke Blue Devils"), 3 = exact key match.
+///
+/// The mascot case only counts on a whole-word boundary, when the trailing words
+/// don't start with a [`SCHOOL_QUALIFIERS`] word and the longer team's own short
+/// name (when it has a separate one) doesn't extend the key, so "Texas" never matches "Texas Tech".
+pub fn team_match_score(a: &Team, b: &Team) -> u8 {
+    let a_keys = team_keys(a);
+    let b_keys = team_keys(b);
+    if a_keys.iter().any(|k| b_keys.contains(k)) {
+        return 3;
+    }
+    if mascot_match(&a_keys, b) || mascot_match(&b_keys, a) {
+        1
+    } else {
+        0
+    }
+}
+
+/// True when `other`'s full name is one of `keys` followed by a mascot.
+fn mascot_match(keys: &[String], other: &Team) -> bool {
+    let short = normalize_team_name(&other.short_name);
+    let full = normalize_team_name(&other.name);
+    keys.iter().any(|k| {
+        let Some(rest) = full
+            .strip_prefix(k.as_str())
+            .and_then(|r| r.strip_prefix(' '))
+        else {
+            return false;
+        };
+        let first = rest.split(' ').next().unwrap_or_default();
+        let short_extends = short != full && short.starts_with(&format!("{k} "));
+        !SCHOOL_QUALIFIERS.contains(&first) && !short_extends
+    })
+}
+
+/// A bracket game paired with an ESPN scoreboard game.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BridgeMatch {
+    /// `Game.id` of the bracket game.
+    pub game_id: String,
+    /// True when ESPN's top/bottom (home/away) are reversed relative to the bracket.
+    pub swapped: bool,
+}
+
+/// Find the bracket game that an ESPN scoreboard game refers to.
+///
+/// Games already bridged are found by `espn_id`. Otherwise both teams must match
+/// (in either orientation); equal seeds and a start date within a day act as tie
+/// breakers. Ambiguous matches are skipped rather than guessed.
+pub fn match_game(tournament: &Tournament, espn: &Game) -> Option<BridgeMatch> {
+    let espn_id = espn.espn_id.as_deref().unwrap_or(espn.id.as_str());
+    let espn_top = espn.top.team.as_ref()?;
+    let espn_bottom = espn.bottom.team.as_ref()?;
+
+    let mut best: Option<(u8, BridgeMatch)> = None;
+    let mut tied = false;
+
+    for game in tournament.games() {
+        let (Some(top), Some(bottom)) = (game.top.team.as_ref(), game.bottom.team.as_ref()) else {
+            continue;
+        };
+        let already_bridged = game.espn_id.as_deref() == Some(espn_id);
+        if game.espn_id.is_some() && !already_bridged {
+            continue;
+        }
+
+        let straight = (
+            team_match_score(top, espn_top),
+            team_match_score(bottom, espn_bottom),
+        );
+        let crossed = (
+            team_match_score(top, espn_bottom),
+            team_match_score(bottom, espn_top),
+        );
+        let (pair, swapped) = if crossed.0 + crossed.1 > straight.0 + straight.1 {
+            (crossed, true)
+        } else {
+            (straight, false)
+        };
+
+        if !already_bridged && (pair.0 == 0 || pair.1 == 0) {
+            continue;
+        }
+
+        let mut score = pair.0 + pair.1;
+        if already_bridged {
+            score += 10;
+        }
+
+        let (espn_top_seed, espn_bottom_seed) = if swapped {
+            (espn.bottom.seed, espn.top.seed)
+        } else {
+            (espn.top.seed, espn.bottom.seed)
+        };
+        if game.top.seed > 0
+            && game.top.seed == espn_top_seed
+            && game.bottom.seed == espn_bottom_seed
+        {
+            score += 1;
+        }
+
+        if let (Some(a), Some(b)) = (game.start_time, espn.start_time) {
+            let days_apart = (a.date_naive() - b.date_naive()).num_days().abs();
+            if days_apart > 1 && !already_bridged {
+                continue;
+            }
+            if days_apart == 0 {
+                score += 1;
+            }
+        }
+
+        let candidate = BridgeMatch {
+            game_id: game.id.clone(),
+            swapped,
+        };
+        match &best {
+            Some((best_score, _)) if score < *best_score => {}
+            Some((best_score, _)) if score == *best_score => tied = true,
+            _ => {
+                best = Some((score, candidate));
+                tied = false;
+            }
+        }
+    }
+
+    if tied { None } else { best.map(|(_, m)| m) }
+}
+
+/// Merge live fields from an ESPN scoreboard game into a bracket game.
+///
+/// Team identities stay NCAA-sourced; only the ESPN ID, status, clock, score and
+/// winner are copied. Scores are re-oriented and the winner translated to the
+/// bracket's team ID when ESPN lists the teams in the opposite order.
+pub fn apply_update(game: &mut Game, espn: &Game, swapped: bool) {
+    game.espn_id = Some(espn.espn_id.clone().unwrap_or_else(|| espn.id.clone()));
+    game.status = espn.status.clone();
+    game.period = espn.period;
+    game.clock = espn.clock.clone();
+    if espn.start_time.is_some() {
+        game.start_time = espn.start_time;
+    }
+    if espn.location.is_some() {
+        game.location = espn.location.clone();
+    }
+    game.score = espn
+        .score
+        .map(|(t, b)| if swapped { (b, t) } else { (t, b) });
+
+    let espn_top_id = espn.top.team.as_ref().map(|t| t.id.as_str());
+    let espn_bottom_id = espn.bottom.team.as_ref().map(|t| t.id.as_str());
+    let winner_is_espn_top = match espn.winner_id.as_deref() {
+        Some(id) if Some(id) == espn_top_id => Some(true),
+        Some(id) if Some(id) == espn_bottom_id => Some(false),
+        _ => None,
+    };
+    game.winner_id = winner_is_espn_top.and_then(|is_top| {
+        let bracket_slot = if is_top != swapped {
+            &game.top
+        } else {
+            &game.bottom
+        };
+        bracket_slot.team.as_ref().map(|t| t.id.clone())
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GameStatus, Gender, Region, Round, RoundKind, TeamSeed};
+
+    fn team(id: &str, name: &str, short: &str) -> Team {
+        Team {
+            id: id.into(),
+            name: name.into(),
+            short_name: short.into(),
+            abbrev: String::new(),
+            color: None,
+        }
+    }
+
+    fn slot(seed: u8, t: Team) -> TeamSeed {
+        TeamSeed {
+            seed,
+            team: Some(t),
+            placeholder: None,
+        }
+    }
+
+    fn ncaa_bracket() -> Tournament {
+        Tournament {
+            id: "ncaa-2026".into(),
+            name: "Championship".into(),
+            year: 2026,
+            gender: Gender::Men,
+            regions: vec![Region {
+                id: "west".into(),
+                name: "West".into(),
+                rounds: vec![Round {
+                    kind: RoundKind::First,
+                    games: vec![
+                        Game {
+                            id: "201".into(),
+                            top: slot(1, team("uconn", "Connecticut", "UConn")),
+                            bottom: slot(16, team("stetson", "Stetson", "Stetson")),
+                            ..Default::default()
+                        },
+                        Game {
+                            id: "202".into(),
+                            top: slot(2, team("stjohns", "St. John's (NY)", "St. John's")),
+                            bottom: slot(15, team("omaha", "Omaha", "Omaha")),
+                            ..Default::default()
+                        },
+                    ],
+                }],
+            }],
+        }
+    }
+
+    #[test]
+    fn normalize_resolves_aliases_and_saint_prefix() {
+        assert_eq!(
+            normalize_team_name("UConn"),
+            normalize_team_name("Connecticut")
+        );
+        assert_eq!(
+            normalize_team_name("St. Mary's"),
+            normalize_team_name("Saint Mary's")
+        );
+        assert_eq!(normalize_team_name("Texas A&M"), "texas aandm");
+        assert_eq!(normalize_team_name("Michigan St."), "michigan state");
+    }
+
+    #[test]
+    fn espn_display_name_with_mascot_matches_by_prefix() {
+        let ncaa = team("duke", "Duke", "Duke");
+        let espn = team("150", "Duke Blue Devils", "Duke Blue Devils");
+        assert_eq!(team_match_score(&ncaa, &espn), 1);
+    }
+
+    #[test]
+    fn school_name_prefix_does_not_match_a_different_school() {
+        let texas = team("texas", "Texas", "Texas");
+        let tech = team("2641", "Texas Tech Red Raiders", "Texas Tech");
+        assert_eq!(team_match_score(&texas, &tech), 0);
+        let tech_no_short = team("2641", "Texas Tech Red Raiders", "Texas Tech Red Raiders");
+        assert_eq!(team_match_score(&texas, &tech_no_short), 0);
+        let fau = team("2226", "Florida Atlantic Owls", "Florida Atlantic Owls");
+        assert_eq!(
+            team_match_score(&team("florida", "Florida", "Florida"), &fau),
+            0
+        );
+        let longhorns = team("251", "Texas Longhorns", "Texas Longhorns");
+        assert_eq!(team_match_score(&texas, &longhorns), 1);
+    }
+
+    #[test]
+    fn scoreboard_game_merges_into_ncaa_bracket_with_swapped_orientation() {
+        let mut bracket = ncaa_bracket();
+        let espn = Game {
+            id: "401746018".into(),
+            espn_id: Some("401746018".into()),
+            top: slot(0, team("2463", "Stetson Hatters", "Stetson")),
+            bottom: slot(0, team("41", "UConn Huskies", "UConn")),
+            status: GameStatus::Final,
+            score: Some((58, 91)),
+            winner_id: Some("41".into()),
+            ..Default::default()
+        };
+
+        bracket.merge_updates(vec![espn]);
+
+        let game = bracket.find_game_mut("201").unwrap();
+        assert_eq!(game.espn_id.as_deref(), Some("401746018"));
+        assert_eq!(game.score, Some((91, 58)));
+        assert_eq!(game.winner_id.as_deref(), Some("uconn"));
+        assert_eq!(game.status, GameStatus::Final);
+        assert_eq!(game.top.team.as_ref().unwrap().id, "uconn");
+    }
+
+    #[test]
+    fn unrelated_scoreboard_game_is_ignored() {
+        let bracket = ncaa_bracket();
+        let espn = Game {
+            id: "1".into(),
+            top: slot(0, team("9", "Gonzaga Bulldogs", "Gonzaga")),
+            bottom: slot(0, team("8", "UConn Huskies", "UConn")),
+            ..Default::default()
+        };
+        assert_eq!(match_game(&bracket, &espn), None);
+    }
+}
@@ -1,40 +1,41 @@
-use crate::espn::{ScoreboardResponse, SummaryResponse, TournamentsResponse};
-use crate::henrygd::HenrygdResponse;
+use crate::espn::{SummaryResponse, TournamentsResponse};
+use crate::source::{
+    BracketSource, ESPN_URL, Embedded, Espn, HENRYGD_URL, Henrygd, Http, LiveSource, LocalFile,
+    SourceKind,
+};
+use crate::topology::Slot;
 use crate::{
-    BoxScore, Game, GameDetail, GameStatus, Play, PlayerLine, Region, Round, RoundKind, Team,
-    TeamSeed, Tournament,
+    BoxScore, Game, GameDetail, GameStatus, Gender, Play, PlayerLine, Region, Round, RoundKind,
+    Team, TeamSeed, Tournament,
 };
 use chrono::{DateTime, Datelike, Utc};
-use reqwest::Client;
 use std::fmt;
-use std::time::Duration;
+use std::sync::Arc;
 
 pub type ApiResult<T> = Result<T, ApiError>;
 
-const ESPN_SITE_V2: &str =
-    "https://site.api.espn.com/apis/site/v2/sports/basketball/mens-college-basketball";
-const ESPN_V2: &str =
-    "https://site.api.espn.com/apis/v2/sports/basketball/mens-college-basketball";
-const NCAA_HENRYGD: &str = "https://ncaa-api.henrygd.me";
 const FALLBACK_BRACKET_YEAR: i32 = 2025;
 const FALLBACK_BRACKET_JSON: &str = include_str!("../../2025_bracket.json");
 
-/// NCAA API client backed by ESPN's public endpoints.
+/// NCAA API client: a chain of bracket sources plus one live source.
 #[derive(Debug, Clone)]
 pub struct NcaaApi {
-    client: Client,
-    timeout: Duration,
+    bracket_sources: Vec<Arc<dyn BracketSource>>,
+    live: Arc<dyn LiveSource>,
+    gender: Gender,
+    /// Local tournament snapshot; `MMTUI_BRACKET_JSON` by default.
+    bracket_json: Option<String>,
 }
 
 impl Default for NcaaApi {
     fn default() -> Self {
-        Self {
-            client: Client::builder()
-                .user_agent("mmtui/0.1 (terminal bracket viewer)")
-                .build()
-                .unwrap_or_default(),
-            timeout: Duration::from_secs(10),
-        }
+        Self::with_sources(
+            Gender::Men,
+            &SourceKind::DEFAULT_ORDER,
+            HENRYGD_URL,
+            ESPN_URL,
+        )
+        .with_bracket_json(std::env::var("MMTUI_BRACKET_JSON").ok())
     }
 }
 
@@ -64,119 +65,116 @@ impl NcaaApi {
         Self::default()
     }
 
-    /// Fetch the current NCAA Men's Basketball Tournament bracket.
+    /// The built-in sources for one tournament: brackets from `order`, live
+    /// data from ESPN.
+    pub fn with_sources(
+        gender: Gender,
+        order: &[SourceKind],
+        henrygd_url: &str,
+        espn_url: &str,
+    ) -> Self {
+        let http = Http::default();
+        let espn = Arc::new(Espn::new(http.clone(), espn_url, gender));
+        let bracket_sources = order
+            .iter()
+            .map(|kind| -> Arc<dyn BracketSource> {
+                match kind {
+                    SourceKind::Henrygd => {
+                        Arc::new(Henrygd::new(http.clone(), henrygd_url, gender))
+                    }
+                    SourceKind::Espn => espn.clone(),
+                    SourceKind::Embedded => Arc::new(Embedded::new(gender)),
+                }
+            })
+            .collect();
+        Self {
+            bracket_sources,
+            live: espn,
+            gender,
+            bracket_json: None,
+        }
+    }
+
+    /// Replace the bracket fallback chain; sources are tried in order.
+    pub fn with_bracket_sources(mut self, sources: Vec<Arc<dyn BracketSource>>) -> Self {
+        self.bracket_sources = sources;
+        self
+    }
+
+    /// Replace where scores and game detail come from.
+    pub fn with_live_source(mut self, source: Arc<dyn LiveSource>) -> Self {
+        self.live = source;
+        self
+    }
+
+    /// Load the bracket from this ESPN-format JSON file instead of the
+    /// fallback chain.
+    pub fn with_bracket_json(mut self, path: Option<String>) -> Self {
+        self.bracket_json = path;
+        self
+    }
+
+    /// Which tournament the built-in sources were set up for.
+    pub fn gender(&self) -> Gender {
+        self.gender
+    }
+
+    /// Fetch the current NCAA Basketball Tournament bracket.
     ///
-    /// Fallback chain:
-    /// 1) `MMTUI_BRACKET_JSON` env var — load from local ESPN-format JSON file.
-    /// 2) NCAA henrygd API — authoritative bracket topology for current year.
-    /// 3) ESPN tournaments API — bracket data for current and adjacent years.
-    /// 4) Embedded 2025 JSON — last-resort offline fallback.
+    /// A [`with_bracket_json`](Self::with_bracket_json) path (by default the
+    /// `MMTUI_BRACKET_JSON` env var) is loaded on its own. Otherwise each
+    /// bracket source is asked for this season's bracket in turn (by default
+    /// henrygd, then ESPN, then the embedded 2025 snapshot).
     pub async fn fetch_tournament(&self) -> ApiResult<Tournament> {
-        if let Ok(path) = std::env::var("MMTUI_BRACKET_JSON")
+        let season_year = season_tournament_year(Utc::now()) as u16;
+        if let Some(path) = &self.bracket_json
             && !path.trim().is_empty()
         {
-            let content = std::fs::read_to_string(&path)
-                .map_err(|e| ApiError::NotFound(format!("could not read {path}: {e}")))?;
-            let raw: TournamentsResponse = serde_json::from_str(&content)
-                .map_err(|e| ApiError::NotFound(format!("invalid tournament json at {path}: {e}")))?;
-
-            let year =
-                infer_year_from_path(&path).unwrap_or_else(|| season_tournament_year(Utc::now()) as u16);
-            let entry = select_tournament_entry(raw.tournaments.unwrap_or_default(), i32::from(year))?;
-            return Ok(map_tournament(entry, year));
+            return LocalFile::new(path.clone(), self.gender)
+                .bracket(season_year)
+                .await;
         }
+        self.first_bracket(season_year, |_| true).await
+    }
 
-        let season_year = season_tournament_year(Utc::now()) as u16;
-
-        // NCAA henrygd: authoritative bracket topology.
-        let ncaa_url = format!("{NCAA_HENRYGD}/brackets/basketball-men/d1/{season_year}");
-        if let Ok(raw) = self.get::<HenrygdResponse>(&ncaa_url).await {
-            if let Some(champ) = raw.championships.into_iter().next() {
-                if !champ.games.is_empty() {
-                    return Ok(map_ncaa_championship(champ));
-                }
-            }
-        }
+    /// Fetch the bracket for a specific year from the first source that has
+    /// it. Useful for pre-loading the 2026 bracket structure before Selection
+    /// Sunday, or for past tournaments.
+    pub async fn fetch_ncaa_bracket(&self, year: u16) -> ApiResult<Tournament> {
+        self.first_bracket(year, |t| t.year == year).await
+    }
 
-        // ESPN fallback: bracket data for current and adjacent years.
-        let candidate_years = candidate_tournament_years(Utc::now());
+    async fn first_bracket(
+        &self,
+        year: u16,
+        accept: impl Fn(&Tournament) -> bool,
+    ) -> ApiResult<Tournament> {
         let mut last_error: Option<ApiError> = None;
-        for year in candidate_years {
-            let url = format!("{ESPN_V2}/tournaments?limit=25&year={year}");
-            match self.get::<TournamentsResponse>(&url).await {
-                Ok(raw) => match select_tournament_entry(raw.tournaments.unwrap_or_default(), year) {
-                    Ok(entry) => return Ok(map_tournament(entry, year as u16)),
-                    Err(e) => last_error = Some(e),
-                },
+        for source in &self.bracket_sources {
+            match source.bracket(year).await {
+                Ok(tournament) if accept(&tournament) => return Ok(tournament),
+                Ok(tournament) => {
+                    last_error = Some(ApiError::NotFound(format!(
+                        "{} has {} instead of {year}",
+                        source.name(),
+                        tournament.year
+                    )))
+                }
                 Err(e) => last_error = Some(e),
             }
         }
-
-        if let Ok(tournament) = load_embedded_fallback_tournament() {
-            return Ok(tournament);
-        }
-
-        Err(last_error.unwrap_or_else(|| {
-            ApiError::NotFound("NCAA Tournament not found in current/adjacent years".into())
-        }))
-    }
-
-    /// Fetch the bracket skeleton from the NCAA henrygd API for a specific year.
-    /// Useful for pre-loading the 2026 bracket structure before Selection Sunday.
-    pub async fn fetch_ncaa_bracket(&self, year: u16) -> ApiResult<Tournament> {
-        let url = format!("{NCAA_HENRYGD}/brackets/basketball-men/d1/{year}");
-        let raw = self.get::<HenrygdResponse>(&url).await?;
-        let champ = raw
-            .championships
-            .into_iter()
-            .next()
-            .ok_or_else(|| ApiError::NotFound(format!("no championship data for {year}")))?;
-        Ok(map_ncaa_championship(champ))
+        Err(last_error
+            .unwrap_or_else(|| ApiError::NotFound(format!("NCAA Tournament not found for {year}"))))
     }
 
     /// Fetch live scores for games currently in the NCAA tournament.
-    /// groups=100 filters to tournament games on ESPN's scoreboard.
     pub async fn fetch_scoreboard(&self) -> ApiResult<Vec<Game>> {
-        let url = format!("{ESPN_SITE_V2}/scoreboard?groups=100&limit=50");
-        let raw: ScoreboardResponse = self.get(&url).await?;
-        let games = raw
-            .events
-            .unwrap_or_default()
-            .iter()
-            .map(map_event_to_game)
-            .collect();
-        Ok(games)
+        self.live.scoreboard().await
     }
 
     /// Fetch detailed game data (play-by-play + box score).
     pub async fn fetch_game_detail(&self, game_id: &str) -> ApiResult<GameDetail> {
-        let url = format!("{ESPN_SITE_V2}/summary?event={game_id}");
-        let raw: SummaryResponse = self.get(&url).await?;
-        Ok(map_summary(game_id, raw))
-    }
-
-    async fn get<T: Default + serde::de::DeserializeOwned>(&self, url: &str) -> ApiResult<T> {
-        let response = self
-            .client
-            .get(url)
-            .timeout(self.timeout)
-            .send()
-            .await
-            .map_err(|e| ApiError::Network(e, url.to_owned()))?;
-
-        match response.error_for_status() {
-            Ok(res) => res
-                .json::<T>()
-                .await
-                .map_err(|e| ApiError::Parsing(e, url.to_owned())),
-            Err(e) => {
-                if e.status().map(|s| s.is_client_error()).unwrap_or(false) {
-                    Ok(T::default())
-                } else {
-                    Err(ApiError::Api(e, url.to_owned()))
-                }
-            }
-        }
+        self.live.game_detail(game_id).await
     }
 }
 
@@ -188,11 +186,14 @@ impl NcaaApi {
 ///
 /// ID strategy:
 ///   - `Game.id` = bracketPositionId string (stable bracket anchor)
-///   - `Game.espn_id` = None (populated later by team-matching bridge)
+///   - `Game.espn_id` = None (populated by `bridge` when scoreboard games merge in)
 ///
 /// Region grouping: games are bucketed by sectionId. Region names come from
 /// the championship's `regions[]` array; fall back to "Region {n}" pre-Selection Sunday.
-fn map_ncaa_championship(champ: crate::henrygd::HenrygdChampionship) -> Tournament {
+pub(crate) fn map_ncaa_championship(
+    champ: crate::henrygd::HenrygdChampionship,
+    gender: Gender,
+) -> Tournament {
     use std::collections::HashMap;
 
     // Build sectionId → region name lookup; fall back to "Region {n}".
@@ -266,19 +267,27 @@ fn map_ncaa_championship(champ: crate::henrygd::HenrygdChampionship) -> Tourname
         });
     }
 
-    Tournament {
+    let mut tournament = Tournament {
         id: format!("ncaa-{}", champ.year),
         name: champ.title,
         year: champ.year,
+        gender,
         regions,
-    }
+    };
+    tournament.link_games();
+    tournament.advance_winners();
+    tournament
 }
 
-/// Sort a round map into a Vec<Round> ordered by RoundKind.
+/// Sort a round map into a Vec<Round> ordered by RoundKind, with games in
+/// bracketPositionId order so adjacent games feed the same next-round game.
 fn build_rounds(rounds_map: std::collections::HashMap<RoundKind, Vec<Game>>) -> Vec<Round> {
     let mut rounds: Vec<Round> = rounds_map
         .into_iter()
-        .map(|(kind, games)| Round { kind, games })
+        .map(|(kind, mut games)| {
+            games.sort_by_key(|g| g.id.parse::<u32>().unwrap_or(u32::MAX));
+            Round { kind, games }
+        })
         .collect();
     rounds.sort_by_key(|r| r.kind);
     rounds
@@ -300,7 +309,11 @@ fn map_ncaa_game(g: &crate::henrygd::HenrygdGame) -> Game {
 
     Game {
         id: g.bracket_position_id.to_string(),
-        espn_id: None, // Populated later by team-matching bridge.
+        espn_id: None, // Populated by the bridge on scoreboard merge.
+        next_game_id: g
+            .victor_bracket_position_id
+            .filter(|&id| id != 0)
+            .map(|id| id.to_string()),
         top,
         bottom,
         status,
@@ -308,11 +321,52 @@ fn map_ncaa_game(g: &crate::henrygd::HenrygdGame) -> Game {
         winner_id,
         period: None,
         clock: None,
-        start_time: None,
+        start_time: parse_ncaa_start(&g.start_date, &g.start_time),
         location: None,
     }
 }
 
+/// Parse henrygd `startDate` ("03/20/2025") + `startTime` ("12:15 PM ET").
+///
+/// "ET" is America/New_York: see [`eastern_offset`]. Returns None when either
+/// half is missing or still "TBA".
+fn parse_ncaa_start(date: &str, time: &str) -> Option<DateTime<Utc>> {
+    use chrono::{NaiveDate, NaiveTime, TimeZone};
+
+    let date = NaiveDate::parse_from_str(date.trim(), "%m/%d/%Y")
+        .or_else(|_| NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d"))
+        .ok()?;
+    let time = time.trim().trim_end_matches("ET").trim().to_uppercase();
+    let time = NaiveTime::parse_from_str(&time, "%I:%M %p")
+        .or_else(|_| NaiveTime::parse_from_str(&time, "%I:%M%p"))
+        .ok()?;
+    let local = date.and_time(time);
+    eastern_offset(local)?
+        .from_local_datetime(&local)
+        .single()
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
+/// UTC offset of America/New_York at a local wall-clock time.
+///
+/// US rules since 2007: EDT (UTC-4) from 2:00 on the second Sunday of March to
+/// 2:00 on the first Sunday of November, EST (UTC-5) otherwise.
+fn eastern_offset(local: chrono::NaiveDateTime) -> Option<chrono::FixedOffset> {
+    use chrono::{FixedOffset, NaiveDate, Weekday};
+
+    let year = local.year();
+    let dst_start =
+        NaiveDate::from_weekday_of_month_opt(year, 3, Weekday::Sun, 2)?.and_hms_opt(2, 0, 0)?;
+    let dst_end =
+        NaiveDate::from_weekday_of_month_opt(year, 11, Weekday::Sun, 1)?.and_hms_opt(2, 0, 0)?;
+    let hours = if (dst_start..dst_end).contains(&local) {
+        4
+    } else {
+        5
+    };
+    FixedOffset::west_opt(hours * 3600)
+}
+
 fn map_ncaa_team(t: &crate::henrygd::HenrygdTeam) -> TeamSeed {
     let team = t.team_id.as_ref().map(|id| Team {
         id: id.clone(),
@@ -333,7 +387,7 @@ fn map_ncaa_team(t: &crate::henrygd::HenrygdTeam) -> TeamSeed {
 // Mapping: ESPN wire types → clean domain types
 // ---------------------------------------------------------------------------
 
-fn infer_year_from_path(path: &str) -> Option<u16> {
+pub(crate) fn infer_year_from_path(path: &str) -> Option<u16> {
     path.split(|c: char| !c.is_ascii_digit())
         .find_map(|token| {
             if token.len() == 4 {
@@ -345,14 +399,19 @@ fn infer_year_from_path(path: &str) -> Option<u16> {
         .filter(|y| (2000..=2100).contains(y))
 }
 
-fn season_tournament_year(now: DateTime<Utc>) -> i32 {
+pub fn season_tournament_year(now: DateTime<Utc>) -> i32 {
     // NCAA tournament championship year tracks the season year. In Nov/Dec,
     // queries should target the next calendar year.
     if now.month() >= 11 { now.year() + 1 } else { now.year() }
 }
 
+#[cfg(test)]
 fn candidate_tournament_years(now: DateTime<Utc>) -> Vec<i32> {
-    let season_year = season_tournament_year(now);
+    candidate_years(season_tournament_year(now))
+}
+
+/// `season_year` and its neighbours, nearest first, plus the 2025 snapshot year.
+pub(crate) fn candidate_years(season_year: i32) -> Vec<i32> {
     let mut years = vec![season_year, season_year - 1, season_year + 1, 2025];
     years.sort_unstable();
     years.dedup();
@@ -365,14 +424,19 @@ fn candidate_tournament_years(now: DateTime<Utc>) -> Vec<i32> {
     years
 }
 
-fn load_embedded_fallback_tournament() -> ApiResult<Tournament> {
+pub(crate) fn load_embedded_fallback_tournament() -> ApiResult<Tournament> {
     let raw: TournamentsResponse = serde_json::from_str(FALLBACK_BRACKET_JSON)
         .map_err(|e| ApiError::NotFound(format!("invalid embedded fallback bracket json: {e}")))?;
-    let entry = select_tournament_entry(raw.tournaments.unwrap_or_default(), FALLBACK_BRACKET_YEAR)?;
-    Ok(map_tournament(entry, FALLBACK_BRACKET_YEAR as u16))
+    let entry =
+        select_tournament_entry(raw.tournaments.unwrap_or_default(), FALLBACK_BRACKET_YEAR)?;
+    Ok(map_tournament(
+        entry,
+        FALLBACK_BRACKET_YEAR as u16,
+        Gender::Men,
+    ))
 }
 
-fn select_tournament_entry(
+pub(crate) fn select_tournament_entry(
     mut entries: Vec<crate::espn::TournamentEntry>,
     year: i32,
 ) -> ApiResult<crate::espn::TournamentEntry> {
@@ -413,191 +477,6 @@ fn select_tournament_entry(
     )))
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use chrono::TimeZone;
-
-    #[test]
-    fn season_year_uses_current_year_before_november() {
-        let dt = Utc.with_ymd_and_hms(2026, 2, 24, 12, 0, 0).unwrap();
-        assert_eq!(season_tournament_year(dt), 2026);
-    }
-
-    #[test]
-    fn season_year_rolls_forward_in_november_and_december() {
-        let nov = Utc.with_ymd_and_hms(2026, 11, 1, 0, 0, 0).unwrap();
-        let dec = Utc.with_ymd_and_hms(2026, 12, 31, 23, 59, 59).unwrap();
-        assert_eq!(season_tournament_year(nov), 2027);
-        assert_eq!(season_tournament_year(dec), 2027);
-    }
-
-    #[test]
-    fn candidate_years_are_nearest_first() {
-        let dt = Utc.with_ymd_and_hms(2026, 2, 24, 12, 0, 0).unwrap();
-        assert_eq!(candidate_tournament_years(dt), vec![2026, 2025, 2027]);
-    }
-
-    #[test]
-    fn candidate_years_always_include_2025_snapshot_fallback() {
-        let dt = Utc.with_ymd_and_hms(2027, 2, 24, 12, 0, 0).unwrap();
-        assert!(candidate_tournament_years(dt).contains(&2025));
-    }
-
-    #[test]
-    fn embedded_fallback_tournament_parses() {
-        let t = load_embedded_fallback_tournament().expect("fallback bracket should parse");
-        assert_eq!(t.year, 2025);
-        assert!(!t.regions.is_empty());
-    }
-
-    #[test]
-    fn test_round_number_mapping() {
-        assert_eq!(round_number_to_kind(1), RoundKind::FirstFour);
-        assert_eq!(round_number_to_kind(2), RoundKind::First);
-        assert_eq!(round_number_to_kind(6), RoundKind::FinalFour);
-        assert_eq!(round_number_to_kind(7), RoundKind::Championship);
-    }
-
-    #[test]
-    fn test_parse_status() {
-        assert_eq!(parse_status("STATUS_IN_PROGRESS"), GameStatus::InProgress);
-        assert_eq!(parse_status("STATUS_FINAL"), GameStatus::Final);
-        assert_eq!(parse_status("STATUS_SCHEDULED"), GameStatus::Scheduled);
-        assert_eq!(parse_status("STATUS_POSTPONED"), GameStatus::Postponed);
-    }
-
-    #[test]
-    fn test_round_kind_navigation() {
-        assert_eq!(RoundKind::First.next(), Some(RoundKind::Second));
-        assert_eq!(RoundKind::Championship.next(), None);
-        assert_eq!(RoundKind::FirstFour.prev(), None);
-        assert_eq!(RoundKind::FinalFour.is_final_four(), true);
-        assert_eq!(RoundKind::Elite8.is_final_four(), false);
-    }
-
-    // -----------------------------------------------------------------------
-    // NCAA henrygd adapter tests
-    // -----------------------------------------------------------------------
-
-    #[test]
-    fn ncaa_position_to_round_covers_all_rounds() {
-        assert_eq!(round_number_to_kind(101 / 100), RoundKind::FirstFour);
-        assert_eq!(round_number_to_kind(201 / 100), RoundKind::First);
-        assert_eq!(round_number_to_kind(301 / 100), RoundKind::Second);
-        assert_eq!(round_number_to_kind(401 / 100), RoundKind::Sweet16);
-        assert_eq!(round_number_to_kind(501 / 100), RoundKind::Elite8);
-        assert_eq!(round_number_to_kind(601 / 100), RoundKind::FinalFour);
-        assert_eq!(round_number_to_kind(701 / 100), RoundKind::Championship);
-    }
-
-    #[test]
-    fn ncaa_game_with_empty_teams_produces_tba_slots() {
-        let raw = crate::henrygd::HenrygdGame {
-            bracket_position_id: 101,
-            game_state: "P".into(),
-            teams: vec![],
-            section_id: 1,
-            ..Default::default()
-        };
-        let game = map_ncaa_game(&raw);
-        assert_eq!(game.id, "101");
-        assert!(game.espn_id.is_none(), "espn_id must be None pre-bridge");
-        assert!(game.top.team.is_none(), "top team should be None when teams is empty");
-        assert!(game.bottom.team.is_none(), "bottom team should be None when teams is empty");
-        assert_eq!(game.top.placeholder.as_deref(), Some("TBA"));
-        assert_eq!(game.status, GameStatus::Scheduled);
-    }
-
-    #[test]
-    fn ncaa_game_with_teams_maps_correctly() {
-        let raw = crate::henrygd::HenrygdGame {
-            bracket_position_id: 201,
-            game_state: "F".into(),
-            teams: vec![
-                crate::henrygd::HenrygdTeam {
-                    team_id: Some("uconn".into()),
-                    name: Some("Connecticut".into()),
-                    short_name: Some("UConn".into()),
-                    seed: Some(1),
-                    winner: Some(true),
-                    description: None,
-                },
-                crate::henrygd::HenrygdTeam {
-                    team_id: Some("stetson".into()),
-                    name: Some("Stetson".into()),
-                    short_name: None,
-                    seed: Some(16),
-                    winner: Some(false),
-                    description: None,
-                },
-            ],
-            section_id: 2,
-            ..Default::default()
-        };
-        let game = map_ncaa_game(&raw);
-        assert_eq!(game.id, "201");
-        assert_eq!(game.winner_id.as_deref(), Some("uconn"));
-        assert_eq!(game.top.seed, 1);
-        assert_eq!(game.bottom.seed, 16);
-        assert_eq!(game.status, GameStatus::Final);
-    }
-
-    #[test]
-    fn ncaa_championship_empty_region_titles_fall_back_to_region_n() {
-        use crate::henrygd::{HenrygdChampionship, HenrygdGame, HenrygdRegion};
-        let champ = HenrygdChampionship {
-            title: "2026 DI Men's Basketball Championship".into(),
-            year: 2026,
-            games: vec![HenrygdGame {
-                bracket_position_id: 201,
-                game_state: "P".into(),
-                section_id: 1,
-                ..Default::default()
-            }],
-            rounds: vec![],
-            regions: vec![HenrygdRegion {
-                id: "4031".into(),
-                section_id: 1,
-                title: String::new(), // empty pre-Selection Sunday
-                region_code: "TL".into(),
-            }],
-        };
-        let tournament = map_ncaa_championship(champ);
-        assert_eq!(tournament.year, 2026);
-        let region = tournament.regions.iter().find(|r| r.id != "national");
-        assert!(region.is_some());
-        assert!(
-            region.unwrap().name.starts_with("Region "),
-            "empty title should fall back to 'Region N', got: {}",
-            region.unwrap().name
-        );
-    }
-
-    #[test]
-    fn ncaa_championship_national_section_maps_to_national_region() {
-        use crate::henrygd::{HenrygdChampionship, HenrygdGame};
-        let champ = HenrygdChampionship {
-            title: "2026 Championship".into(),
-            year: 2026,
-            games: vec![HenrygdGame {
-                bracket_position_id: 701,
-                game_state: "P".into(),
-                section_id: 6,
-                ..Default::default()
-            }],
-            rounds: vec![],
-            regions: vec![],
-        };
-        let tournament = map_ncaa_championship(champ);
-        let national = tournament.regions.iter().find(|r| r.id == "national");
-        assert!(national.is_some(), "sectionId 6 must produce the National region");
-        let rounds = &national.unwrap().rounds;
-        assert_eq!(rounds.len(), 1);
-        assert_eq!(rounds[0].kind, RoundKind::Championship);
-    }
-}
-
 fn to_title_case(s: &str) -> String {
     let mut chars = s.chars();
     match chars.next() {
@@ -606,8 +485,14 @@ fn to_title_case(s: &str) -> String {
     }
 }
 
-fn map_tournament(entry: crate::espn::TournamentEntry, year: u16) -> Tournament {
-    let name = entry.name.unwrap_or_else(|| "NCAA Tournament".into());
+pub(crate) fn map_tournament(
+    entry: crate::espn::TournamentEntry,
+    year: u16,
+    gender: Gender,
+) -> Tournament {
+    let name = entry
+        .name
+        .unwrap_or_else(|| format!("{} NCAA Tournament", gender.label()));
     let id = entry.id.clone();
 
     let bracket = entry.bracket.unwrap_or_default();
@@ -675,7 +560,17 @@ fn map_tournament(entry: crate::espn::TournamentEntry, year: u16) -> Tournament
         });
     }
 
-    Tournament { id, name, year, regions: built_regions }
+    // ESPN has no victor position, so parent/child links come from bracket shape.
+    let mut tournament = Tournament {
+        id,
+        name,
+        year,
+        gender,
+        regions: built_regions,
+    };
+    tournament.link_games();
+    tournament.advance_winners();
+    tournament
 }
 
 fn round_number_to_kind(number: u32) -> RoundKind {
@@ -732,6 +627,7 @@ fn map_matchup(m: &crate::espn::EspnMatchup) -> Game {
     Game {
         espn_id: Some(id.clone()),
         id,
+        next_game_id: None,
         top,
         bottom,
         status,
@@ -744,7 +640,7 @@ fn map_matchup(m: &crate::espn::EspnMatchup) -> Game {
     }
 }
 
-fn map_event_to_game(event: &crate::espn::EspnEvent) -> Game {
+pub(crate) fn map_event_to_game(event: &crate::espn::EspnEvent) -> Game {
     let id = event.id.clone().unwrap_or_default();
 
     let status = event
@@ -788,8 +684,8 @@ fn map_event_to_game(event: &crate::espn::EspnEvent) -> Game {
 
     // Derive score from competitors
     let score = {
-        let ts = top.team.as_ref().and_then(|_| top.score.as_ref());
-        let bs = bottom.team.as_ref().and_then(|_| bottom.score.as_ref());
+        let ts = top.team.as_ref().and(top.score.as_ref());
+        let bs = bottom.team.as_ref().and(bottom.score.as_ref());
         if let (Some(t), Some(b)) = (ts, bs) {
             t.parse::<u16>().ok().zip(b.parse::<u16>().ok())
         } else {
@@ -805,8 +701,9 @@ fn map_event_to_game(event: &crate::espn::EspnEvent) -> Game {
     Game {
         espn_id: Some(id.clone()),
         id,
-        top: map_competitor(&top),
-        bottom: map_competitor(&bottom),
+        next_game_id: None,
+        top: map_competitor(top),
+        bottom: map_competitor(bottom),
         status,
         score,
         winner_id,
@@ -898,43 +795,75 @@ fn parse_status(s: &str) -> GameStatus {
     }
 }
 
-fn map_summary(game_id: &str, raw: SummaryResponse) -> GameDetail {
+pub(crate) fn map_summary(game_id: &str, raw: SummaryResponse) -> GameDetail {
     let plays = raw
         .plays
         .unwrap_or_default()
         .into_iter()
-        .map(|p| Play {
-            period: p.period.and_then(|x| x.number).unwrap_or_default(),
-            clock: p
-                .clock
-                .and_then(|c| c.display_value)
-                .unwrap_or_default(),
-            description: p.text.unwrap_or_default(),
-            home_score: p.home_score.unwrap_or_default(),
-            away_score: p.away_score.unwrap_or_default(),
+        .map(|p| {
+            let scoring = p.scoring_play.unwrap_or(false);
+            let coordinate = p
+                .coordinate
+                .and_then(|c| Some((c.x?, c.y?)))
+                .filter(|&(x, y)| p.shooting_play == Some(true) && x >= 0.0 && y >= 0.0);
+            Play {
+                period: p.period.and_then(|x| x.number).unwrap_or_default(),
+                clock: p.clock.and_then(|c| c.display_value).unwrap_or_default(),
+                description: p.text.unwrap_or_default(),
+                home_score: p.home_score.unwrap_or_default(),
+                away_score: p.away_score.unwrap_or_default(),
+                team_id: p.team.and_then(|t| t.id),
+                play_type: p.play_type.and_then(|t| t.text).unwrap_or_default(),
+                scoring,
+                points: if scoring {
+                    p.score_value.unwrap_or_default()
+                } else {
+                    0
+                },
+                coordinate,
+            }
         })
         .collect();
 
-    let mut home_box = BoxScore::default();
-    let mut away_box = BoxScore::default();
+    // The box score lists the teams in no promised order; the header says
+    // which one is at home.
+    let home_id = raw
+        .header
+        .and_then(|h| h.competitions)
+        .and_then(|c| c.into_iter().next())
+        .and_then(|c| c.competitors)
+        .unwrap_or_default()
+        .into_iter()
+        .find(|c| c.home_away.as_deref() == Some("home"))
+        .and_then(|c| c.team.and_then(|t| t.id).or(c.id));
 
-    if let Some(boxscore) = raw.boxscore {
-        let team_players = boxscore.players.unwrap_or_default();
-        for (i, team_data) in team_players.into_iter().enumerate() {
-            let box_score = build_box_score(team_data);
-            if i == 0 {
-                home_box = box_score;
-            } else {
-                away_box = box_score;
-            }
-        }
-    }
+    let mut boxes: Vec<BoxScore> = raw
+        .boxscore
+        .and_then(|b| b.players)
+        .unwrap_or_default()
+        .into_iter()
+        .map(build_box_score)
+        .collect();
+    let home_idx = home_id
+        .and_then(|id| {
+            boxes
+                .iter()
+                .position(|b| b.team.as_ref().is_some_and(|t| t.id == id))
+        })
+        .unwrap_or(0);
+    let home_box = if home_idx < boxes.len() {
+        boxes.remove(home_idx)
+    } else {
+        BoxScore::default()
+    };
+    let away_box = boxes.into_iter().next().unwrap_or_default();
 
     GameDetail {
         game_id: game_id.to_owned(),
         plays,
         home_box,
         away_box,
+        home_slot: Slot::Top,
     }
 }
 
@@ -947,30 +876,39 @@ fn build_box_score(team_data: crate::espn::EspnTeamPlayers) -> BoxScore {
         color: t.color.clone(),
     });
 
+    // Older feeds name the category "athletes"; current ones leave it
+    // unnamed, so take the first category that lists players.
     let stats_cat = team_data
         .statistics
         .unwrap_or_default()
         .into_iter()
-        .find(|s| s.name.as_deref() == Some("athletes"));
+        .find(|s| s.athletes.is_some() || s.name.as_deref() == Some("athletes"));
 
     let (players, totals) = stats_cat
         .map(|cat| {
             let keys = cat.keys.unwrap_or_default();
+            let labels = cat.labels.unwrap_or_default();
             let athletes = cat.athletes.unwrap_or_default();
             let raw_totals = cat.totals.unwrap_or_default();
 
-            let player_lines = athletes
+            let mut player_lines: Vec<PlayerLine> = athletes
                 .into_iter()
+                .filter(|a| a.did_not_play != Some(true))
                 .map(|a| {
                     let name = a
                         .athlete
                         .and_then(|ath| ath.display_name)
                         .unwrap_or_default();
-                    parse_player_stats(name, &a.stats.unwrap_or_default(), &keys)
+                    let mut line =
+                        parse_player_stats(name, &a.stats.unwrap_or_default(), &keys, &labels);
+                    line.starter = a.starter.unwrap_or(false);
+                    line
                 })
                 .collect();
+            // Stable, so each group keeps ESPN's order.
+            player_lines.sort_by_key(|p| !p.starter);
 
-            let totals_line = parse_player_stats("TOTALS".into(), &raw_totals, &keys);
+            let totals_line = parse_player_stats("TOTALS".into(), &raw_totals, &keys, &labels);
             (player_lines, totals_line)
         })
         .unwrap_or_default();
@@ -978,24 +916,428 @@ fn build_box_score(team_data: crate::espn::EspnTeamPlayers) -> BoxScore {
     BoxScore { team, players, totals }
 }
 
-fn parse_player_stats(name: String, stats: &[String], keys: &[String]) -> PlayerLine {
-    let get = |key: &str| -> String {
+/// Read one stat line. ESPN identifies columns by long keys
+/// (`"fieldGoalsMade-fieldGoalsAttempted"`) with short labels (`"FG"`)
+/// alongside; older payloads used the labels as keys, so both are accepted.
+fn parse_player_stats(
+    name: String,
+    stats: &[String],
+    keys: &[String],
+    labels: &[String],
+) -> PlayerLine {
+    let get = |label: &str, key: &str| -> String {
         keys.iter()
-            .position(|k| k == key)
+            .position(|k| k == key || k == label)
+            .or_else(|| labels.iter().position(|l| l == label))
             .and_then(|i| stats.get(i))
             .cloned()
             .unwrap_or_default()
     };
 
-    let parse_u16 = |key: &str| get(key).parse::<u16>().unwrap_or_default();
+    let parse_u16 = |label: &str, key: &str| get(label, key).parse::<u16>().unwrap_or_default();
 
     PlayerLine {
         name,
-        points: parse_u16("PTS"),
-        rebounds: parse_u16("REB"),
-        assists: parse_u16("AST"),
-        minutes: get("MIN"),
-        fg: get("FG"),
-        fg3: get("3PT"),
+        starter: false,
+        minutes: get("MIN", "minutes"),
+        points: parse_u16("PTS", "points"),
+        rebounds: parse_u16("REB", "rebounds"),
+        offensive_rebounds: parse_u16("OREB", "offensiveRebounds"),
+        defensive_rebounds: parse_u16("DREB", "defensiveRebounds"),
+        assists: parse_u16("AST", "assists"),
+        steals: parse_u16("STL", "steals"),
+        blocks: parse_u16("BLK", "blocks"),
+        turnovers: parse_u16("TO", "turnovers"),
+        fouls: parse_u16("PF", "fouls"),
+        fg: get("FG", "fieldGoalsMade-fieldGoalsAttempted"),
+        fg3: get(
+            "3PT",
+            "threePointFieldGoalsMade-threePointFieldGoalsAttempted",
+        ),
+        ft: get("FT", "freeThrowsMade-freeThrowsAttempted"),
+        plus_minus: get("+/-", "plusMinus").parse().ok(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn season_year_uses_current_year_before_november() {
+        let dt = Utc.with_ymd_and_hms(2026, 2, 24, 12, 0, 0).unwrap();
+        assert_eq!(season_tournament_year(dt), 2026);
+    }
+
+    #[test]
+    fn season_year_rolls_forward_in_november_and_december() {
+        let nov = Utc.with_ymd_and_hms(2026, 11, 1, 0, 0, 0).unwrap();
+        let dec = Utc.with_ymd_and_hms(2026, 12, 31, 23, 59, 59).unwrap();
+        assert_eq!(season_tournament_year(nov), 2027);
+        assert_eq!(season_tournament_year(dec), 2027);
+    }
+
+    #[test]
+    fn candidate_years_are_nearest_first() {
+        let dt = Utc.with_ymd_and_hms(2026, 2, 24, 12, 0, 0).unwrap();
+        assert_eq!(candidate_tournament_years(dt), vec![2026, 2025, 2027]);
+    }
+
+    #[test]
+    fn candidate_years_always_include_2025_snapshot_fallback() {
+        let dt = Utc.with_ymd_and_hms(2027, 2, 24, 12, 0, 0).unwrap();
+        assert!(candidate_tournament_years(dt).contains(&2025));
+    }
+
+    #[test]
+    fn embedded_fallback_tournament_parses() {
+        let t = load_embedded_fallback_tournament().expect("fallback bracket should parse");
+        assert_eq!(t.year, 2025);
+        assert!(!t.regions.is_empty());
+    }
+
+    #[test]
+    fn embedded_fallback_links_every_game_to_its_parent() {
+        let t = load_embedded_fallback_tournament().unwrap();
+        let unlinked: Vec<_> = t.games().filter(|g| g.next_game_id.is_none()).collect();
+        assert_eq!(unlinked.len(), 1);
+        assert_eq!(unlinked[0].id, "401746082"); // Championship
+        // South's Elite Eight (Auburn) feeds the Auburn/Florida semifinal.
+        assert_eq!(
+            t.parent("401746078").map(|g| g.id.as_str()),
+            Some("401746081")
+        );
+        // First Four winner Mount St. Mary's lands in Duke's first-round game.
+        assert_eq!(
+            t.parent("401745913").map(|g| g.id.as_str()),
+            Some("401746003")
+        );
+        assert!(t.games().all(|g| t.feeders(&g.id).len() <= 2));
+    }
+
+    #[test]
+    fn test_round_number_mapping() {
+        assert_eq!(round_number_to_kind(1), RoundKind::FirstFour);
+        assert_eq!(round_number_to_kind(2), RoundKind::First);
+        assert_eq!(round_number_to_kind(6), RoundKind::FinalFour);
+        assert_eq!(round_number_to_kind(7), RoundKind::Championship);
+    }
+
+    #[test]
+    fn test_parse_status() {
+        assert_eq!(parse_status("STATUS_IN_PROGRESS"), GameStatus::InProgress);
+        assert_eq!(parse_status("STATUS_FINAL"), GameStatus::Final);
+        assert_eq!(parse_status("STATUS_SCHEDULED"), GameStatus::Scheduled);
+        assert_eq!(parse_status("STATUS_POSTPONED"), GameStatus::Postponed);
+    }
+
+    #[test]
+    fn test_round_kind_navigation() {
+        assert_eq!(RoundKind::First.next(), Some(RoundKind::Second));
+        assert_eq!(RoundKind::Championship.next(), None);
+        assert_eq!(RoundKind::FirstFour.prev(), None);
+        assert!(RoundKind::FinalFour.is_final_four());
+        assert!(!RoundKind::Elite8.is_final_four());
+    }
+
+    // -----------------------------------------------------------------------
+    // NCAA henrygd adapter tests
+    // -----------------------------------------------------------------------
+
+    #[test]
+    fn ncaa_position_to_round_covers_all_rounds() {
+        assert_eq!(round_number_to_kind(101 / 100), RoundKind::FirstFour);
+        assert_eq!(round_number_to_kind(201 / 100), RoundKind::First);
+        assert_eq!(round_number_to_kind(301 / 100), RoundKind::Second);
+        assert_eq!(round_number_to_kind(401 / 100), RoundKind::Sweet16);
+        assert_eq!(round_number_to_kind(501 / 100), RoundKind::Elite8);
+        assert_eq!(round_number_to_kind(601 / 100), RoundKind::FinalFour);
+        assert_eq!(round_number_to_kind(701 / 100), RoundKind::Championship);
+    }
+
+    #[test]
+    fn ncaa_game_with_empty_teams_produces_tba_slots() {
+        let raw = crate::henrygd::HenrygdGame {
+            bracket_position_id: 101,
+            game_state: "P".into(),
+            teams: vec![],
+            section_id: 1,
+            ..Default::default()
+        };
+        let game = map_ncaa_game(&raw);
+        assert_eq!(game.id, "101");
+        assert!(game.espn_id.is_none(), "espn_id must be None pre-bridge");
+        assert!(
+            game.top.team.is_none(),
+            "top team should be None when teams is empty"
+        );
+        assert!(
+            game.bottom.team.is_none(),
+            "bottom team should be None when teams is empty"
+        );
+        assert_eq!(game.top.placeholder.as_deref(), Some("TBA"));
+        assert_eq!(game.status, GameStatus::Scheduled);
+    }
+
+    #[test]
+    fn ncaa_game_with_teams_maps_correctly() {
+        let raw = crate::henrygd::HenrygdGame {
+            bracket_position_id: 201,
+            game_state: "F".into(),
+            teams: vec![
+                crate::henrygd::HenrygdTeam {
+                    team_id: Some("uconn".into()),
+                    name: Some("Connecticut".into()),
+                    short_name: Some("UConn".into()),
+                    seed: Some(1),
+                    winner: Some(true),
+                    description: None,
+                },
+                crate::henrygd::HenrygdTeam {
+                    team_id: Some("stetson".into()),
+                    name: Some("Stetson".into()),
+                    short_name: None,
+                    seed: Some(16),
+                    winner: Some(false),
+                    description: None,
+                },
+            ],
+            section_id: 2,
+            victor_bracket_position_id: Some(301),
+            ..Default::default()
+        };
+        let game = map_ncaa_game(&raw);
+        assert_eq!(game.id, "201");
+        assert_eq!(game.next_game_id.as_deref(), Some("301"));
+        assert_eq!(game.winner_id.as_deref(), Some("uconn"));
+        assert_eq!(game.top.seed, 1);
+        assert_eq!(game.bottom.seed, 16);
+        assert_eq!(game.status, GameStatus::Final);
+    }
+
+    #[test]
+    fn ncaa_start_parses_eastern_time_and_skips_tba() {
+        let dt = parse_ncaa_start("03/20/2025", "12:15 PM ET").unwrap();
+        assert_eq!(dt, Utc.with_ymd_and_hms(2025, 3, 20, 16, 15, 0).unwrap());
+        assert!(parse_ncaa_start("03/20/2025", "TBA").is_none());
+        assert!(parse_ncaa_start("", "").is_none());
+    }
+
+    #[test]
+    fn ncaa_start_uses_standard_time_before_the_dst_switch() {
+        let dt = parse_ncaa_start("03/08/2025", "7:00 PM ET").unwrap();
+        assert_eq!(dt, Utc.with_ymd_and_hms(2025, 3, 9, 0, 0, 0).unwrap());
+        let dt = parse_ncaa_start("03/09/2025", "7:00 PM ET").unwrap();
+        assert_eq!(dt, Utc.with_ymd_and_hms(2025, 3, 9, 23, 0, 0).unwrap());
+    }
+
+    #[test]
+    fn ncaa_championship_empty_region_titles_fall_back_to_region_n() {
+        use crate::henrygd::{HenrygdChampionship, HenrygdGame, HenrygdRegion};
+        let champ = HenrygdChampionship {
+            title: "2026 DI Men's Basketball Championship".into(),
+            year: 2026,
+            games: vec![HenrygdGame {
+                bracket_position_id: 201,
+                game_state: "P".into(),
+                section_id: 1,
+                ..Default::default()
+            }],
+            rounds: vec![],
+            regions: vec![HenrygdRegion {
+                id: "4031".into(),
+                section_id: 1,
+                title: String::new(), // empty pre-Selection Sunday
+                region_code: "TL".into(),
+            }],
+        };
+        let tournament = map_ncaa_championship(champ, Gender::Men);
+        assert_eq!(tournament.year, 2026);
+        let region = tournament.regions.iter().find(|r| r.id != "national");
+        assert!(region.is_some());
+        assert!(
+            region.unwrap().name.starts_with("Region "),
+            "empty title should fall back to 'Region N', got: {}",
+            region.unwrap().name
+        );
+    }
+
+    #[test]
+    fn ncaa_championship_national_section_maps_to_national_region() {
+        use crate::henrygd::{HenrygdChampionship, HenrygdGame};
+        let champ = HenrygdChampionship {
+            title: "2026 Championship".into(),
+            year: 2026,
+            games: vec![HenrygdGame {
+                bracket_position_id: 701,
+                game_state: "P".into(),
+                section_id: 6,
+                ..Default::default()
+            }],
+            rounds: vec![],
+            regions: vec![],
+        };
+        let tournament = map_ncaa_championship(champ, Gender::Men);
+        let national = tournament.regions.iter().find(|r| r.id == "national");
+        assert!(
+            national.is_some(),
+            "sectionId 6 must produce the National region"
+        );
+        let rounds = &national.unwrap().rounds;
+        assert_eq!(rounds.len(), 1);
+        assert_eq!(rounds[0].kind, RoundKind::Championship);
+    }
+
+    #[test]
+    fn summary_box_score_reads_espn_stat_keys_and_splits_the_bench() {
+        let raw: SummaryResponse = serde_json::from_value(serde_json::json!({
+            "boxscore": {
+                "players": [{
+                    "team": { "id": "150", "shortDisplayName": "Duke" },
+                    "statistics": [{
+                        "names": ["MIN", "FG", "3PT", "FT", "OREB", "DREB", "REB", "AST", "STL", "BLK", "TO", "PF", "PTS"],
+                        "labels": ["MIN", "FG", "3PT", "FT", "OREB", "DREB", "REB", "AST", "STL", "BLK", "TO", "PF", "PTS"],
+                        "keys": [
+                            "minutes",
+                            "fieldGoalsMade-fieldGoalsAttempted",
+                            "threePointFieldGoalsMade-threePointFieldGoalsAttempted",
+                            "freeThrowsMade-freeThrowsAttempted",
+                            "offensiveRebounds", "defensiveRebounds", "rebounds",
+                            "assists", "steals", "blocks", "turnovers", "fouls", "points"
+                        ],
+                        "athletes": [
+                            {
+                                "athlete": { "displayName": "Bench Guard" },
+                                "starter": false,
+                                "stats": ["12", "2-3", "1-1", "0-0", "0", "1", "1", "2", "0", "0", "1", "2", "5"]
+                            },
+                            {
+                                "athlete": { "displayName": "Cooper Flagg" },
+                                "starter": true,
+                                "stats": ["35", "8-15", "2-5", "4-6", "3", "6", "9", "4", "2", "1", "3", "2", "22"]
+                            },
+                            {
+                                "athlete": { "displayName": "Walk On" },
+                                "starter": false,
+                                "didNotPlay": true,
+                                "stats": []
+                            }
+                        ],
+                        "totals": ["", "10-18", "3-6", "4-6", "3", "7", "10", "6", "2", "1", "4", "4", "27"]
+                    }]
+                }]
+            }
+        }))
+        .unwrap();
+
+        let detail = map_summary("401", raw);
+        let duke = &detail.home_box;
+        let names: Vec<_> = duke.players.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, ["Cooper Flagg", "Bench Guard"]);
+        assert_eq!(duke.starters().count(), 1);
+        assert_eq!(duke.bench().count(), 1);
+
+        let flagg = &duke.players[0];
+        assert_eq!(flagg.minutes, "35");
+        assert_eq!(flagg.points, 22);
+        assert_eq!((flagg.offensive_rebounds, flagg.defensive_rebounds), (3, 6));
+        assert_eq!((flagg.steals, flagg.blocks, flagg.turnovers), (2, 1, 3));
+        assert_eq!(flagg.fouls, 2);
+        assert_eq!(flagg.ft, "4-6");
+        assert_eq!(flagg.plus_minus, None);
+        assert_eq!(duke.totals.points, 27);
+        assert_eq!(crate::made_attempted(&duke.totals.fg), Some((10, 18)));
+    }
+
+    #[test]
+    fn summary_sides_follow_the_header_and_orient_to_the_bracket() {
+        let raw: SummaryResponse = serde_json::from_value(serde_json::json!({
+            "header": { "competitions": [{ "competitors": [
+                { "id": "150", "homeAway": "away", "team": { "id": "150" } },
+                { "id": "248", "homeAway": "home", "team": { "id": "248" } }
+            ]}]},
+            "boxscore": { "players": [
+                { "team": { "id": "150", "shortDisplayName": "Duke" }, "statistics": [] },
+                { "team": { "id": "248", "shortDisplayName": "Houston" }, "statistics": [] }
+            ]},
+            "plays": [{ "homeScore": 40, "awayScore": 38 }]
+        }))
+        .unwrap();
+        let mut detail = map_summary("401", raw);
+        let short = |b: &BoxScore| b.team.as_ref().unwrap().short_name.clone();
+        assert_eq!(short(&detail.home_box), "Houston");
+        assert_eq!(short(&detail.away_box), "Duke");
+        assert_eq!(short(detail.top_box()), "Houston");
+
+        // An NCAA-sourced bracket with its own IDs and Duke on top.
+        let team = |id: &str, name: &str| TeamSeed {
+            seed: 1,
+            team: Some(Team {
+                id: id.into(),
+                short_name: name.into(),
+                ..Default::default()
+            }),
+            placeholder: None,
+        };
+        let game = Game {
+            top: team("duke", "Duke"),
+            bottom: team("houston", "Houston"),
+            ..Default::default()
+        };
+        detail.orient(&game);
+        assert_eq!(detail.home_slot, Slot::Bottom);
+        assert_eq!(short(detail.top_box()), "Duke");
+        assert_eq!(short(detail.bottom_box()), "Houston");
+        assert_eq!(detail.slot_scores(&detail.plays[0]), (38, 40));
+    }
+
+    #[test]
+    fn summary_plays_carry_team_type_points_and_shot_location() {
+        let raw: SummaryResponse = serde_json::from_value(serde_json::json!({
+            "plays": [
+                {
+                    "text": "Cooper Flagg made Three Point Jumper.",
+                    "type": { "id": "558", "text": "JumpShot" },
+                    "team": { "id": "150" },
+                    "scoringPlay": true,
+                    "scoreValue": 3,
+                    "shootingPlay": true,
+                    "coordinate": { "x": 3, "y": 22 },
+                    "homeScore": 3,
+                    "awayScore": 0
+                },
+                {
+                    "text": "J'Wan Roberts missed Layup.",
+                    "type": { "text": "LayUpShot" },
+                    "team": { "id": "248" },
+                    "scoringPlay": false,
+                    "scoreValue": 2,
+                    "shootingPlay": true,
+                    "coordinate": { "x": 25, "y": 2 }
+                },
+                {
+                    "text": "Official TV Timeout",
+                    "scoringPlay": false,
+                    "shootingPlay": false,
+                    "coordinate": { "x": -214748340, "y": -214748365 }
+                }
+            ]
+        }))
+        .unwrap();
+        let plays = map_summary("401", raw).plays;
+
+        assert_eq!(plays[0].team_id.as_deref(), Some("150"));
+        assert_eq!(plays[0].play_type, "JumpShot");
+        assert!(plays[0].scoring);
+        assert_eq!(plays[0].points, 3);
+        assert_eq!(plays[0].coordinate, Some((3.0, 22.0)));
+
+        assert!(!plays[1].scoring);
+        assert_eq!(plays[1].points, 0);
+        assert_eq!(plays[1].coordinate, Some((25.0, 2.0)));
+
+        assert_eq!(plays[2].team_id, None);
+        assert_eq!(plays[2].coordinate, None);
     }
 }
@@ -142,10 +142,17 @@ pub struct EspnVenue {
 
 #[derive(Debug, Deserialize, Default, Clone)]
 pub struct SummaryResponse {
+    pub header: Option<EspnSummaryHeader>,
     pub plays: Option<Vec<EspnPlay>>,
     pub boxscore: Option<EspnBoxscore>,
 }
 
+/// Carries the competitors with their `homeAway` sides.
+#[derive(Debug, Deserialize, Default, Clone)]
+pub struct EspnSummaryHeader {
+    pub competitions: Option<Vec<EspnCompetition>>,
+}
+
 #[derive(Debug, Deserialize, Clone)]
 pub struct EspnPlay {
     pub period: Option<EspnPeriod>,
@@ -155,6 +162,29 @@ pub struct EspnPlay {
     pub home_score: Option<u16>,
     #[serde(rename = "awayScore")]
     pub away_score: Option<u16>,
+    #[serde(rename = "type")]
+    pub play_type: Option<EspnPlayType>,
+    pub team: Option<EspnTeam>,
+    #[serde(rename = "scoringPlay")]
+    pub scoring_play: Option<bool>,
+    /// Value of the attempt — set on missed shots too.
+    #[serde(rename = "scoreValue")]
+    pub score_value: Option<u8>,
+    #[serde(rename = "shootingPlay")]
+    pub shooting_play: Option<bool>,
+    pub coordinate: Option<EspnCoordinate>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct EspnPlayType {
+    pub text: Option<String>,
+}
+
+/// Plays without a location carry large negative sentinels.
+#[derive(Debug, Deserialize, Clone)]
+pub struct EspnCoordinate {
+    pub x: Option<f32>,
+    pub y: Option<f32>,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -192,6 +222,9 @@ pub struct EspnStatCategory {
 pub struct EspnAthleteStats {
     pub athlete: Option<EspnAthlete>,
     pub stats: Option<Vec<String>>,
+    pub starter: Option<bool>,
+    #[serde(rename = "didNotPlay")]
+    pub did_not_play: Option<bool>,
 }
 
 #[derive(Debug, Deserialize, Clone)]
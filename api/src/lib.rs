@@ -1,23 +1,119 @@
+pub mod bridge;
 pub mod client;
 pub mod espn;
 pub mod henrygd;
+pub mod scenarios;
+pub mod scoring;
+pub mod simulate;
+pub mod source;
+pub mod topology;
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use topology::Slot;
 
 // ---------------------------------------------------------------------------
 // Domain types — clean model, independent of ESPN wire format
 // ---------------------------------------------------------------------------
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Tournament {
     pub id: String,
     pub name: String,
     pub year: u16,
+    #[serde(default)]
+    pub gender: Gender,
     pub regions: Vec<Region>,
 }
 
+/// Which NCAA Division I tournament: the men's or the women's bracket.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Gender {
+    #[default]
+    Men,
+    Women,
+}
+
+impl Gender {
+    /// `men` or `women`, as in henrygd paths and config values.
+    pub fn slug(self) -> &'static str {
+        match self {
+            Gender::Men => "men",
+            Gender::Women => "women",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Gender::Men => "Men's",
+            Gender::Women => "Women's",
+        }
+    }
+
+    /// The other bracket.
+    pub fn toggle(self) -> Self {
+        match self {
+            Gender::Men => Gender::Women,
+            Gender::Women => Gender::Men,
+        }
+    }
+
+    /// Periods in regulation: the men play two halves, the women four
+    /// quarters.
+    pub fn regulation_periods(self) -> u8 {
+        match self {
+            Gender::Men => 2,
+            Gender::Women => 4,
+        }
+    }
+
+    /// The period whose end is halftime.
+    pub fn halftime_period(self) -> u8 {
+        self.regulation_periods() / 2
+    }
+
+    /// Scoreboard label for `period`: `1H`/`2H` or `Q1`–`Q4`, then `OT`,
+    /// `2OT`, ...
+    pub fn period_label(self, period: u8) -> String {
+        let regulation = self.regulation_periods();
+        match (self, period) {
+            (_, 0) => String::new(),
+            (Gender::Men, p) if p <= regulation => format!("{p}H"),
+            (Gender::Women, p) if p <= regulation => format!("Q{p}"),
+            (_, p) if p == regulation + 1 => "OT".to_string(),
+            (_, p) => format!("{}OT", p - regulation),
+        }
+    }
+}
+
+impl std::str::FromStr for Gender {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().replace('\'', "").as_str() {
+            "men" | "mens" | "m" => Ok(Gender::Men),
+            "women" | "womens" | "w" => Ok(Gender::Women),
+            other => Err(format!("unknown tournament {other} (men or women)")),
+        }
+    }
+}
+
 impl Tournament {
+    /// Iterate over every game in the tree, region by region, round by round.
+    pub fn games(&self) -> impl Iterator<Item = &Game> {
+        self.regions
+            .iter()
+            .flat_map(|r| r.rounds.iter())
+            .flat_map(|r| r.games.iter())
+    }
+
+    /// Find a game by ID across all regions and rounds.
+    pub fn find_game(&self, game_id: &str) -> Option<&Game> {
+        self.games().find(|g| g.id == game_id)
+    }
+
     /// Find a game by ID across all regions and rounds.
     pub fn find_game_mut(&mut self, game_id: &str) -> Option<&mut Game> {
         for region in &mut self.regions {
@@ -32,24 +128,65 @@ impl Tournament {
         None
     }
 
+    /// The game this game's winner advances into.
+    pub fn parent(&self, game_id: &str) -> Option<&Game> {
+        let next_id = self.find_game(game_id)?.next_game_id.as_deref()?;
+        self.find_game(next_id)
+    }
+
+    /// Games whose winners meet in `game_id`, in bracket order.
+    pub fn feeders(&self, game_id: &str) -> Vec<&Game> {
+        topology::feeders(self, game_id)
+    }
+
+    /// Feeder game IDs for `game`'s top and bottom slots.
+    pub fn slot_feeders(&self, game: &Game) -> (Option<String>, Option<String>) {
+        topology::slot_feeders(self, game)
+    }
+
+    /// Record parent/child links for games the upstream feed left unlinked.
+    pub fn link_games(&mut self) {
+        topology::link_games(self);
+    }
+
+    /// Propagate finished games' winners into the next round's open slots.
+    /// Returns the number of slots filled.
+    pub fn advance_winners(&mut self) -> usize {
+        topology::advance_winners(self)
+    }
+
     /// Merge partial game updates (from scoreboard refresh) into the tree.
+    ///
+    /// Updates whose ID is already in the tree (ESPN-sourced brackets) replace the
+    /// game outright. Anything else goes through the team-matching bridge, which
+    /// pairs ESPN scoreboard games with NCAA bracket slots and sets `espn_id`.
+    /// Winners are advanced into the next round once everything is merged.
     pub fn merge_updates(&mut self, updates: Vec<Game>) {
         for update in updates {
             if let Some(game) = self.find_game_mut(&update.id) {
-                *game = update;
+                let next_game_id = game.next_game_id.take();
+                *game = Game {
+                    next_game_id: update.next_game_id.clone().or(next_game_id),
+                    ..update
+                };
+            } else if let Some(m) = bridge::match_game(self, &update)
+                && let Some(game) = self.find_game_mut(&m.game_id)
+            {
+                bridge::apply_update(game, &update, m.swapped);
             }
         }
+        self.advance_winners();
     }
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Region {
     pub id: String,
     pub name: String, // "East", "West", "South", "Midwest", "National" (Final Four)
     pub rounds: Vec<Round>,
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Round {
     pub kind: RoundKind,
     pub games: Vec<Game>,
@@ -69,6 +206,17 @@ pub enum RoundKind {
 }
 
 impl RoundKind {
+    /// Every round, earliest first.
+    pub const ALL: [RoundKind; 7] = [
+        RoundKind::FirstFour,
+        RoundKind::First,
+        RoundKind::Second,
+        RoundKind::Sweet16,
+        RoundKind::Elite8,
+        RoundKind::FinalFour,
+        RoundKind::Championship,
+    ];
+
     pub fn label(&self) -> &'static str {
         match self {
             RoundKind::FirstFour => "First Four",
@@ -81,6 +229,19 @@ impl RoundKind {
         }
     }
 
+    /// Compact column header for tables ("R64", "S16", ...).
+    pub fn abbrev(&self) -> &'static str {
+        match self {
+            RoundKind::FirstFour => "FF",
+            RoundKind::First => "R64",
+            RoundKind::Second => "R32",
+            RoundKind::Sweet16 => "S16",
+            RoundKind::Elite8 => "E8",
+            RoundKind::FinalFour => "F4",
+            RoundKind::Championship => "NC",
+        }
+    }
+
     pub fn is_final_four(&self) -> bool {
         matches!(self, RoundKind::FinalFour | RoundKind::Championship)
     }
@@ -119,13 +280,17 @@ impl RoundKind {
     }
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Game {
     pub id: String,
     /// ESPN event ID used to route `fetch_game_detail` calls.
-    /// - NCAA-sourced games: `None` until the team-matching bridge fires.
+    /// - NCAA-sourced games: `None` until `bridge::match_game` pairs the game with
+    ///   an ESPN scoreboard event in `Tournament::merge_updates`.
     /// - ESPN-sourced games (2025 fallback): `Some(id)` — same value as `id`.
     pub espn_id: Option<String>,
+    /// Game the winner advances into (`victorBracketPositionId` for NCAA data,
+    /// inferred by `topology::link_games` otherwise). None for the title game.
+    pub next_game_id: Option<String>,
     pub top: TeamSeed,    // higher seed (or "top" of the bracket slot)
     pub bottom: TeamSeed, // lower seed (or "bottom" of the bracket slot)
     pub status: GameStatus,
@@ -152,14 +317,14 @@ impl Game {
     }
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct TeamSeed {
     pub seed: u8,
     pub team: Option<Team>, // None = TBD / "Winner of Game X"
     pub placeholder: Option<String>, // "Winner of #42" etc.
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Team {
     pub id: String,
     pub name: String,        // "Duke Blue Devils"
@@ -168,7 +333,7 @@ pub struct Team {
     pub color: Option<String>, // hex color from ESPN
 }
 
-#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub enum GameStatus {
     #[default]
     Scheduled,
@@ -177,38 +342,154 @@ pub enum GameStatus {
     Postponed,
 }
 
+/// One entrant's bracket: game ID → picked team ID. Slots whose team was not
+/// known when the pick was made use `top:{game_id}` / `bottom:{game_id}`.
+///
+/// The seal fields are optional so hand-written picks files still load; see
+/// the TUI's `seal` module for how they are produced and checked.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BracketPicks {
+    pub user_id: String,
+    pub year: u16,
+    pub selections: HashMap<String, String>,
+    /// When the picks were last changed and sealed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub submitted_at: Option<DateTime<Utc>>,
+    /// Hex SHA-256 over the user, year, selections and `submitted_at`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_hash: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<PicksSignature>,
+}
+
+/// secp256k1 ECDSA signature over `content_hash`, with the signer's key.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PicksSignature {
+    /// Compressed public key, hex.
+    pub pubkey: String,
+    /// Compact (64-byte) signature, hex.
+    pub signature: String,
+}
+
 /// Detailed game data (play-by-play, box score) fetched on demand.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct GameDetail {
     pub game_id: String,
     pub plays: Vec<Play>,
     pub home_box: BoxScore,
     pub away_box: BoxScore,
+    /// Which slot of the bracket game the home team plays in. ESPN games put
+    /// the home team on top; [`GameDetail::orient`] corrects it for others.
+    #[serde(default)]
+    pub home_slot: Slot,
 }
 
-#[derive(Debug, Clone, Default)]
+impl GameDetail {
+    /// Box score of the team in the bracket's top slot.
+    pub fn top_box(&self) -> &BoxScore {
+        match self.home_slot {
+            Slot::Top => &self.home_box,
+            Slot::Bottom => &self.away_box,
+        }
+    }
+
+    /// Box score of the team in the bracket's bottom slot.
+    pub fn bottom_box(&self) -> &BoxScore {
+        match self.home_slot {
+            Slot::Top => &self.away_box,
+            Slot::Bottom => &self.home_box,
+        }
+    }
+
+    /// A play's score as (top, bottom).
+    pub fn slot_scores(&self, play: &Play) -> (u16, u16) {
+        match self.home_slot {
+            Slot::Top => (play.home_score, play.away_score),
+            Slot::Bottom => (play.away_score, play.home_score),
+        }
+    }
+
+    /// Line the boxes up with `game`'s slots. Teams match by ID, or by name
+    /// when the bracket came from a source with its own IDs; the current
+    /// orientation stands when neither fits better.
+    pub fn orient(&mut self, game: &Game) {
+        let fit = |slot: &TeamSeed, box_score: &BoxScore| match (&slot.team, &box_score.team) {
+            (Some(a), Some(b)) if !a.id.is_empty() && a.id == b.id => 4,
+            (Some(a), Some(b)) => bridge::team_match_score(a, b),
+            _ => 0,
+        };
+        let home_top = fit(&game.top, &self.home_box) + fit(&game.bottom, &self.away_box);
+        let home_bottom = fit(&game.top, &self.away_box) + fit(&game.bottom, &self.home_box);
+        if home_top > home_bottom {
+            self.home_slot = Slot::Top;
+        } else if home_bottom > home_top {
+            self.home_slot = Slot::Bottom;
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Play {
     pub period: u8,
     pub clock: String,
     pub description: String,
     pub home_score: u16,
     pub away_score: u16,
+    /// ESPN ID of the team the play belongs to.
+    pub team_id: Option<String>,
+    /// ESPN play type, e.g. "JumpShot" or "Substitution".
+    pub play_type: String,
+    pub scoring: bool,
+    /// Points scored on the play; 0 unless `scoring`.
+    pub points: u8,
+    /// Shot location in ESPN court units, for shooting plays that have one.
+    pub coordinate: Option<(f32, f32)>,
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct BoxScore {
     pub team: Option<Team>,
+    /// Starters first, then the bench, each in ESPN's order. Players who
+    /// did not play are left out.
     pub players: Vec<PlayerLine>,
     pub totals: PlayerLine,
 }
 
-#[derive(Debug, Clone, Default)]
+impl BoxScore {
+    pub fn starters(&self) -> impl Iterator<Item = &PlayerLine> {
+        self.players.iter().filter(|p| p.starter)
+    }
+
+    pub fn bench(&self) -> impl Iterator<Item = &PlayerLine> {
+        self.players.iter().filter(|p| !p.starter)
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct PlayerLine {
     pub name: String,
+    pub starter: bool,
+    pub minutes: String,
     pub points: u16,
     pub rebounds: u16,
+    pub offensive_rebounds: u16,
+    pub defensive_rebounds: u16,
     pub assists: u16,
-    pub minutes: String,
+    pub steals: u16,
+    pub blocks: u16,
+    pub turnovers: u16,
+    pub fouls: u16,
     pub fg: String,  // "7-12"
     pub fg3: String, // "2-5"
+    pub ft: String,  // "4-6"
+    /// Not every ESPN feed reports +/-.
+    pub plus_minus: Option<i16>,
+}
+
+/// `"7-12"` → `(7, 12)`.
+pub fn made_attempted(stat: &str) -> Option<(u16, u16)> {
+    let (made, attempted) = stat.split_once('-')?;
+    Some((made.trim().parse().ok()?, attempted.trim().parse().ok()?))
 }
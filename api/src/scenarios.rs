@@ -0,0 +1,339 @@
+//! "What needs to happen" analysis for each pool entrant.
+//!
+//! Once few enough games remain, every combination of results is enumerated
+//! depth-first in play order. A branch is cut as soon as the entrant's best
+//! possible total (current points plus every remaining pick whose team is
+//! still alive) falls below another entrant's points so far, so eliminated
+//! entrants finish almost immediately. Winning leaves are then folded into the
+//! results every winning path shares: those are the games the entrant needs.
+use crate::scoring::ScoringRule;
+use crate::simulate::{Outcome, Pick, SimBracket, WinModel};
+use crate::{BracketPicks, RoundKind, Tournament};
+
+/// Enumeration is exhaustive, so it waits until the Sweet 16 (15 games left).
+pub const MAX_REMAINING_GAMES: usize = 15;
+
+/// One result an entrant needs in every scenario where they win the pool.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Requirement {
+    pub game_id: String,
+    pub round: RoundKind,
+    pub winner: String,
+    /// The opponent, when it is the same in every winning scenario.
+    pub loser: Option<String>,
+}
+
+impl Requirement {
+    pub fn describe(&self) -> String {
+        match &self.loser {
+            Some(loser) => format!("{} over {}", self.winner, loser),
+            None => format!("{} to win the {}", self.winner, self.round.label()),
+        }
+    }
+}
+
+/// Path analysis for one entrant. A tie for first counts as a win.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntrantPath {
+    pub user_id: String,
+    pub eliminated: bool,
+    pub winning_scenarios: u64,
+    pub total_scenarios: u64,
+    /// Results shared by every winning scenario, in play order.
+    pub needs: Vec<Requirement>,
+}
+
+/// Analyze every entry. Fails while more than [`MAX_REMAINING_GAMES`] games
+/// are undecided.
+pub fn analyze_paths(
+    tournament: &Tournament,
+    entries: &[BracketPicks],
+    rule: ScoringRule,
+) -> Result<Vec<EntrantPath>, String> {
+    let bracket = SimBracket::build(tournament, &WinModel::Seed);
+    let remaining = bracket.nodes.iter().filter(|n| n.decided.is_none()).count();
+    if remaining > MAX_REMAINING_GAMES {
+        return Err(format!(
+            "{remaining} games left; path analysis opens up at the Sweet 16 \
+             ({MAX_REMAINING_GAMES} or fewer)"
+        ));
+    }
+
+    let picks: Vec<Vec<Pick>> = entries.iter().map(|e| bracket.resolve_picks(e)).collect();
+    Ok(entries
+        .iter()
+        .enumerate()
+        .map(|(target, entry)| {
+            let mut search = Search {
+                bracket: &bracket,
+                picks: &picks,
+                rule,
+                target,
+                outcome: vec![None; bracket.nodes.len()],
+                scores: vec![0; entries.len()],
+                lost: vec![false; bracket.teams.len()],
+                wins: 0,
+                shared: vec![Shared::Unset; bracket.nodes.len()],
+            };
+            search.descend(0);
+
+            let needs = search
+                .shared
+                .iter()
+                .enumerate()
+                .filter(|&(i, _)| bracket.nodes[i].decided.is_none())
+                .filter_map(|(i, shared)| match *shared {
+                    Shared::Same(w, l) => Some(Requirement {
+                        game_id: bracket.nodes[i].id.clone(),
+                        round: bracket.nodes[i].round,
+                        winner: bracket.teams[w].name.clone(),
+                        loser: l.map(|l| bracket.teams[l].name.clone()),
+                    }),
+                    _ => None,
+                })
+                .collect();
+
+            EntrantPath {
+                user_id: entry.user_id.clone(),
+                eliminated: search.wins == 0,
+                winning_scenarios: search.wins,
+                total_scenarios: 1u64 << remaining,
+                needs,
+            }
+        })
+        .collect())
+}
+
+/// What every winning leaf agrees on for one node.
+#[derive(Debug, Clone, Copy)]
+enum Shared {
+    Unset,
+    /// Same winner; opponent too when `Some`.
+    Same(usize, Option<usize>),
+    Varies,
+}
+
+struct Search<'a> {
+    bracket: &'a SimBracket,
+    picks: &'a [Vec<Pick>],
+    rule: ScoringRule,
+    target: usize,
+    outcome: Vec<Outcome>,
+    scores: Vec<u32>,
+    /// Teams knocked out on the current path.
+    lost: Vec<bool>,
+    wins: u64,
+    shared: Vec<Shared>,
+}
+
+impl Search<'_> {
+    fn descend(&mut self, i: usize) {
+        if i == self.bracket.nodes.len() {
+            self.record_leaf();
+            return;
+        }
+        if self.best_case(i) < self.leader_floor() {
+            return;
+        }
+
+        let teams = self.bracket.slot_teams(i, &self.outcome);
+        let sides = match (self.bracket.nodes[i].decided, teams) {
+            (Some(side), _) => vec![side],
+            (None, [Some(_), Some(_)]) => vec![0, 1],
+            (None, [Some(_), None]) => vec![0],
+            (None, [None, Some(_)]) => vec![1],
+            (None, [None, None]) => vec![],
+        };
+        if sides.is_empty() {
+            self.outcome[i] = None;
+            self.descend(i + 1);
+            return;
+        }
+
+        for side in sides {
+            let Some(winner) = teams[side] else { continue };
+            let loser = teams[1 - side];
+            let result = Some((winner, loser, side));
+            self.apply(i, result, true);
+            self.descend(i + 1);
+            self.apply(i, result, false);
+        }
+    }
+
+    /// Set (or undo) node `i`'s result, updating scores and knocked-out teams.
+    fn apply(&mut self, i: usize, result: Outcome, set: bool) {
+        for (e, picks) in self.picks.iter().enumerate() {
+            let pts = self.bracket.points(i, picks[i], result, self.rule);
+            if set {
+                self.scores[e] += pts;
+            } else {
+                self.scores[e] -= pts;
+            }
+        }
+        if let Some((_, Some(loser), _)) = result {
+            self.lost[loser] = set;
+        }
+        self.outcome[i] = if set { result } else { None };
+    }
+
+    /// Target's points so far plus every remaining pick that could still hit.
+    fn best_case(&self, from: usize) -> u32 {
+        let picks = &self.picks[self.target];
+        let upside: u32 = (from..self.bracket.nodes.len())
+            .map(|j| match picks[j] {
+                Pick::Team(t) if !self.lost[t] => {
+                    let seed = self.bracket.teams[t].seed;
+                    // Beating a 1 seed is the most an upset bonus can add.
+                    self.rule.points(self.bracket.nodes[j].round, seed, Some(1))
+                }
+                Pick::Side(_) => self.rule.points(self.bracket.nodes[j].round, 16, Some(1)),
+                _ => 0,
+            })
+            .sum();
+        self.scores[self.target] + upside
+    }
+
+    /// Highest score any other entrant has already locked in.
+    fn leader_floor(&self) -> u32 {
+        self.scores
+            .iter()
+            .enumerate()
+            .filter(|&(e, _)| e != self.target)
+            .map(|(_, &s)| s)
+            .max()
+            .unwrap_or(0)
+    }
+
+    fn record_leaf(&mut self) {
+        if self.scores[self.target] < self.leader_floor() {
+            return;
+        }
+        self.wins += 1;
+        for (shared, result) in self.shared.iter_mut().zip(&self.outcome) {
+            let Some((w, l, _)) = *result else {
+                *shared = Shared::Varies;
+                continue;
+            };
+            *shared = match *shared {
+                Shared::Unset => Shared::Same(w, l),
+                Shared::Same(sw, sl) if sw == w => Shared::Same(w, if sl == l { l } else { None }),
+                _ => Shared::Varies,
+            };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Game, GameStatus, Region, Round, Team, TeamSeed};
+
+    fn seeded(seed: u8, id: &str) -> TeamSeed {
+        TeamSeed {
+            seed,
+            team: Some(Team {
+                id: id.into(),
+                short_name: id.to_uppercase(),
+                ..Default::default()
+            }),
+            placeholder: None,
+        }
+    }
+
+    /// Final Four already set: Auburn v Florida, Duke v Houston, then the title.
+    fn final_four() -> Tournament {
+        let semi = |id: &str, top, bottom| Game {
+            id: id.into(),
+            top,
+            bottom,
+            ..Default::default()
+        };
+        let mut t = Tournament {
+            regions: vec![Region {
+                name: "National".into(),
+                rounds: vec![
+                    Round {
+                        kind: RoundKind::FinalFour,
+                        games: vec![
+                            semi("ff1", seeded(1, "aub"), seeded(1, "fla")),
+                            semi("ff2", seeded(1, "duke"), seeded(1, "hou")),
+                        ],
+                    },
+                    Round {
+                        kind: RoundKind::Championship,
+                        games: vec![semi("nc", TeamSeed::default(), TeamSeed::default())],
+                    },
+                ],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        t.link_games();
+        t
+    }
+
+    fn entry(user: &str, picks: &[(&str, &str)]) -> BracketPicks {
+        BracketPicks {
+            user_id: user.into(),
+            year: 2025,
+            selections: picks
+                .iter()
+                .map(|(g, t)| (g.to_string(), t.to_string()))
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn lists_required_results_for_a_live_entrant() {
+        let t = final_four();
+        let entries = [
+            entry("alice", &[("ff1", "fla"), ("ff2", "duke"), ("nc", "duke")]),
+            entry("bob", &[("ff1", "fla"), ("ff2", "hou"), ("nc", "fla")]),
+        ];
+        let paths = analyze_paths(&t, &entries, ScoringRule::EspnStandard).unwrap();
+
+        assert_eq!(paths[0].total_scenarios, 8);
+        assert!(!paths[0].eliminated);
+        // Alice wins when Duke takes the title, or Duke reaches it and Auburn
+        // beats them: Duke over Houston is the one result every path shares.
+        assert_eq!(paths[0].winning_scenarios, 3);
+        let needs: Vec<_> = paths[0].needs.iter().map(Requirement::describe).collect();
+        assert_eq!(needs, ["DUKE over HOU"]);
+    }
+
+    #[test]
+    fn entrant_behind_with_dead_picks_is_eliminated() {
+        let mut t = final_four();
+        for (id, winner) in [("ff1", "fla"), ("ff2", "hou")] {
+            let g = t.find_game_mut(id).unwrap();
+            g.status = GameStatus::Final;
+            g.winner_id = Some(winner.into());
+        }
+        t.advance_winners();
+        let entries = [
+            entry("alice", &[("ff1", "aub"), ("ff2", "duke"), ("nc", "duke")]),
+            entry("bob", &[("ff1", "fla"), ("ff2", "hou"), ("nc", "fla")]),
+        ];
+        let paths = analyze_paths(&t, &entries, ScoringRule::EspnStandard).unwrap();
+        assert!(paths[0].eliminated);
+        assert_eq!(paths[0].winning_scenarios, 0);
+        assert_eq!(paths[1].winning_scenarios, 2);
+        assert!(paths[1].needs.is_empty());
+    }
+
+    #[test]
+    fn refuses_to_enumerate_early_rounds() {
+        let mut t = final_four();
+        let filler: Vec<Game> = (0..MAX_REMAINING_GAMES)
+            .map(|i| Game {
+                id: format!("x{i}"),
+                top: seeded(1, &format!("a{i}")),
+                bottom: seeded(2, &format!("b{i}")),
+                ..Default::default()
+            })
+            .collect();
+        t.regions[0].rounds[0].games.extend(filler);
+        assert!(analyze_paths(&t, &[], ScoringRule::EspnStandard).is_err());
+    }
+}
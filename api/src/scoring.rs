@@ -0,0 +1,372 @@
+//! Pool scoring rules for bracket picks.
+//!
+//! Every rule starts from a per-round base value; the seed-aware rules then
+//! scale or top it up using the seeds of the winning (and, for upsets, the
+//! losing) team. Points only count for correct picks. `max_points` adds what a
+//! still-alive pick could earn, using the seed of the team that was picked.
+use crate::{BracketPicks, Game, GameStatus, RoundKind, Tournament};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ScoringRule {
+    /// 1-2-4-8-16-32, doubling each round.
+    #[default]
+    EspnStandard,
+    /// 2-3-5-8-13-21.
+    Fibonacci,
+    /// Round value times the winner's seed.
+    SeedMultiplier,
+    /// Round value plus the seed difference when the lower seed wins.
+    UpsetBonus,
+    /// Round value plus the winner's seed.
+    RoundPlusSeed,
+}
+
+impl ScoringRule {
+    pub const ALL: [ScoringRule; 5] = [
+        ScoringRule::EspnStandard,
+        ScoringRule::Fibonacci,
+        ScoringRule::SeedMultiplier,
+        ScoringRule::UpsetBonus,
+        ScoringRule::RoundPlusSeed,
+    ];
+
+    /// Config key, as accepted by `FromStr` and serde.
+    pub fn key(&self) -> &'static str {
+        match self {
+            ScoringRule::EspnStandard => "espn-standard",
+            ScoringRule::Fibonacci => "fibonacci",
+            ScoringRule::SeedMultiplier => "seed-multiplier",
+            ScoringRule::UpsetBonus => "upset-bonus",
+            ScoringRule::RoundPlusSeed => "round-plus-seed",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ScoringRule::EspnStandard => "ESPN standard",
+            ScoringRule::Fibonacci => "Fibonacci",
+            ScoringRule::SeedMultiplier => "Seed multiplier",
+            ScoringRule::UpsetBonus => "Upset bonus",
+            ScoringRule::RoundPlusSeed => "Round + seed",
+        }
+    }
+
+    /// Base value of a correct pick in `round`, before any seed adjustment.
+    pub fn round_base(&self, round: RoundKind) -> u32 {
+        match self {
+            ScoringRule::Fibonacci => match round {
+                RoundKind::FirstFour => 1,
+                RoundKind::First => 2,
+                RoundKind::Second => 3,
+                RoundKind::Sweet16 => 5,
+                RoundKind::Elite8 => 8,
+                RoundKind::FinalFour => 13,
+                RoundKind::Championship => 21,
+            },
+            _ => match round {
+                RoundKind::FirstFour => 1,
+                RoundKind::First => 1,
+                RoundKind::Second => 2,
+                RoundKind::Sweet16 => 4,
+                RoundKind::Elite8 => 8,
+                RoundKind::FinalFour => 16,
+                RoundKind::Championship => 32,
+            },
+        }
+    }
+
+    /// Points for correctly picking a `winner_seed` team in `round`.
+    ///
+    /// `loser_seed` is only needed for the upset bonus; pass None when the
+    /// opponent is not known yet (no bonus is assumed). Unknown seeds (0) count
+    /// as 1.
+    pub fn points(&self, round: RoundKind, winner_seed: u8, loser_seed: Option<u8>) -> u32 {
+        let base = self.round_base(round);
+        let seed = u32::from(winner_seed.max(1));
+        match self {
+            ScoringRule::EspnStandard | ScoringRule::Fibonacci => base,
+            ScoringRule::SeedMultiplier => base * seed,
+            ScoringRule::RoundPlusSeed => base + seed,
+            ScoringRule::UpsetBonus => {
+                let loser = loser_seed.filter(|&s| s > 0).map(u32::from).unwrap_or(seed);
+                base + seed.saturating_sub(loser)
+            }
+        }
+    }
+}
+
+impl fmt::Display for ScoringRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.key())
+    }
+}
+
+impl FromStr for ScoringRule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let key = s.trim().to_lowercase().replace(['_', ' '], "-");
+        match key.as_str() {
+            "espn" | "espn-standard" | "standard" => Ok(ScoringRule::EspnStandard),
+            "fib" | "fibonacci" => Ok(ScoringRule::Fibonacci),
+            "seed" | "seed-multiplier" => Ok(ScoringRule::SeedMultiplier),
+            "upset" | "upset-bonus" => Ok(ScoringRule::UpsetBonus),
+            "round-plus-seed" | "round+seed" => Ok(ScoringRule::RoundPlusSeed),
+            _ => Err(format!(
+                "unknown scoring rule {s:?} (expected one of: {})",
+                ScoringRule::ALL.map(|r| r.key()).join(", ")
+            )),
+        }
+    }
+}
+
+/// Result of scoring one bracket.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ScoreCard {
+    pub points: u32,
+    pub max_points: u32,
+    pub correct: u32,
+    pub total: u32,
+    /// Points earned per round.
+    pub round_points: BTreeMap<RoundKind, u32>,
+}
+
+/// Team IDs that lost a Final game.
+pub fn eliminated_teams(tournament: &Tournament) -> HashSet<String> {
+    let mut eliminated = HashSet::new();
+    for game in tournament.games() {
+        if game.status != GameStatus::Final {
+            continue;
+        }
+        let Some(winner_id) = game.winner_id.as_deref() else {
+            continue;
+        };
+        for slot in [&game.top, &game.bottom] {
+            if let Some(team) = &slot.team
+                && team.id != winner_id
+            {
+                eliminated.insert(team.id.clone());
+            }
+        }
+    }
+    eliminated
+}
+
+/// Score `picks` against the current state of `tournament`.
+pub fn score_bracket(
+    tournament: &Tournament,
+    picks: &BracketPicks,
+    rule: ScoringRule,
+) -> ScoreCard {
+    let eliminated = eliminated_teams(tournament);
+    let seeds = team_seeds(tournament);
+    let mut card = ScoreCard::default();
+
+    for region in &tournament.regions {
+        for round in &region.rounds {
+            for game in &round.games {
+                let Some(selection) = picks.selections.get(&game.id) else {
+                    continue;
+                };
+                card.total += 1;
+
+                if game.status == GameStatus::Final {
+                    if picked_winner(selection, game) {
+                        let (winner, loser) = if winner_is_top(game) {
+                            (&game.top, &game.bottom)
+                        } else {
+                            (&game.bottom, &game.top)
+                        };
+                        let pts = rule.points(round.kind, winner.seed, Some(loser.seed));
+                        card.correct += 1;
+                        card.points += pts;
+                        card.max_points += pts;
+                        *card.round_points.entry(round.kind).or_default() += pts;
+                    }
+                } else if is_viable(selection, game, &eliminated) {
+                    let (seed, opponent) = pick_seeds(selection, game, &seeds);
+                    card.max_points += rule.points(round.kind, seed, opponent);
+                }
+            }
+        }
+    }
+    card
+}
+
+fn winner_is_top(game: &Game) -> bool {
+    game.winner_id.is_some() && game.winner_id.as_ref() == game.top.team.as_ref().map(|t| &t.id)
+}
+
+fn picked_winner(selection: &str, game: &Game) -> bool {
+    let winner_is_bottom = game.winner_id.is_some()
+        && game.winner_id.as_ref() == game.bottom.team.as_ref().map(|t| &t.id);
+    game.winner_id.as_deref() == Some(selection)
+        || (selection == format!("top:{}", game.id) && winner_is_top(game))
+        || (selection == format!("bottom:{}", game.id) && winner_is_bottom)
+}
+
+/// Returns true if the pick is still viable (picked team has not been eliminated).
+/// Handles both direct team ID picks and placeholder picks ("top:{game_id}", "bottom:{game_id}").
+/// Treats TBD slots (team = None) as alive — a team that hasn't entered the bracket can't be out.
+fn is_viable(selection: &str, game: &Game, eliminated: &HashSet<String>) -> bool {
+    let team_id: Option<&str> = if selection == format!("top:{}", game.id) {
+        game.top.team.as_ref().map(|t| t.id.as_str())
+    } else if selection == format!("bottom:{}", game.id) {
+        game.bottom.team.as_ref().map(|t| t.id.as_str())
+    } else {
+        Some(selection)
+    };
+    match team_id {
+        Some(id) => !eliminated.contains(id),
+        None => true, // TBD slot — not yet in the bracket, cannot be eliminated
+    }
+}
+
+/// Seed of every team listed anywhere in the bracket.
+fn team_seeds(tournament: &Tournament) -> HashMap<&str, u8> {
+    tournament
+        .games()
+        .flat_map(|g| [&g.top, &g.bottom])
+        .filter(|s| s.seed > 0)
+        .filter_map(|s| s.team.as_ref().map(|t| (t.id.as_str(), s.seed)))
+        .collect()
+}
+
+/// Seed of the picked team and, when it is already in this game, its opponent.
+fn pick_seeds(selection: &str, game: &Game, seeds: &HashMap<&str, u8>) -> (u8, Option<u8>) {
+    let is = |slot: &crate::TeamSeed, side: &str| {
+        selection == format!("{side}:{}", game.id)
+            || slot.team.as_ref().is_some_and(|t| t.id == selection)
+    };
+    if is(&game.top, "top") {
+        (game.top.seed, Some(game.bottom.seed))
+    } else if is(&game.bottom, "bottom") {
+        (game.bottom.seed, Some(game.top.seed))
+    } else {
+        (seeds.get(selection).copied().unwrap_or(0), None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Region, Round, Team, TeamSeed};
+
+    fn seeded(seed: u8, id: &str) -> TeamSeed {
+        TeamSeed {
+            seed,
+            team: Some(Team {
+                id: id.into(),
+                ..Default::default()
+            }),
+            placeholder: None,
+        }
+    }
+
+    fn tournament() -> Tournament {
+        Tournament {
+            regions: vec![Region {
+                name: "East".into(),
+                rounds: vec![
+                    Round {
+                        kind: RoundKind::First,
+                        games: vec![
+                            Game {
+                                id: "g1".into(),
+                                top: seeded(4, "ariz"),
+                                bottom: seeded(13, "akr"),
+                                status: GameStatus::Final,
+                                winner_id: Some("akr".into()),
+                                ..Default::default()
+                            },
+                            Game {
+                                id: "g2".into(),
+                                top: seeded(5, "ore"),
+                                bottom: seeded(12, "lib"),
+                                status: GameStatus::Final,
+                                winner_id: Some("ore".into()),
+                                ..Default::default()
+                            },
+                        ],
+                    },
+                    Round {
+                        kind: RoundKind::Second,
+                        games: vec![Game {
+                            id: "g3".into(),
+                            top: seeded(13, "akr"),
+                            bottom: seeded(5, "ore"),
+                            ..Default::default()
+                        }],
+                    },
+                ],
+                ..Default::default()
+            }],
+            ..Default::default()
+        }
+    }
+
+    fn picks(selections: &[(&str, &str)]) -> BracketPicks {
+        BracketPicks {
+            user_id: "alice".into(),
+            year: 2026,
+            selections: selections
+                .iter()
+                .map(|(g, t)| (g.to_string(), t.to_string()))
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn rule_points_by_preset() {
+        let r = RoundKind::Sweet16;
+        assert_eq!(ScoringRule::EspnStandard.points(r, 11, Some(2)), 4);
+        assert_eq!(ScoringRule::Fibonacci.points(r, 11, Some(2)), 5);
+        assert_eq!(ScoringRule::SeedMultiplier.points(r, 11, Some(2)), 44);
+        assert_eq!(ScoringRule::UpsetBonus.points(r, 11, Some(2)), 13);
+        assert_eq!(ScoringRule::UpsetBonus.points(r, 2, Some(11)), 4);
+        assert_eq!(ScoringRule::UpsetBonus.points(r, 11, None), 4);
+        assert_eq!(ScoringRule::RoundPlusSeed.points(r, 11, Some(2)), 15);
+    }
+
+    #[test]
+    fn rule_parses_from_config_keys() {
+        for rule in ScoringRule::ALL {
+            assert_eq!(rule.key().parse::<ScoringRule>(), Ok(rule));
+        }
+        assert_eq!("Seed_Multiplier".parse(), Ok(ScoringRule::SeedMultiplier));
+        assert!("bogus".parse::<ScoringRule>().is_err());
+    }
+
+    #[test]
+    fn score_card_breaks_points_down_by_round() {
+        let t = tournament();
+        let p = picks(&[("g1", "akr"), ("g2", "lib"), ("g3", "akr")]);
+
+        let card = score_bracket(&t, &p, ScoringRule::SeedMultiplier);
+        assert_eq!(card.correct, 1);
+        assert_eq!(card.total, 3);
+        assert_eq!(card.points, 13);
+        assert_eq!(card.round_points.get(&RoundKind::First), Some(&13));
+        assert_eq!(card.round_points.get(&RoundKind::Second), None);
+        // Akron is still alive: 2 × 13 on top of what is already banked.
+        assert_eq!(card.max_points, 13 + 26);
+
+        let card = score_bracket(&t, &p, ScoringRule::UpsetBonus);
+        assert_eq!(card.points, 1 + 9);
+        assert_eq!(card.max_points, 10 + 2 + 8);
+    }
+
+    #[test]
+    fn eliminated_pick_has_no_potential() {
+        let t = tournament();
+        let card = score_bracket(&t, &picks(&[("g3", "ariz")]), ScoringRule::EspnStandard);
+        assert_eq!(card.points, 0);
+        assert_eq!(card.max_points, 0);
+    }
+}
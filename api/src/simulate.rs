@@ -0,0 +1,475 @@
+//! Monte Carlo simulation of the remaining tournament for pool odds.
+//!
+//! Each run plays out every undecided game, then scores every entrant's picks
+//! against the finished bracket. Across runs this gives each entrant's chance
+//! of finishing first (ties split the win) and their expected final points.
+//!
+//! Win probabilities come from seeds by default. A ratings file (team name or
+//! ID → point-margin rating, e.g. adjusted efficiency margin) can replace the
+//! seed model for the teams it covers.
+use crate::bridge::normalize_team_name;
+use crate::scoring::ScoringRule;
+use crate::{BracketPicks, GameStatus, RoundKind, Team, Tournament};
+use std::collections::HashMap;
+
+/// Runs used by the Compare tab.
+pub const DEFAULT_ITERATIONS: u32 = 10_000;
+
+/// Logistic slope per seed line. Puts a 1 over a 16 at ~93% and a 5 over a
+/// 12 at ~77%, close to the historical first-round rates.
+const SEED_SLOPE: f64 = 0.175;
+
+/// Rating points per tenfold change in odds.
+const RATING_SCALE: f64 = 10.0;
+
+/// Team strength ratings keyed by team ID or normalized school name.
+#[derive(Debug, Clone, Default)]
+pub struct Ratings(HashMap<String, f64>);
+
+impl Ratings {
+    /// Parse a JSON object of `{"Duke": 28.4, "Houston": 27.9, ...}`.
+    pub fn from_json(raw: &str) -> Result<Self, String> {
+        let map: HashMap<String, f64> =
+            serde_json::from_str(raw).map_err(|e| format!("invalid ratings json: {e}"))?;
+        Ok(Self(
+            map.into_iter()
+                .map(|(k, v)| (normalize_team_name(&k), v))
+                .collect(),
+        ))
+    }
+
+    pub fn load(path: &std::path::Path) -> Result<Self, String> {
+        let raw = std::fs::read_to_string(path)
+            .map_err(|e| format!("{}: read failed: {e}", path.display()))?;
+        Self::from_json(&raw).map_err(|e| format!("{}: {e}", path.display()))
+    }
+
+    fn get(&self, team: &Team) -> Option<f64> {
+        [&team.id, &team.name, &team.short_name]
+            .into_iter()
+            .find_map(|k| self.0.get(&normalize_team_name(k)).copied())
+    }
+}
+
+/// How the winner of an undecided game is drawn.
+#[derive(Debug, Clone, Default)]
+pub enum WinModel {
+    #[default]
+    Seed,
+    /// Ratings where both teams are covered, seeds otherwise.
+    Ratings(Ratings),
+}
+
+impl WinModel {
+    fn probability(&self, a: &SimTeam, b: &SimTeam) -> f64 {
+        if let (WinModel::Ratings(_), Some(ra), Some(rb)) = (self, a.rating, b.rating) {
+            return 1.0 / (1.0 + 10f64.powf((rb - ra) / RATING_SCALE));
+        }
+        let diff = f64::from(b.seed.max(1)) - f64::from(a.seed.max(1));
+        1.0 / (1.0 + (-SEED_SLOPE * diff).exp())
+    }
+}
+
+/// One entrant's simulated outlook, in the order the entries were passed in.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EntrantOdds {
+    pub user_id: String,
+    /// Share of runs in which the entrant finished first.
+    pub win_probability: f64,
+    pub expected_points: f64,
+}
+
+/// Simulate the rest of `tournament` `iterations` times and report odds for
+/// every entry. `seed` makes a run reproducible.
+pub fn simulate_pool(
+    tournament: &Tournament,
+    entries: &[BracketPicks],
+    rule: ScoringRule,
+    model: &WinModel,
+    iterations: u32,
+    seed: u64,
+) -> Vec<EntrantOdds> {
+    let sim = SimBracket::build(tournament, model);
+    let picks: Vec<Vec<Pick>> = entries.iter().map(|e| sim.resolve_picks(e)).collect();
+    let mut rng = SplitMix64(seed);
+    let mut wins = vec![0.0; entries.len()];
+    let mut totals = vec![0u64; entries.len()];
+    let mut outcome = vec![None; sim.nodes.len()];
+    let mut points = vec![0u32; entries.len()];
+
+    for _ in 0..iterations {
+        sim.play(model, &mut rng, &mut outcome);
+        for (i, entry) in picks.iter().enumerate() {
+            points[i] = sim.score(entry, &outcome, rule);
+            totals[i] += u64::from(points[i]);
+        }
+        let best = points.iter().copied().max().unwrap_or(0);
+        let leaders = points.iter().filter(|&&p| p == best).count() as f64;
+        for (i, &p) in points.iter().enumerate() {
+            if p == best {
+                wins[i] += 1.0 / leaders;
+            }
+        }
+    }
+
+    let runs = f64::from(iterations.max(1));
+    entries
+        .iter()
+        .enumerate()
+        .map(|(i, e)| EntrantOdds {
+            user_id: e.user_id.clone(),
+            win_probability: wins[i] / runs,
+            expected_points: totals[i] as f64 / runs,
+        })
+        .collect()
+}
+
+// ---------------------------------------------------------------------------
+// Compact bracket representation
+// ---------------------------------------------------------------------------
+
+#[derive(Debug)]
+pub(crate) struct SimTeam {
+    pub(crate) name: String,
+    pub(crate) seed: u8,
+    rating: Option<f64>,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Source {
+    Team(usize),
+    Winner(usize),
+    Empty,
+}
+
+#[derive(Debug)]
+pub(crate) struct Node {
+    pub(crate) id: String,
+    pub(crate) round: RoundKind,
+    slots: [Source; 2],
+    /// Side (0 = top) that already won, for Final games.
+    pub(crate) decided: Option<usize>,
+}
+
+/// Which team an entrant picked for a node.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Pick {
+    Team(usize),
+    Side(usize),
+    None,
+}
+
+/// Winner and loser team indices plus the winning side.
+pub(crate) type Outcome = Option<(usize, Option<usize>, usize)>;
+
+/// The tournament flattened into indexed games and teams, shared by the
+/// simulator and the scenario enumerator.
+pub(crate) struct SimBracket {
+    pub(crate) teams: Vec<SimTeam>,
+    team_index: HashMap<String, usize>,
+    /// Games in play order: every feeder precedes the game it feeds.
+    pub(crate) nodes: Vec<Node>,
+}
+
+impl SimBracket {
+    pub(crate) fn build(tournament: &Tournament, model: &WinModel) -> Self {
+        let mut sim = SimBracket {
+            teams: Vec::new(),
+            team_index: HashMap::new(),
+            nodes: Vec::new(),
+        };
+        let ratings = match model {
+            WinModel::Ratings(r) => Some(r),
+            WinModel::Seed => None,
+        };
+
+        let ordered: Vec<_> = RoundKind::ALL
+            .iter()
+            .flat_map(|&kind| {
+                tournament
+                    .regions
+                    .iter()
+                    .flat_map(|r| r.rounds.iter())
+                    .filter(move |r| r.kind == kind)
+                    .flat_map(|r| r.games.iter().map(move |g| (r.kind, g)))
+            })
+            .collect();
+        let node_index: HashMap<&str, usize> = ordered
+            .iter()
+            .enumerate()
+            .map(|(i, (_, g))| (g.id.as_str(), i))
+            .collect();
+
+        for (round, game) in &ordered {
+            let feeders = tournament.slot_feeders(game);
+            let mut slots = [Source::Empty; 2];
+            for (side, (seed, feeder)) in [(&game.top, feeders.0), (&game.bottom, feeders.1)]
+                .into_iter()
+                .enumerate()
+            {
+                slots[side] = if let Some(team) = &seed.team {
+                    Source::Team(sim.intern(team, seed.seed, ratings))
+                } else if let Some(&i) = feeder.as_deref().and_then(|f| node_index.get(f)) {
+                    Source::Winner(i)
+                } else {
+                    Source::Empty
+                };
+            }
+            let decided = match (&game.status, game.winner_id.as_deref()) {
+                (GameStatus::Final, Some(w)) => [&game.top, &game.bottom]
+                    .iter()
+                    .position(|s| s.team.as_ref().is_some_and(|t| t.id == w)),
+                _ => None,
+            };
+            sim.nodes.push(Node {
+                id: game.id.clone(),
+                round: *round,
+                slots,
+                decided,
+            });
+        }
+        sim
+    }
+
+    fn intern(&mut self, team: &Team, seed: u8, ratings: Option<&Ratings>) -> usize {
+        if let Some(&i) = self.team_index.get(&team.id) {
+            return i;
+        }
+        self.teams.push(SimTeam {
+            name: team.short_name.clone(),
+            seed,
+            rating: ratings.and_then(|r| r.get(team)),
+        });
+        self.team_index
+            .insert(team.id.clone(), self.teams.len() - 1);
+        self.teams.len() - 1
+    }
+
+    pub(crate) fn resolve_picks(&self, entry: &BracketPicks) -> Vec<Pick> {
+        self.nodes
+            .iter()
+            .map(|node| match entry.selections.get(&node.id) {
+                None => Pick::None,
+                Some(sel) if *sel == format!("top:{}", node.id) => Pick::Side(0),
+                Some(sel) if *sel == format!("bottom:{}", node.id) => Pick::Side(1),
+                Some(sel) => self
+                    .team_index
+                    .get(sel)
+                    .map_or(Pick::None, |&t| Pick::Team(t)),
+            })
+            .collect()
+    }
+
+    /// Teams in node `i`'s slots given the results of earlier nodes.
+    pub(crate) fn slot_teams(&self, i: usize, outcome: &[Outcome]) -> [Option<usize>; 2] {
+        self.nodes[i].slots.map(|s| match s {
+            Source::Team(t) => Some(t),
+            Source::Winner(n) => outcome[n].map(|(w, _, _)| w),
+            Source::Empty => None,
+        })
+    }
+
+    /// Play one run, writing each node's result into `outcome`.
+    fn play(&self, model: &WinModel, rng: &mut SplitMix64, outcome: &mut [Outcome]) {
+        for (i, node) in self.nodes.iter().enumerate() {
+            let teams = self.slot_teams(i, outcome);
+            let winner_side = match (node.decided, teams) {
+                (Some(side), _) => Some(side),
+                (None, [Some(a), Some(b)]) => {
+                    let p = model.probability(&self.teams[a], &self.teams[b]);
+                    Some(if rng.next_f64() < p { 0 } else { 1 })
+                }
+                // A slot nobody can fill yet: the known team walks through.
+                (None, [Some(_), None]) => Some(0),
+                (None, [None, Some(_)]) => Some(1),
+                (None, [None, None]) => None,
+            };
+            outcome[i] =
+                winner_side.and_then(|side| teams[side].map(|w| (w, teams[1 - side], side)));
+        }
+    }
+
+    fn score(&self, picks: &[Pick], outcome: &[Outcome], rule: ScoringRule) -> u32 {
+        (0..self.nodes.len())
+            .map(|i| self.points(i, picks[i], outcome[i], rule))
+            .sum()
+    }
+
+    /// Points `pick` earns at node `i` given its result.
+    pub(crate) fn points(&self, i: usize, pick: Pick, result: Outcome, rule: ScoringRule) -> u32 {
+        let Some((winner, loser, side)) = result else {
+            return 0;
+        };
+        let hit = match pick {
+            Pick::Team(t) => t == winner,
+            Pick::Side(s) => s == side,
+            Pick::None => false,
+        };
+        if !hit {
+            return 0;
+        }
+        let loser_seed = loser.map(|l| self.teams[l].seed);
+        rule.points(self.nodes[i].round, self.teams[winner].seed, loser_seed)
+    }
+}
+
+/// Small, dependency-free PRNG; plenty for simulation and reproducible by seed.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Game, Region, Round, TeamSeed};
+
+    fn seeded(seed: u8, id: &str) -> TeamSeed {
+        TeamSeed {
+            seed,
+            team: Some(Team {
+                id: id.into(),
+                name: id.into(),
+                ..Default::default()
+            }),
+            placeholder: None,
+        }
+    }
+
+    /// Four-team region: 1 v 16 (already final), 8 v 9, then the final.
+    fn tournament() -> Tournament {
+        let mut t = Tournament {
+            regions: vec![Region {
+                name: "East".into(),
+                rounds: vec![
+                    Round {
+                        kind: RoundKind::First,
+                        games: vec![
+                            Game {
+                                id: "g1".into(),
+                                top: seeded(1, "duke"),
+                                bottom: seeded(16, "amer"),
+                                status: GameStatus::Final,
+                                winner_id: Some("duke".into()),
+                                ..Default::default()
+                            },
+                            Game {
+                                id: "g2".into(),
+                                top: seeded(8, "msu"),
+                                bottom: seeded(9, "bay"),
+                                ..Default::default()
+                            },
+                        ],
+                    },
+                    Round {
+                        kind: RoundKind::Second,
+                        games: vec![Game {
+                            id: "g3".into(),
+                            ..Default::default()
+                        }],
+                    },
+                ],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        t.link_games();
+        t.advance_winners();
+        t
+    }
+
+    fn entry(user: &str, picks: &[(&str, &str)]) -> BracketPicks {
+        BracketPicks {
+            user_id: user.into(),
+            year: 2026,
+            selections: picks
+                .iter()
+                .map(|(g, t)| (g.to_string(), t.to_string()))
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn seed_model_favors_the_better_seed() {
+        let model = WinModel::Seed;
+        let one = SimTeam {
+            name: "A".into(),
+            seed: 1,
+            rating: None,
+        };
+        let sixteen = SimTeam {
+            name: "B".into(),
+            seed: 16,
+            rating: None,
+        };
+        let p = model.probability(&one, &sixteen);
+        assert!(p > 0.9 && p < 0.99, "{p}");
+        assert!((model.probability(&one, &one) - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn ratings_override_seeds_for_covered_teams() {
+        let ratings = Ratings::from_json(r#"{"Duke": 30.0, "amer": 0.0}"#).unwrap();
+        let duke = Team {
+            id: "150".into(),
+            name: "Duke".into(),
+            ..Default::default()
+        };
+        assert_eq!(ratings.get(&duke), Some(30.0));
+        assert!(Ratings::from_json("[1, 2]").is_err());
+    }
+
+    #[test]
+    fn odds_sum_to_one_and_respect_decided_games() {
+        let t = tournament();
+        let entries = [
+            entry("chalk", &[("g1", "duke"), ("g2", "msu"), ("g3", "duke")]),
+            entry("busted", &[("g1", "amer"), ("g2", "bay"), ("g3", "amer")]),
+        ];
+        let odds = simulate_pool(
+            &t,
+            &entries,
+            ScoringRule::EspnStandard,
+            &WinModel::Seed,
+            2_000,
+            7,
+        );
+
+        let total: f64 = odds.iter().map(|o| o.win_probability).sum();
+        assert!((total - 1.0).abs() < 1e-9);
+        assert!(odds[0].win_probability > odds[1].win_probability);
+        // Chalk banks 1 for g1; g3 is worth 2 and Duke (1 seed) is a heavy favorite.
+        assert!(odds[0].expected_points > 2.0 && odds[0].expected_points <= 4.0);
+        // Busted can only ever score on the 8/9 game.
+        assert!(odds[1].expected_points <= 1.0);
+    }
+
+    #[test]
+    fn same_seed_gives_same_result() {
+        let t = tournament();
+        let entries = [entry("a", &[("g2", "msu")]), entry("b", &[("g2", "bay")])];
+        let run = |seed| {
+            simulate_pool(
+                &t,
+                &entries,
+                ScoringRule::EspnStandard,
+                &WinModel::Seed,
+                500,
+                seed,
+            )
+        };
+        assert_eq!(run(42), run(42));
+    }
+}
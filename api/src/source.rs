@@ -0,0 +1,399 @@
+//! Where [`NcaaApi`](crate::client::NcaaApi) gets its data.
+//!
+//! Brackets come from a chain of [`BracketSource`]s tried in order; live
+//! scores and game detail come from one [`LiveSource`]. The built-in sources
+//! take their base URL as an argument, so a mirror or a local stand-in server
+//! can replace the public APIs.
+use crate::client::{
+    ApiError, ApiResult, candidate_years, infer_year_from_path, load_embedded_fallback_tournament,
+    map_event_to_game, map_ncaa_championship, map_summary, map_tournament, select_tournament_entry,
+};
+use crate::espn::{ScoreboardResponse, SummaryResponse, TournamentsResponse};
+use crate::henrygd::HenrygdResponse;
+use crate::{Game, GameDetail, Gender, Tournament};
+use reqwest::Client;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::str::FromStr;
+use std::time::Duration;
+
+pub const HENRYGD_URL: &str = "https://ncaa-api.henrygd.me";
+pub const ESPN_URL: &str = "https://site.api.espn.com/apis";
+
+/// Boxed so the traits stay object safe.
+pub type SourceFuture<'a, T> = Pin<Box<dyn Future<Output = ApiResult<T>> + Send + 'a>>;
+
+/// Something that can produce a tournament bracket.
+pub trait BracketSource: fmt::Debug + Send + Sync {
+    /// Short name for errors and configuration.
+    fn name(&self) -> &str;
+
+    /// The bracket for the tournament held in `year`. Sources holding a
+    /// single snapshot return it whatever the year.
+    fn bracket(&self, year: u16) -> SourceFuture<'_, Tournament>;
+}
+
+/// Something that can produce live scores and game detail.
+pub trait LiveSource: fmt::Debug + Send + Sync {
+    /// Tournament games on today's scoreboard.
+    fn scoreboard(&self) -> SourceFuture<'_, Vec<Game>>;
+
+    /// Play-by-play and box score for one game.
+    fn game_detail<'a>(&'a self, game_id: &'a str) -> SourceFuture<'a, GameDetail>;
+}
+
+/// The built-in bracket sources, by the names used in configuration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SourceKind {
+    Henrygd,
+    Espn,
+    Embedded,
+}
+
+impl SourceKind {
+    /// The order `NcaaApi` uses unless told otherwise.
+    pub const DEFAULT_ORDER: [SourceKind; 3] =
+        [SourceKind::Henrygd, SourceKind::Espn, SourceKind::Embedded];
+}
+
+impl FromStr for SourceKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "henrygd" | "ncaa" => Ok(SourceKind::Henrygd),
+            "espn" => Ok(SourceKind::Espn),
+            "embedded" => Ok(SourceKind::Embedded),
+            other => Err(format!(
+                "unknown bracket source {other} (henrygd, espn or embedded)"
+            )),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// HTTP
+// ---------------------------------------------------------------------------
+
+/// Shared HTTP client for the network sources.
+#[derive(Debug, Clone)]
+pub struct Http {
+    client: Client,
+    timeout: Duration,
+}
+
+impl Default for Http {
+    fn default() -> Self {
+        Self {
+            client: Client::builder()
+                .user_agent("mmtui/0.1 (terminal bracket viewer)")
+                .build()
+                .unwrap_or_default(),
+            timeout: Duration::from_secs(10),
+        }
+    }
+}
+
+impl Http {
+    /// GET `url` as JSON. Client errors (4xx) read as an empty response.
+    pub async fn get<T: Default + serde::de::DeserializeOwned>(&self, url: &str) -> ApiResult<T> {
+        let response = self
+            .client
+            .get(url)
+            .timeout(self.timeout)
+            .send()
+            .await
+            .map_err(|e| ApiError::Network(e, url.to_owned()))?;
+
+        match response.error_for_status() {
+            Ok(res) => res
+                .json::<T>()
+                .await
+                .map_err(|e| ApiError::Parsing(e, url.to_owned())),
+            Err(e) => {
+                if e.status().map(|s| s.is_client_error()).unwrap_or(false) {
+                    Ok(T::default())
+                } else {
+                    Err(ApiError::Api(e, url.to_owned()))
+                }
+            }
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// henrygd NCAA API
+// ---------------------------------------------------------------------------
+
+/// NCAA bracket topology from the henrygd API.
+#[derive(Debug, Clone)]
+pub struct Henrygd {
+    http: Http,
+    base_url: String,
+    gender: Gender,
+}
+
+impl Henrygd {
+    pub fn new(http: Http, base_url: impl Into<String>, gender: Gender) -> Self {
+        Self {
+            http,
+            base_url: base_url.into().trim_end_matches('/').to_owned(),
+            gender,
+        }
+    }
+}
+
+impl BracketSource for Henrygd {
+    fn name(&self) -> &str {
+        "henrygd"
+    }
+
+    fn bracket(&self, year: u16) -> SourceFuture<'_, Tournament> {
+        Box::pin(async move {
+            let url = format!(
+                "{}/brackets/basketball-{}/d1/{year}",
+                self.base_url,
+                self.gender.slug()
+            );
+            let raw = self.http.get::<HenrygdResponse>(&url).await?;
+            raw.championships
+                .into_iter()
+                .next()
+                .filter(|champ| !champ.games.is_empty())
+                .map(|champ| map_ncaa_championship(champ, self.gender))
+                .ok_or_else(|| ApiError::NotFound(format!("no championship data for {year}")))
+        })
+    }
+}
+
+// ---------------------------------------------------------------------------
+// ESPN
+// ---------------------------------------------------------------------------
+
+/// ESPN's public APIs: brackets, the scoreboard and game summaries.
+#[derive(Debug, Clone)]
+pub struct Espn {
+    http: Http,
+    base_url: String,
+    gender: Gender,
+}
+
+impl Espn {
+    pub fn new(http: Http, base_url: impl Into<String>, gender: Gender) -> Self {
+        Self {
+            http,
+            base_url: base_url.into().trim_end_matches('/').to_owned(),
+            gender,
+        }
+    }
+
+    /// `{base}/{api}/sports/basketball/{mens,womens}-college-basketball`.
+    fn sport_url(&self, api: &str) -> String {
+        let sport = match self.gender {
+            Gender::Men => "mens-college-basketball",
+            Gender::Women => "womens-college-basketball",
+        };
+        format!("{}/{api}/sports/basketball/{sport}", self.base_url)
+    }
+}
+
+impl BracketSource for Espn {
+    fn name(&self) -> &str {
+        "espn"
+    }
+
+    /// ESPN files some tournaments under an adjacent season, so nearby years
+    /// are tried too, nearest first.
+    fn bracket(&self, year: u16) -> SourceFuture<'_, Tournament> {
+        Box::pin(async move {
+            let mut last_error = None;
+            for year in candidate_years(i32::from(year)) {
+                let url = format!("{}/tournaments?limit=25&year={year}", self.sport_url("v2"));
+                let result = self
+                    .http
+                    .get::<TournamentsResponse>(&url)
+                    .await
+                    .and_then(|raw| {
+                        select_tournament_entry(raw.tournaments.unwrap_or_default(), year)
+                    });
+                match result {
+                    Ok(entry) => return Ok(map_tournament(entry, year as u16, self.gender)),
+                    Err(e) => last_error = Some(e),
+                }
+            }
+            Err(last_error.unwrap_or_else(|| {
+                ApiError::NotFound(format!("NCAA tournament bracket not found near {year}"))
+            }))
+        })
+    }
+}
+
+impl LiveSource for Espn {
+    /// groups=100 filters to tournament games on ESPN's scoreboard.
+    fn scoreboard(&self) -> SourceFuture<'_, Vec<Game>> {
+        Box::pin(async move {
+            let url = format!(
+                "{}/scoreboard?groups=100&limit=50",
+                self.sport_url("site/v2")
+            );
+            let raw: ScoreboardResponse = self.http.get(&url).await?;
+            Ok(raw
+                .events
+                .unwrap_or_default()
+                .iter()
+                .map(map_event_to_game)
+                .collect())
+        })
+    }
+
+    fn game_detail<'a>(&'a self, game_id: &'a str) -> SourceFuture<'a, GameDetail> {
+        Box::pin(async move {
+            let url = format!("{}/summary?event={game_id}", self.sport_url("site/v2"));
+            let raw: SummaryResponse = self.http.get(&url).await?;
+            Ok(map_summary(game_id, raw))
+        })
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Snapshots
+// ---------------------------------------------------------------------------
+
+/// An ESPN-format tournament JSON file on disk. The year comes from the file
+/// name when it has one.
+#[derive(Debug, Clone)]
+pub struct LocalFile {
+    path: String,
+    gender: Gender,
+}
+
+impl LocalFile {
+    pub fn new(path: impl Into<String>, gender: Gender) -> Self {
+        Self {
+            path: path.into(),
+            gender,
+        }
+    }
+}
+
+impl BracketSource for LocalFile {
+    fn name(&self) -> &str {
+        "file"
+    }
+
+    fn bracket(&self, year: u16) -> SourceFuture<'_, Tournament> {
+        Box::pin(async move {
+            let path = &self.path;
+            let content = std::fs::read_to_string(path)
+                .map_err(|e| ApiError::NotFound(format!("could not read {path}: {e}")))?;
+            let raw: TournamentsResponse = serde_json::from_str(&content).map_err(|e| {
+                ApiError::NotFound(format!("invalid tournament json at {path}: {e}"))
+            })?;
+            let year = infer_year_from_path(path).unwrap_or(year);
+            let entry =
+                select_tournament_entry(raw.tournaments.unwrap_or_default(), i32::from(year))?;
+            Ok(map_tournament(entry, year, self.gender))
+        })
+    }
+}
+
+/// The 2025 men's bracket compiled into the binary; the last-resort offline
+/// fallback.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Embedded {
+    gender: Gender,
+}
+
+impl Embedded {
+    pub fn new(gender: Gender) -> Self {
+        Self { gender }
+    }
+}
+
+impl BracketSource for Embedded {
+    fn name(&self) -> &str {
+        "embedded"
+    }
+
+    fn bracket(&self, _year: u16) -> SourceFuture<'_, Tournament> {
+        Box::pin(async move {
+            match self.gender {
+                Gender::Men => load_embedded_fallback_tournament(),
+                Gender::Women => Err(ApiError::NotFound(
+                    "no embedded women's bracket".to_string(),
+                )),
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::NcaaApi;
+    use mockito::Matcher;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn falls_through_to_the_next_source_at_its_base_url() {
+        let mut server = mockito::Server::new_async().await;
+        let henrygd = server
+            .mock("GET", "/ncaa/brackets/basketball-men/d1/2025")
+            .with_status(404)
+            .create_async()
+            .await;
+        let espn = server
+            .mock(
+                "GET",
+                "/espn/v2/sports/basketball/mens-college-basketball/tournaments",
+            )
+            .match_query(Matcher::UrlEncoded("year".into(), "2025".into()))
+            .with_body(include_str!("../../2025_bracket.json"))
+            .create_async()
+            .await;
+
+        let http = Http::default();
+        let api = NcaaApi::new().with_bracket_sources(vec![
+            Arc::new(Henrygd::new(
+                http.clone(),
+                format!("{}/ncaa", server.url()),
+                Gender::Men,
+            )),
+            Arc::new(Espn::new(
+                http,
+                format!("{}/espn/", server.url()),
+                Gender::Men,
+            )),
+        ]);
+        let tournament = api.fetch_ncaa_bracket(2025).await.unwrap();
+        assert_eq!(tournament.year, 2025);
+        henrygd.assert_async().await;
+        espn.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn live_scores_come_from_the_configured_source() {
+        let mut server = mockito::Server::new_async().await;
+        let scoreboard = server
+            .mock(
+                "GET",
+                "/site/v2/sports/basketball/womens-college-basketball/scoreboard",
+            )
+            .match_query(Matcher::Any)
+            .with_body(r#"{"events": []}"#)
+            .create_async()
+            .await;
+
+        let espn = Espn::new(Http::default(), server.url(), Gender::Women);
+        let api = NcaaApi::new().with_live_source(Arc::new(espn));
+        assert!(api.fetch_scoreboard().await.unwrap().is_empty());
+        scoreboard.assert_async().await;
+
+        let women = NcaaApi::with_sources(Gender::Women, &[SourceKind::Embedded], "", "");
+        assert!(women.fetch_tournament().await.is_err());
+
+        assert_eq!("ESPN".parse(), Ok(SourceKind::Espn));
+        assert!("mirror".parse::<SourceKind>().is_err());
+    }
+}
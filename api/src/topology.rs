@@ -0,0 +1,379 @@
+//! Parent/child links between bracket games and winner advancement.
+//!
+//! NCAA (henrygd) brackets carry `victorBracketPositionId` on every game, which
+//! maps straight onto `Game.next_game_id`. The ESPN fallback has no such field,
+//! so missing links are inferred from the bracket's shape: regional rounds are
+//! stored in bracket order (game `i` feeds game `i / 2`), First Four winners
+//! drop into the first-round game holding their seed, and regional champions
+//! meet in the Final Four game that already lists them (or by region pairing
+//! before the field is known).
+use crate::{Game, RoundKind, TeamSeed, Tournament};
+use serde::{Deserialize, Serialize};
+
+/// Which side of a bracket game a team plays in, or a winner lands on.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Slot {
+    #[default]
+    Top,
+    Bottom,
+}
+
+/// Fill in `next_game_id` for every game that does not have one yet.
+///
+/// Links already present (from the NCAA feed) are left untouched.
+pub fn link_games(t: &mut Tournament) {
+    let links = infer_links(t);
+    for (game_id, next_id) in links {
+        if let Some(game) = t.find_game_mut(&game_id) {
+            game.next_game_id = Some(next_id);
+        }
+    }
+}
+
+/// Games whose winner advances into `game_id`, in bracket order.
+pub fn feeders<'a>(t: &'a Tournament, game_id: &str) -> Vec<&'a Game> {
+    t.games()
+        .filter(|g| g.next_game_id.as_deref() == Some(game_id))
+        .collect()
+}
+
+/// Decide which of a game's feeders fills its top and bottom slots.
+///
+/// A feeder whose team already sits in a slot claims that slot (results or
+/// First Four winners already advanced); the rest take the structural order
+/// (first feeder on top) or whichever slot is still open.
+pub fn slot_feeders(t: &Tournament, game: &Game) -> (Option<String>, Option<String>) {
+    let feeders = feeders(t, &game.id);
+    let team_in = |seed: &TeamSeed, feeder: &Game| {
+        seed.team.as_ref().is_some_and(|t| {
+            [&feeder.top, &feeder.bottom]
+                .iter()
+                .any(|s| s.team.as_ref().is_some_and(|ft| ft.id == t.id))
+        })
+    };
+
+    let (mut top, mut bottom) = (None, None);
+    let mut rest = Vec::new();
+    for (i, feeder) in feeders.iter().enumerate() {
+        if top.is_none() && team_in(&game.top, feeder) {
+            top = Some(feeder.id.clone());
+        } else if bottom.is_none() && team_in(&game.bottom, feeder) {
+            bottom = Some(feeder.id.clone());
+        } else {
+            rest.push((i, feeder.id.clone()));
+        }
+    }
+    for (i, id) in rest {
+        let prefer_top = feeders.len() == 2 && i == 0;
+        let top_open = top.is_none() && (feeders.len() == 2 || game.top.team.is_none());
+        let bottom_open = bottom.is_none() && (feeders.len() == 2 || game.bottom.team.is_none());
+        if top_open && (prefer_top || !bottom_open) {
+            top = Some(id);
+        } else if bottom_open {
+            bottom = Some(id);
+        }
+    }
+    (top, bottom)
+}
+
+/// Copy every decided winner into its parent game's open slot.
+///
+/// A slot is only written when it is empty (TBA / "Winner of #42") or still
+/// holds a team from the same feeder game, so results already published by the
+/// upstream API are never overwritten. Returns the number of slots filled.
+pub fn advance_winners(t: &mut Tournament) -> usize {
+    let mut moves: Vec<(String, Slot, TeamSeed)> = Vec::new();
+
+    for game in t.games() {
+        let Some(next_id) = game.next_game_id.as_deref() else {
+            continue;
+        };
+        let Some(winner) = winning_side(game) else {
+            continue;
+        };
+        let Some(parent) = t.games().find(|g| g.id == next_id) else {
+            continue;
+        };
+        let winner_id = winner.team.as_ref().map(|t| t.id.as_str());
+        if team_id(&parent.top) == winner_id || team_id(&parent.bottom) == winner_id {
+            continue;
+        }
+
+        let siblings = feeders(t, next_id);
+        let structural = match siblings.iter().position(|g| g.id == game.id) {
+            Some(0) if siblings.len() == 2 => Some(Slot::Top),
+            Some(1) => Some(Slot::Bottom),
+            _ => None,
+        };
+
+        let from_feeder = |s: &TeamSeed| {
+            let id = team_id(s);
+            id.is_some() && (id == team_id(&game.top) || id == team_id(&game.bottom))
+        };
+        let target = [Slot::Top, Slot::Bottom]
+            .into_iter()
+            .find(|&s| from_feeder(side(parent, s)))
+            .or(structural.filter(|&s| side(parent, s).team.is_none()))
+            .or_else(|| {
+                [Slot::Top, Slot::Bottom]
+                    .into_iter()
+                    .find(|&s| side(parent, s).team.is_none())
+            });
+
+        if let Some(slot) = target {
+            moves.push((next_id.to_owned(), slot, winner.clone()));
+        }
+    }
+
+    let filled = moves.len();
+    for (parent_id, slot, winner) in moves {
+        if let Some(parent) = t.find_game_mut(&parent_id) {
+            let dest = match slot {
+                Slot::Top => &mut parent.top,
+                Slot::Bottom => &mut parent.bottom,
+            };
+            *dest = TeamSeed {
+                placeholder: None,
+                ..winner
+            };
+        }
+    }
+    filled
+}
+
+fn team_id(s: &TeamSeed) -> Option<&str> {
+    s.team.as_ref().map(|t| t.id.as_str())
+}
+
+fn side(game: &Game, slot: Slot) -> &TeamSeed {
+    match slot {
+        Slot::Top => &game.top,
+        Slot::Bottom => &game.bottom,
+    }
+}
+
+/// The side that won a finished game, if the winner matches one of its teams.
+fn winning_side(game: &Game) -> Option<&TeamSeed> {
+    if game.status != crate::GameStatus::Final {
+        return None;
+    }
+    let winner = game.winner_id.as_deref()?;
+    [&game.top, &game.bottom]
+        .into_iter()
+        .find(|s| team_id(s) == Some(winner))
+}
+
+/// Structural links for games that have no `next_game_id`.
+fn infer_links(t: &Tournament) -> Vec<(String, String)> {
+    let mut links = Vec::new();
+    let national = t.regions.iter().find(|r| r.name == "National");
+    let regional: Vec<_> = t.regions.iter().filter(|r| r.name != "National").collect();
+    for (region_idx, region) in regional.iter().enumerate() {
+        for round in &region.rounds {
+            let unlinked = round
+                .games
+                .iter()
+                .enumerate()
+                .filter(|(_, g)| g.next_game_id.is_none());
+            match round.kind {
+                RoundKind::FirstFour => {
+                    let firsts = round_games(&region.rounds, RoundKind::First);
+                    for (_, game) in unlinked {
+                        // ESPN occasionally files a play-in under the wrong
+                        // region, so a team already listed elsewhere wins.
+                        let listed = regional
+                            .iter()
+                            .flat_map(|r| round_games(&r.rounds, RoundKind::First))
+                            .find(|f| shares_team(game, f));
+                        if let Some(next) = listed.or_else(|| first_four_target(game, &firsts)) {
+                            links.push((game.id.clone(), next.id.clone()));
+                        }
+                    }
+                }
+                RoundKind::Elite8 => {
+                    let Some(national) = national else { continue };
+                    let semis = round_games(&national.rounds, RoundKind::FinalFour);
+                    for (_, game) in unlinked {
+                        let next = semis
+                            .iter()
+                            .find(|s| shares_team(game, s))
+                            .or_else(|| semis.get(region_idx / 2));
+                        if let Some(next) = next {
+                            links.push((game.id.clone(), next.id.clone()));
+                        }
+                    }
+                }
+                kind => {
+                    let Some(next_kind) = kind.next() else {
+                        continue;
+                    };
+                    let nexts = round_games(&region.rounds, next_kind);
+                    for (idx, game) in unlinked {
+                        if let Some(next) = nexts.get(idx / 2) {
+                            links.push((game.id.clone(), next.id.clone()));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    if let Some(national) = national {
+        let finals = round_games(&national.rounds, RoundKind::Championship);
+        if let Some(final_game) = finals.first() {
+            for semi in round_games(&national.rounds, RoundKind::FinalFour) {
+                if semi.next_game_id.is_none() {
+                    links.push((semi.id.clone(), final_game.id.clone()));
+                }
+            }
+        }
+    }
+
+    links
+}
+
+fn round_games(rounds: &[crate::Round], kind: RoundKind) -> Vec<&Game> {
+    rounds
+        .iter()
+        .filter(|r| r.kind == kind)
+        .flat_map(|r| r.games.iter())
+        .collect()
+}
+
+/// First-round game in the region with an open slot at the play-in seed.
+fn first_four_target<'a>(game: &Game, firsts: &[&'a Game]) -> Option<&'a Game> {
+    let seed = game.top.seed.max(game.bottom.seed);
+    firsts
+        .iter()
+        .find(|f| {
+            [&f.top, &f.bottom]
+                .into_iter()
+                .any(|s| s.team.is_none() && (s.seed == seed || s.seed == 0))
+                && (f.top.seed == seed || f.bottom.seed == seed || f.top.seed + seed == 17)
+        })
+        .copied()
+}
+
+fn shares_team(a: &Game, b: &Game) -> bool {
+    [&a.top, &a.bottom].into_iter().any(|x| {
+        team_id(x).is_some() && (team_id(x) == team_id(&b.top) || team_id(x) == team_id(&b.bottom))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GameStatus, Region, Round, Team};
+
+    fn seeded(seed: u8, id: &str) -> TeamSeed {
+        TeamSeed {
+            seed,
+            team: Some(Team {
+                id: id.into(),
+                name: id.into(),
+                short_name: id.into(),
+                ..Default::default()
+            }),
+            placeholder: None,
+        }
+    }
+
+    fn tba(placeholder: &str) -> TeamSeed {
+        TeamSeed {
+            seed: 0,
+            team: None,
+            placeholder: Some(placeholder.into()),
+        }
+    }
+
+    fn game(id: &str, top: TeamSeed, bottom: TeamSeed) -> Game {
+        Game {
+            id: id.into(),
+            top,
+            bottom,
+            ..Default::default()
+        }
+    }
+
+    fn final_game(id: &str, top: TeamSeed, bottom: TeamSeed, winner: &str) -> Game {
+        Game {
+            status: GameStatus::Final,
+            winner_id: Some(winner.into()),
+            ..game(id, top, bottom)
+        }
+    }
+
+    /// One region with a play-in game, two first-round games and their
+    /// second-round game, all unlinked (ESPN-style).
+    fn espn_region() -> Tournament {
+        Tournament {
+            regions: vec![Region {
+                id: "east".into(),
+                name: "East".into(),
+                rounds: vec![
+                    Round {
+                        kind: RoundKind::FirstFour,
+                        games: vec![final_game("ff", seeded(16, "a"), seeded(16, "b"), "b")],
+                    },
+                    Round {
+                        kind: RoundKind::First,
+                        games: vec![
+                            final_game("g1", seeded(1, "duke"), tba("Winner of #ff"), "duke"),
+                            game("g2", seeded(8, "msu"), seeded(9, "bay")),
+                        ],
+                    },
+                    Round {
+                        kind: RoundKind::Second,
+                        games: vec![game("g3", tba("TBA"), tba("TBA"))],
+                    },
+                ],
+            }],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn structural_links_follow_bracket_order_and_seed() {
+        let mut t = espn_region();
+        link_games(&mut t);
+        let next = |id: &str| t.games().find(|g| g.id == id).unwrap().next_game_id.clone();
+        assert_eq!(next("ff").as_deref(), Some("g1"));
+        assert_eq!(next("g1").as_deref(), Some("g3"));
+        assert_eq!(next("g2").as_deref(), Some("g3"));
+        assert_eq!(next("g3"), None);
+        let feeder_ids: Vec<_> = feeders(&t, "g3").iter().map(|g| g.id.as_str()).collect();
+        assert_eq!(feeder_ids, ["g1", "g2"]);
+    }
+
+    #[test]
+    fn winners_fill_open_slots_and_clear_placeholders() {
+        let mut t = espn_region();
+        link_games(&mut t);
+        assert_eq!(advance_winners(&mut t), 2);
+
+        let g1 = t.find_game_mut("g1").unwrap();
+        assert_eq!(g1.bottom.team.as_ref().unwrap().id, "b");
+        assert_eq!(g1.bottom.seed, 16);
+        assert_eq!(g1.bottom.placeholder, None);
+
+        let g3 = t.find_game_mut("g3").unwrap();
+        assert_eq!(g3.top.team.as_ref().unwrap().id, "duke");
+        assert!(g3.bottom.team.is_none());
+
+        // Nothing left to move once the slots are filled.
+        assert_eq!(advance_winners(&mut t), 0);
+    }
+
+    #[test]
+    fn published_results_are_not_overwritten() {
+        let mut t = espn_region();
+        link_games(&mut t);
+        // Upstream already placed Baylor on top of the second-round game.
+        t.find_game_mut("g3").unwrap().top = seeded(9, "bay");
+        advance_winners(&mut t);
+        let g3 = t.find_game_mut("g3").unwrap();
+        assert_eq!(g3.top.team.as_ref().unwrap().id, "bay");
+        assert_eq!(g3.bottom.team.as_ref().unwrap().id, "duke");
+    }
+}
@@ -1,18 +1,27 @@
-use crate::state::app_settings::AppSettings;
-use crate::state::app_state::{AppState, BracketPicks, ChatMessage, CompareRow};
-use crate::state::chat::ChatWireMessage;
+use crate::state::announce::{self, GameEvent};
+use crate::state::app_settings::{AppSettings, config_file};
+use crate::state::app_state::{
+    AppState, BracketPicks, BracketState, ChatMessage, ChatState, CompareRow, Delivery,
+};
+use crate::state::chat::{ChatCommand, ChatWireMessage};
 use crate::state::custodian::{
     CustodianConfig, CustodianEntry, CustodianWizardState,
     bip67_sort, compute_threshold, custodian_config_path,
 };
+use crate::state::messages::NetworkRequest;
+use crate::state::slash::{self, ChatInput};
 use bitcoin::address::Address;
 use bitcoin::key::PublicKey;
 use bitcoin::script::Builder;
 use bitcoin::opcodes;
 use bitcoin::Network;
-use chrono::Local;
-use ncaa_api::{Game, GameDetail, GameStatus, RoundKind, Tournament};
-use std::collections::HashSet;
+use chrono::{Local, Utc};
+use mmtui::seal;
+use ncaa_api::scenarios::EntrantPath;
+use ncaa_api::scoring::{ScoringRule, score_bracket};
+use ncaa_api::simulate::EntrantOdds;
+use ncaa_api::{Game, GameDetail, Gender, Tournament};
+use std::collections::HashMap;
 use std::path::PathBuf;
 use std::str::FromStr;
 
@@ -35,13 +44,12 @@ pub struct App {
 }
 
 impl App {
-    pub fn new() -> Self {
-        let settings = AppSettings::load();
+    pub fn new(settings: AppSettings) -> Self {
+        let mut state = AppState::new();
+        state.chat = ChatState::new(&settings.chat);
+        state.gender = settings.gender;
 
-        let mut app = Self {
-            state: AppState::new(),
-            settings,
-        };
+        let mut app = Self { state, settings };
 
         if let Some(level) = app.settings.log_level {
             log::set_max_level(level);
@@ -56,28 +64,123 @@ impl App {
     // Network response handlers — called from main_ui_loop
     // -----------------------------------------------------------------------
 
+    /// Ask the network worker to switch tournaments; data for the current
+    /// one is ignored until it confirms.
+    pub fn select_tournament(&mut self, gender: Gender) -> Option<NetworkRequest> {
+        if self.state.pending_gender.unwrap_or(self.state.gender) == gender {
+            return None;
+        }
+        self.state.pending_gender = Some(gender);
+        Some(NetworkRequest::SelectTournament { gender })
+    }
+
+    /// The worker now serves `gender`. Returns true when the tournament
+    /// changed and the bracket needs loading.
+    pub fn on_tournament_selected(&mut self, gender: Gender) -> bool {
+        self.state.pending_gender = None;
+        if gender == self.state.gender {
+            return false;
+        }
+        self.state.gender = gender;
+        self.state.bracket = BracketState {
+            full_view: self.state.bracket.full_view,
+            ..Default::default()
+        };
+        self.state.game_detail = Default::default();
+        self.state.live_feed = Default::default();
+        self.state.pick_wizard = Default::default();
+        self.state.compare = Default::default();
+        self.state
+            .chat
+            .push_system(format!("Switched to the {} tournament.", gender.label()));
+        true
+    }
+
     pub fn on_bracket_loaded(&mut self, tournament: Tournament) {
         self.state.last_error = None;
         self.state.bracket.load(tournament);
         self.state.live_feed = Default::default();
     }
 
-    pub fn on_scores_updated(&mut self, games: Vec<Game>) {
+    /// Merge a score refresh. Returns true when a result changed and the
+    /// loaded Compare entries were rescored.
+    pub fn on_scores_updated(&mut self, games: Vec<Game>) -> bool {
+        let before = self.state.bracket.tournament.as_ref().map(winners);
+        let snapshot: HashMap<String, Game> = self
+            .state
+            .bracket
+            .tournament
+            .iter()
+            .flat_map(|t| t.games())
+            .map(|g| (g.id.clone(), g.clone()))
+            .collect();
         self.state.bracket.merge_updates(games);
+        if let Some(t) = &self.state.bracket.tournament {
+            let events = announce::scoreboard_events(&snapshot, t, &self.settings.announce);
+            self.state
+                .notifier
+                .check(&snapshot, t, &self.settings.follow);
+            self.post_events(events);
+        }
+        let after = self.state.bracket.tournament.as_ref().map(winners);
+        if before == after || self.state.compare.entries.is_empty() {
+            return false;
+        }
+        self.rescore_compare();
+        true
     }
 
-    pub fn on_game_detail_loaded(&mut self, detail: GameDetail) {
+    pub fn on_game_detail_loaded(&mut self, mut detail: GameDetail) {
         self.state.last_error = None;
-        let previous_game_id = self.state.game_detail.detail.as_ref().map(|d| d.game_id.clone());
+        // Show the teams in the slots the bracket has them in.
+        if let Some(game) = self.state.bracket.tournament.as_ref().and_then(|t| {
+            t.games()
+                .find(|g| g.espn_id.as_deref() == Some(detail.game_id.as_str()))
+                .or_else(|| t.find_game(&detail.game_id))
+        }) {
+            detail.orient(game);
+        }
+        let previous_game_id = self
+            .state
+            .game_detail
+            .detail
+            .as_ref()
+            .map(|d| d.game_id.clone());
         let game_changed = previous_game_id.as_deref() != Some(detail.game_id.as_str());
 
         self.state.live_feed.update_from_detail(&detail);
+        // Plays are only "new" once the feed has seen this game before.
+        if !game_changed
+            && let Some(event) = announce::buzzer_beater(
+                &detail,
+                &self.state.live_feed.plays,
+                self.state.gender,
+                &self.settings.announce,
+            )
+        {
+            self.post_events(vec![event]);
+        }
         self.state.game_detail.detail = Some(detail);
         if game_changed {
             self.state.game_detail.scroll_offset = 0;
         }
     }
 
+    /// Post new game events to chat, queueing them for the room when
+    /// broadcasting is on.
+    fn post_events(&mut self, events: Vec<GameEvent>) {
+        let settings = &self.settings.announce;
+        for event in self.state.announcer.fresh(events, settings) {
+            // A broadcast goes out, and shows, as one of our own messages.
+            if settings.broadcast {
+                let send = self.state.chat.say(format!("📣 {}", event.text));
+                self.state.chat.outbox.push(send);
+            } else {
+                self.state.chat.announce(event.text);
+            }
+        }
+    }
+
     pub fn on_prize_pool_balance_updated(&mut self, balance_sat: u64) {
         self.state.prize_pool.balance_sat = balance_sat;
         self.state.prize_pool.loading = false;
@@ -99,9 +202,6 @@ impl App {
         if self.state.active_tab == MenuItem::PickWizard {
             self.start_pick_wizard();
         }
-        if self.state.active_tab == MenuItem::Compare {
-            self.load_compare_sources();
-        }
     }
 
     pub fn exit_help(&mut self) {
@@ -146,6 +246,10 @@ impl App {
         self.state.bracket.cycle_region();
     }
 
+    pub fn bracket_toggle_full_view(&mut self) {
+        self.state.bracket.toggle_full_view();
+    }
+
     /// Returns (bracket_id, espn_id) if the user pressed Enter on a game.
     /// Switches to the GameDetail tab as a side-effect.
     pub fn bracket_select_game(&mut self) -> Option<(String, Option<String>)> {
@@ -166,6 +270,9 @@ impl App {
         self.state.animation.advance(frame_count);
     }
 
+    /// Compare loads, simulations and path analyses answer with their own
+    /// responses even when they fail, so an unrelated error (a missed score
+    /// refresh, say) leaves their in-flight flags alone.
     pub fn on_error(&mut self, message: String) {
         self.state.last_error = Some(message);
     }
@@ -182,49 +289,136 @@ impl App {
             self.state.chat.push_system("chat disconnected, retrying...");
         }
         self.state.chat.connected = false;
+        self.state.chat.members.clear();
     }
 
     pub fn on_chat_error(&mut self, message: String) {
         self.state.chat.push_system(format!("chat error: {message}"));
     }
 
+    pub fn on_chat_rejected(&mut self, id: String, reason: String) {
+        self.state.chat.set_delivery(&id, Delivery::Failed);
+        self.state
+            .chat
+            .push_system(format!("message not delivered: {reason}"));
+    }
+
     pub fn on_chat_message(&mut self, msg: ChatWireMessage) {
+        let Some(verified) = self.state.chat.verify(&msg) else {
+            self.state.chat.push_system(format!(
+                "dropped a message from {} with a bad signature",
+                msg.author
+            ));
+            return;
+        };
+        // Everything relayed is shown as its author's, whatever id it carries;
+        // only this client's own announcements render as system lines.
         self.state.chat.ingest_message(ChatMessage {
             id: msg.id,
             author: msg.author,
             body: msg.body,
             timestamp: msg.timestamp,
             is_system: false,
+            verified,
+            delivery: None,
         });
     }
 
+    /// Run what was typed in the chat composer: a slash command, or a
+    /// message. Returns what the chat worker should send, if anything.
+    pub fn submit_chat_input(&mut self) -> Option<ChatCommand> {
+        let input = self.state.chat.take_input()?;
+        let chat = &mut self.state.chat;
+        let share = match slash::parse(&input) {
+            ChatInput::Say(body) | ChatInput::Emote(body) => Ok(body),
+            ChatInput::Nick(name) => return Some(chat.rejoin(chat.room.clone(), name)),
+            ChatInput::Join(room) => return Some(chat.rejoin(room, chat.username.clone())),
+            ChatInput::Score(team) => self.chat_score(&team),
+            ChatInput::Bracket(user) => self.chat_bracket(user),
+            ChatInput::Admin(command) if chat.admin_token.is_some() => {
+                return Some(ChatCommand::Admin { command });
+            }
+            ChatInput::Admin(command) => Err(format!(
+                "{} needs MMTUI_CHAT_ADMIN_TOKEN",
+                command.split_whitespace().next().unwrap_or_default()
+            )),
+            ChatInput::Help => Err(slash::HELP.to_string()),
+            ChatInput::Invalid(reason) => Err(reason),
+        };
+        match share {
+            Ok(body) => Some(self.state.chat.say(body)),
+            Err(note) => {
+                self.state.chat.push_system(note);
+                None
+            }
+        }
+    }
+
+    fn chat_score(&self, team: &str) -> Result<String, String> {
+        let tournament = self
+            .state
+            .bracket
+            .tournament
+            .as_ref()
+            .ok_or("bracket not loaded yet")?;
+        slash::score_line(tournament, team)
+    }
+
+    /// Your own picks come from Pick Wizard (or its saved file); anyone
+    /// else's from the entries loaded in Compare.
+    fn chat_bracket(&self, user: Option<String>) -> Result<String, String> {
+        let tournament = self
+            .state
+            .bracket
+            .tournament
+            .as_ref()
+            .ok_or("bracket not loaded yet")?;
+        let me = &self.state.chat.username;
+        let picks = match user.filter(|u| u != me) {
+            None if !self.state.pick_wizard.selections.is_empty() => {
+                self.state.pick_wizard.to_export(me.clone())
+            }
+            None => self
+                .load_pick_wizard_file()
+                .map_err(|_| "no picks yet; make some in Pick Wizard".to_string())?,
+            Some(user) => self
+                .state
+                .compare
+                .entries
+                .iter()
+                .map(|(_, picks)| picks)
+                .find(|p| p.user_id.eq_ignore_ascii_case(&user))
+                .cloned()
+                .ok_or_else(|| format!("no bracket for {user}; load it in Compare first"))?,
+        };
+        Ok(slash::bracket_summary(tournament, &picks))
+    }
+
     pub fn setup_prize_pool(&mut self) {
         let entries = self.load_custodian_entries();
         self.apply_custodian_entries(entries);
     }
 
-    /// Load custodian entries: file → env var → fake placeholders.
+    /// Load custodian entries: file → configured keys → fake placeholders.
     fn load_custodian_entries(&self) -> Vec<CustodianEntry> {
         // 1. Try custodians.json
         let path = custodian_config_path();
-        if let Ok(config) = CustodianConfig::load_from_path(&path) {
-            if config.custodians.len() >= 2 {
-                return config.custodians;
-            }
+        if let Ok(config) = CustodianConfig::load_from_path(&path)
+            && config.custodians.len() >= 2
+        {
+            return config.custodians;
         }
 
-        // 2. Try env var
-        if let Ok(keys_raw) = std::env::var("MMTUI_PRIZE_POOL_KEYS") {
-            let entries: Vec<CustodianEntry> = keys_raw
-                .split(',')
-                .enumerate()
-                .filter_map(|(i, s)| {
-                    CustodianEntry::new(&format!("Custodian {}", i + 1), s.trim()).ok()
-                })
-                .collect();
-            if entries.len() >= 2 {
-                return entries;
-            }
+        // 2. Try configured keys
+        let entries: Vec<CustodianEntry> = self
+            .settings
+            .prize_pool_keys
+            .iter()
+            .enumerate()
+            .filter_map(|(i, s)| CustodianEntry::new(&format!("Custodian {}", i + 1), s).ok())
+            .collect();
+        if entries.len() >= 2 {
+            return entries;
         }
 
         // 3. Fake placeholders — valid secp256k1 generator multiples so address still generates
@@ -291,9 +485,7 @@ impl App {
             self.state.last_error = Some("Pick Wizard needs bracket data".to_string());
             return;
         };
-        self.state
-            .pick_wizard
-            .load_from_tournament_2025_template(&tournament);
+        self.state.pick_wizard.load_from_tournament(&tournament);
         if let Ok(saved) = self.load_pick_wizard_file() {
             self.state.pick_wizard.apply_saved_selections(saved.selections);
         }
@@ -314,8 +506,19 @@ impl App {
     }
 
     pub fn save_pick_wizard_file(&mut self) -> Result<(), String> {
-        let picks = self.state.pick_wizard.to_export(self.state.chat.username.clone());
-        let path = pick_wizard_path(picks.year);
+        let mut picks = self
+            .state
+            .pick_wizard
+            .to_export(self.state.chat.username.clone());
+        let path = pick_wizard_path(self.state.pick_wizard.gender, picks.year);
+        // Unchanged picks keep their original seal and submission time,
+        // unless they were signed by a different key than the one we use now.
+        if let Ok(saved) = self.load_pick_wizard_file()
+            && seal::is_sealed_copy(&saved, &picks, self.settings.picks_key.as_ref())
+        {
+            return Ok(());
+        }
+        seal::seal(&mut picks, Utc::now(), self.settings.picks_key.as_ref());
         if let Some(parent) = path.parent() {
             std::fs::create_dir_all(parent).map_err(|e| format!("create dir failed: {e}"))?;
         }
@@ -325,6 +528,19 @@ impl App {
         Ok(())
     }
 
+    /// Save picks locally, then build the request that submits them to the
+    /// configured pool server.
+    pub fn submit_picks_request(&mut self) -> Result<NetworkRequest, String> {
+        let pool_url = self
+            .settings
+            .pool_url
+            .clone()
+            .ok_or("set MMTUI_POOL_URL to submit picks")?;
+        self.save_pick_wizard_file()?;
+        let picks = self.load_pick_wizard_file()?;
+        Ok(NetworkRequest::SubmitPicks { pool_url, picks })
+    }
+
     pub fn reset_pick_wizard(&mut self) {
         // Clear in-memory selections and reset wizard progress
         self.state.pick_wizard.selections.clear();
@@ -332,7 +548,7 @@ impl App {
         self.state.pick_wizard.current_index = 0;
 
         // Attempt to remove saved picks file; ignore error if not present
-        let path = pick_wizard_path(self.state.pick_wizard.year);
+        let path = pick_wizard_path(self.state.pick_wizard.gender, self.state.pick_wizard.year);
         match std::fs::remove_file(&path) {
             Ok(_) => self
                 .state
@@ -343,34 +559,70 @@ impl App {
     }
 
     pub fn load_pick_wizard_file(&self) -> Result<BracketPicks, String> {
-        let path = pick_wizard_path(self.state.pick_wizard.year);
+        let path = pick_wizard_path(self.state.pick_wizard.gender, self.state.pick_wizard.year);
         let content =
             std::fs::read_to_string(&path).map_err(|e| format!("read picks failed: {e}"))?;
         serde_json::from_str::<BracketPicks>(&content)
             .map_err(|e| format!("parse picks failed: {e}"))
     }
 
-    pub fn load_compare_sources(&mut self) {
-        let Some(tournament) = self.state.bracket.tournament.as_ref() else {
+    /// Ask the network worker to fetch every Compare source.
+    pub fn compare_load_request(&mut self) -> Option<NetworkRequest> {
+        if self.state.bracket.tournament.is_none() {
             self.state.last_error = Some("Compare needs bracket data".to_string());
+            return None;
+        }
+        self.state.compare.loading = true;
+        Some(NetworkRequest::LoadCompareSources {
+            sources: self.compare_sources(),
+        })
+    }
+
+    pub fn on_compare_sources_loaded(
+        &mut self,
+        entries: Vec<(String, BracketPicks)>,
+        mut source_errors: Vec<String>,
+    ) {
+        self.state.compare.loading = false;
+        let Some(tournament) = self.state.bracket.tournament.as_ref() else {
             return;
         };
 
         let mut loaded: Vec<(String, BracketPicks)> = Vec::new();
-        let mut source_errors = Vec::new();
-
-        for source in self.compare_sources() {
-            match load_picks_source(&source) {
-                Ok(picks) => loaded.push((source, picks)),
-                Err(e) => source_errors.push(e),
+        for (source, picks) in entries {
+            match seal::check(picks, tournament, &self.settings.seal_policy) {
+                Ok((picks, note)) => {
+                    // Unsealed rows are flagged on the board instead.
+                    if let Some(note) = note.filter(|_| picks.content_hash.is_some()) {
+                        source_errors.push(format!("{source}: {note}"));
+                    }
+                    loaded.push((source, picks));
+                }
+                Err(e) => source_errors.push(format!("{source}: rejected: {e}")),
             }
         }
 
-        let eliminated = build_eliminated_set(tournament);
-        let mut rows = Vec::new();
-        for (source, picks) in loaded {
-            rows.push(score_picks(tournament, &source, &picks, &eliminated));
-        }
+        self.state.compare.entries = loaded;
+        self.state.compare.selected = 0;
+        self.rescore_compare();
+        self.state.compare.source_errors = source_errors;
+        self.state.compare.last_loaded_at = Some(Local::now().format("%H:%M").to_string());
+    }
+
+    /// Score the loaded entries against the current bracket. Simulation and
+    /// path results are dropped since they no longer match.
+    fn rescore_compare(&mut self) {
+        let Some(tournament) = self.state.bracket.tournament.as_ref() else {
+            return;
+        };
+        let rule = self.settings.scoring_rule;
+        let mut rows: Vec<CompareRow> = self
+            .state
+            .compare
+            .entries
+            .iter()
+            .map(|(source, picks)| score_picks(tournament, source, picks, rule))
+            .collect();
         rows.sort_by(|a, b| {
             b.points
                 .cmp(&a.points)
@@ -378,28 +630,105 @@ impl App {
                 .then_with(|| a.user_id.cmp(&b.user_id))
         });
 
-        self.state.compare.rows = rows;
-        self.state.compare.source_errors = source_errors;
-        self.state.compare.last_loaded_at = Some(Local::now().format("%H:%M").to_string());
-        self.state.compare.scroll_offset = 0;
+        self.state.compare.set_rows(rows);
+        self.state.compare.paths = None;
+        self.state.compare.generation += 1;
+    }
+
+    /// Request a pool simulation for the loaded Compare entries, if any. Only
+    /// one runs at a time; one that comes back stale is requested again.
+    pub fn pool_simulation_request(&mut self) -> Option<NetworkRequest> {
+        let tournament = self.state.bracket.tournament.clone()?;
+        if self.state.compare.entries.is_empty() || self.state.compare.simulating {
+            return None;
+        }
+        self.state.compare.simulating = true;
+        Some(NetworkRequest::SimulatePool {
+            generation: self.state.compare.generation,
+            tournament: Box::new(tournament),
+            entries: self.state.compare.entries.clone(),
+            rule: self.settings.scoring_rule,
+            ratings_path: self.settings.ratings_path.clone(),
+        })
+    }
+
+    /// Apply odds from the current generation; a stale run is replaced by a
+    /// fresh request.
+    pub fn on_pool_simulated(
+        &mut self,
+        generation: u64,
+        odds: Result<Vec<(String, EntrantOdds)>, String>,
+    ) -> Option<NetworkRequest> {
+        self.state.compare.simulating = false;
+        if generation != self.state.compare.generation {
+            return self.pool_simulation_request();
+        }
+        let odds = match odds {
+            Ok(odds) => odds,
+            Err(message) => {
+                self.state.last_error = Some(message);
+                return None;
+            }
+        };
+        for row in &mut self.state.compare.rows {
+            if let Some((_, o)) = odds.iter().find(|(source, _)| *source == row.source) {
+                row.win_probability = Some(o.win_probability);
+                row.expected_points = Some(o.expected_points);
+            }
+        }
+        None
     }
 
-    pub fn compare_scroll_down(&mut self) {
-        let max = self.state.compare.rows.len().saturating_sub(1) as u16;
-        self.state.compare.scroll_offset = (self.state.compare.scroll_offset + 1).min(max);
+    /// Open or close the path drill-down; opening requests the analysis when
+    /// it has not been run for the current entries.
+    pub fn toggle_compare_detail(&mut self) -> Option<NetworkRequest> {
+        self.state.compare.detail_open = !self.state.compare.detail_open;
+        self.paths_request()
     }
 
-    pub fn compare_scroll_up(&mut self) {
-        self.state.compare.scroll_offset = self.state.compare.scroll_offset.saturating_sub(1);
+    /// Path analysis for the open drill-down, unless it is current or running.
+    pub fn paths_request(&mut self) -> Option<NetworkRequest> {
+        let compare = &mut self.state.compare;
+        if !compare.detail_open || compare.paths.is_some() || compare.analyzing {
+            return None;
+        }
+        let tournament = self.state.bracket.tournament.clone()?;
+        compare.analyzing = true;
+        Some(NetworkRequest::AnalyzePaths {
+            generation: compare.generation,
+            tournament: Box::new(tournament),
+            entries: compare.entries.clone(),
+            rule: self.settings.scoring_rule,
+        })
+    }
+
+    /// Show paths from the current generation; a stale analysis is replaced
+    /// by a fresh request.
+    pub fn on_paths_analyzed(
+        &mut self,
+        generation: u64,
+        paths: Result<Vec<(String, EntrantPath)>, String>,
+    ) -> Option<NetworkRequest> {
+        self.state.compare.analyzing = false;
+        if generation != self.state.compare.generation {
+            return self.paths_request();
+        }
+        self.state.compare.paths = Some(paths);
+        None
     }
 
     fn compare_sources(&self) -> Vec<String> {
+        let (gender, year) = self
+            .state
+            .bracket
+            .tournament
+            .as_ref()
+            .map(|t| (t.gender, t.year))
+            .unwrap_or((self.state.pick_wizard.gender, self.state.pick_wizard.year));
         let mut out = Vec::new();
-        out.push(pick_wizard_path(2025).display().to_string());
+        out.push(pick_wizard_path(gender, year).display().to_string());
 
-        if let Some(compare_dir) = pick_wizard_path(2025).parent().map(|p| p.join("compare"))
-            && let Ok(entries) = std::fs::read_dir(compare_dir)
-        {
+        if let Ok(entries) = std::fs::read_dir(compare_dir(gender)) {
             for entry in entries.flatten() {
                 let p = entry.path();
                 if p.extension().and_then(|e| e.to_str()) == Some("json") {
@@ -408,169 +737,57 @@ impl App {
             }
         }
 
-        if let Ok(extra) = std::env::var("MMTUI_COMPARE_SOURCES") {
-            out.extend(
-                extra
-                    .split(',')
-                    .map(str::trim)
-                    .filter(|s| !s.is_empty())
-                    .map(ToString::to_string),
-            );
+        if let Some(pool_url) = &self.settings.pool_url {
+            out.push(format!("{pool_url}/picks"));
         }
 
+        out.extend(self.settings.compare_sources.iter().cloned());
+
         out.sort();
         out.dedup();
         out
     }
 }
 
-fn pick_wizard_path(year: u16) -> PathBuf {
-    if let Ok(config_dir) = std::env::var("XDG_CONFIG_HOME")
-        && !config_dir.trim().is_empty()
-    {
-        return PathBuf::from(config_dir).join("mmtui").join(format!("picks_{year}.json"));
-    }
-    if let Ok(home) = std::env::var("HOME")
-        && !home.trim().is_empty()
-    {
-        return PathBuf::from(home)
-            .join(".config")
-            .join("mmtui")
-            .join(format!("picks_{year}.json"));
-    }
-    PathBuf::from(format!("picks_{year}.json"))
-}
-
-fn round_weight(round: RoundKind) -> u32 {
-    match round {
-        RoundKind::FirstFour => 1,
-        RoundKind::First => 1,
-        RoundKind::Second => 2,
-        RoundKind::Sweet16 => 4,
-        RoundKind::Elite8 => 8,
-        RoundKind::FinalFour => 16,
-        RoundKind::Championship => 32,
-    }
-}
-
-/// Build the set of eliminated team IDs from all Final games in the tournament.
-/// A team is eliminated if it participated in a Final game but was not the winner.
-fn build_eliminated_set(tournament: &Tournament) -> HashSet<String> {
-    let mut eliminated = HashSet::new();
-    for region in &tournament.regions {
-        for round in &region.rounds {
-            for game in &round.games {
-                if game.status != GameStatus::Final {
-                    continue;
-                }
-                let Some(winner_id) = game.winner_id.as_deref() else {
-                    continue;
-                };
-                for slot in [&game.top, &game.bottom] {
-                    if let Some(team) = &slot.team {
-                        if team.id != winner_id {
-                            eliminated.insert(team.id.clone());
-                        }
-                    }
-                }
-            }
-        }
-    }
-    eliminated
-}
-
-/// Returns true if the pick is still viable (picked team has not been eliminated).
-/// Handles both direct team ID picks and placeholder picks ("top:{game_id}", "bottom:{game_id}").
-/// Treats TBD slots (team = None) as alive — a team that hasn't entered the bracket can't be out.
-fn is_viable(selection: &str, game: &Game, eliminated: &HashSet<String>) -> bool {
-    let team_id: Option<&str> = if selection == format!("top:{}", game.id) {
-        game.top.team.as_ref().map(|t| t.id.as_str())
-    } else if selection == format!("bottom:{}", game.id) {
-        game.bottom.team.as_ref().map(|t| t.id.as_str())
-    } else {
-        Some(selection)
-    };
-    match team_id {
-        Some(id) => !eliminated.contains(id),
-        None => true, // TBD slot — not yet in the bracket, cannot be eliminated
+/// `picks_<year>.json` for the men's tournament, `picks_women_<year>.json`
+/// for the women's.
+pub fn pick_wizard_path(gender: Gender, year: u16) -> PathBuf {
+    match gender {
+        Gender::Men => config_file(&format!("picks_{year}.json")),
+        Gender::Women => config_file(&format!("picks_women_{year}.json")),
     }
 }
 
-fn load_picks_source(source: &str) -> Result<BracketPicks, String> {
-    if source.starts_with("http://") || source.starts_with("https://") {
-        let body = reqwest::blocking::get(source)
-            .map_err(|e| format!("{source}: fetch failed: {e}"))?
-            .text()
-            .map_err(|e| format!("{source}: read body failed: {e}"))?;
-        serde_json::from_str(&body).map_err(|e| format!("{source}: invalid picks json: {e}"))
-    } else {
-        let content = std::fs::read_to_string(source)
-            .map_err(|e| format!("{source}: read failed: {e}"))?;
-        serde_json::from_str(&content).map_err(|e| format!("{source}: invalid picks json: {e}"))
+/// Other entrants' picks files: `compare/`, or `compare_women/`.
+fn compare_dir(gender: Gender) -> PathBuf {
+    match gender {
+        Gender::Men => config_file("compare"),
+        Gender::Women => config_file("compare_women"),
     }
 }
 
-fn score_picks(tournament: &Tournament, source: &str, picks: &BracketPicks, eliminated: &HashSet<String>) -> CompareRow {
-    let mut points = 0u32;
-    let mut max_points = 0u32;
-    let mut correct = 0u32;
-    let mut total = 0u32;
-
-    for region in &tournament.regions {
-        for round in &region.rounds {
-            for game in &round.games {
-                let Some(selection) = picks.selections.get(&game.id) else {
-                    continue;
-                };
-                total += 1;
-                let weight = round_weight(round.kind);
-                let winner_side = if game.winner_id.as_ref() == game.top.team.as_ref().map(|t| &t.id) {
-                    Some("top")
-                } else if game.winner_id.as_ref() == game.bottom.team.as_ref().map(|t| &t.id) {
-                    Some("bottom")
-                } else {
-                    None
-                };
-
-                let picked_correct = match game.status {
-                    GameStatus::Final => {
-                        let is_team_match = game.winner_id.as_deref() == Some(selection.as_str());
-                        let is_side_match = selection == &format!("top:{}", game.id)
-                            && winner_side == Some("top")
-                            || selection == &format!("bottom:{}", game.id)
-                                && winner_side == Some("bottom");
-                        is_team_match || is_side_match
-                    }
-                    _ => false,
-                };
-
-                if picked_correct {
-                    correct += 1;
-                    points += weight;
-                }
-
-                match game.status {
-                    GameStatus::Final => {
-                        if picked_correct {
-                            max_points += weight;
-                        }
-                    }
-                    _ => {
-                        if is_viable(selection, game, eliminated) {
-                            max_points += weight;
-                        }
-                    }
-                }
-            }
-        }
-    }
-
+fn score_picks(
+    tournament: &Tournament,
+    source: &str,
+    picks: &BracketPicks,
+    rule: ScoringRule,
+) -> CompareRow {
+    let card = score_bracket(tournament, picks, rule);
     CompareRow {
         user_id: picks.user_id.clone(),
         source: source.to_string(),
-        points,
-        max_points,
-        correct,
-        total,
+        points: card.points,
+        max_points: card.max_points,
+        correct: card.correct,
+        total: card.total,
+        round_points: card.round_points,
+        win_probability: None,
+        expected_points: None,
+        unsealed: picks.content_hash.is_none(),
     }
 }
+
+/// Winner of every game, in bracket order; changes whenever a result does.
+fn winners(tournament: &Tournament) -> Vec<Option<String>> {
+    tournament.games().map(|g| g.winner_id.clone()).collect()
+}
@@ -1,23 +1,97 @@
+//! WebSocket chat relay. Routes messages by room, replays each room's recent
+//! history to new arrivals and keeps every room told who is present.
+//!
+//! - `MMTUI_CHAT_BIND` listen address (default `0.0.0.0:8787`)
+//! - `MMTUI_CHAT_HISTORY` messages kept per room (default 200)
+//! - `MMTUI_CHAT_HISTORY_FILE` optional JSON-lines file that history is
+//!   loaded from on start and appended to by a writer thread, which compacts
+//!   it back to the kept history once it holds twice that; name→key pins and
+//!   bans are kept beside it in `<file>.keys.json` and `<file>.bans.json`,
+//!   saved by the same thread
+//! - `MMTUI_CHAT_PASSWORD` optional shared password required to join
+//! - `MMTUI_CHAT_ADMIN_TOKEN` enables `/mute`, `/unmute`, `/kick`, `/ban` and
+//!   `/slow` for clients that present it
+//! - `MMTUI_CHAT_BURST` / `MMTUI_CHAT_PER_MINUTE` per-connection token bucket
+//!   (default 5 messages, refilled at 20 a minute); joins and admin commands
+//!   get a bucket of their own with the same limits
+//! - `MMTUI_CHAT_MAX_BODY` longest message body in characters (default 500)
+//! - `MMTUI_CHAT_MAX_FRAME` largest WebSocket frame in bytes (default 16 KiB)
+//! - `MMTUI_CHAT_SLOW_MODE` seconds each user must wait between messages
+//!   (default 0; admins can change it per room)
+//!
+//! Clients that never join a room (older TUI builds) receive the default
+//! room's messages, which is where those builds chat. That is off when a
+//! password is set.
+//!
+//! Each connection has a bounded outbound queue. A client that falls that far
+//! behind is disconnected; it can rejoin with `since` to catch up.
+//!
+//! Delivery: each room numbers its messages. Senders get an ack with the
+//! number, re-sent messages (same id, author and signature) are acked again
+//! rather than repeated, and a client rejoining with `since` gets only what
+//! it missed.
+//!
+//! Identity: a message must carry the name its sender joined with. Signed
+//! messages must verify, and the first key seen for a name owns it from then
+//! on; unsigned or differently-signed messages under that name are dropped.
+//! Pins outlive restarts only when there is a history file. The relay holds
+//! at most 10,000 pins; once full, signed messages under new names are
+//! refused until an operator prunes the keys file.
+//!
+//! Room and user names are capped at 32 characters. A connection that sends
+//! three wrong admin tokens is dropped.
+//!
+//! Moderation targets identities, not addresses. A ban covers the name and
+//! the key pinned to it, relay-wide. A mute covers the connection and the
+//! pinned key in one room, so `/nick` does not shed it; mutes are not saved,
+//! and an unsigned user can still leave one behind by reconnecting under
+//! another name.
+use bitcoin::hashes::{Hash, sha256};
 use futures_util::{SinkExt, StreamExt};
+use mmtui::chat_protocol::{Authenticity, ChatWireMessage, ClientFrame, DEFAULT_ROOM, RelayFrame};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::env;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
 use tokio::net::{TcpListener, TcpStream};
-use tokio::sync::broadcast;
-use tokio_tungstenite::{accept_async, tungstenite::Message};
+use tokio::sync::{Notify, mpsc};
+use tokio_tungstenite::tungstenite::protocol::WebSocketConfig;
+use tokio_tungstenite::{accept_async_with_config, tungstenite::Message};
+
+const DEFAULT_MUTE: Duration = Duration::from_secs(10 * 60);
+/// Longest room or user name, in characters.
+const MAX_NAME: usize = 32;
+/// Wrong admin tokens a connection may send before it is dropped.
+const MAX_ADMIN_FAILURES: u32 = 3;
+/// Name→key pins kept, so signing under fresh names can't grow them forever.
+const MAX_PINS: usize = 10_000;
+/// Frames queued for one connection before it counts as too slow.
+const CLIENT_QUEUE: usize = 256;
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     let addr = env::var("MMTUI_CHAT_BIND").unwrap_or_else(|_| "0.0.0.0:8787".to_string());
+    let config = Config::from_env();
+    let ws_config = WebSocketConfig::default()
+        .max_frame_size(Some(config.max_frame))
+        .max_message_size(Some(config.max_frame));
+
+    let relay = Relay::open(config)?;
+    let relay = Arc::new(Mutex::new(relay));
     let listener = TcpListener::bind(&addr).await?;
-    let (tx, _rx) = broadcast::channel::<String>(512);
 
     eprintln!("chat relay listening on {addr}");
 
     loop {
         let (stream, peer) = listener.accept().await?;
-        let tx = tx.clone();
-        let rx = tx.subscribe();
+        let relay = relay.clone();
         tokio::spawn(async move {
-            if let Err(e) = handle_client(stream, tx, rx).await {
+            if let Err(e) = handle_client(stream, relay, ws_config).await {
                 eprintln!("client {peer} disconnected: {e}");
             }
         });
@@ -26,38 +100,1529 @@ async fn main() -> anyhow::Result<()> {
 
 async fn handle_client(
     stream: TcpStream,
-    tx: broadcast::Sender<String>,
-    mut rx: broadcast::Receiver<String>,
+    relay: Arc<Mutex<Relay>>,
+    ws_config: WebSocketConfig,
 ) -> anyhow::Result<()> {
-    let ws = accept_async(stream).await?;
+    let ws = accept_async_with_config(stream, Some(ws_config)).await?;
     let (mut write, mut read) = ws.split();
+    let (tx, mut rx) = mpsc::channel::<String>(CLIENT_QUEUE);
+    let (id, overflow) = lock(&relay).connect(tx);
 
-    loop {
-        tokio::select! {
-            inbound = read.next() => {
-                match inbound {
-                    Some(Ok(Message::Text(text))) => {
-                        let _ = tx.send(text.to_string());
+    let result = async {
+        loop {
+            tokio::select! {
+                inbound = read.next() => {
+                    match inbound {
+                        Some(Ok(Message::Text(text))) => match ClientFrame::parse(&text) {
+                            Ok(frame) => lock(&relay).handle(id, frame, Instant::now()),
+                            Err(e) => eprintln!("client {id}: ignoring frame: {e}"),
+                        },
+                        Some(Ok(Message::Close(_))) | None => break,
+                        Some(Ok(_)) => {}
+                        Some(Err(e)) => return Err(e.into()),
                     }
-                    Some(Ok(Message::Binary(_))) => {}
-                    Some(Ok(Message::Ping(_))) => {}
-                    Some(Ok(Message::Pong(_))) => {}
-                    Some(Ok(Message::Frame(_))) => {}
-                    Some(Ok(Message::Close(_))) | None => break,
-                    Some(Err(e)) => return Err(e.into()),
-                }
-            }
-            outbound = rx.recv() => {
-                match outbound {
-                    Ok(text) => {
-                        write.send(Message::Text(text.into())).await?;
+                }
+                outbound = rx.recv() => {
+                    // The relay drops our sender when we are kicked.
+                    let Some(text) = outbound else { break };
+                    write.send(Message::Text(text.into())).await?;
+                }
+                _ = overflow.notified() => {
+                    anyhow::bail!("more than {CLIENT_QUEUE} frames behind");
+                }
+            }
+        }
+        Ok(())
+    }
+    .await;
+
+    lock(&relay).disconnect(id);
+    result
+}
+
+/// The relay state is only touched briefly between awaits.
+fn lock(relay: &Mutex<Relay>) -> std::sync::MutexGuard<'_, Relay> {
+    relay.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+// ---------------------------------------------------------------------------
+// Configuration
+// ---------------------------------------------------------------------------
+
+struct Config {
+    history_limit: usize,
+    history_file: Option<PathBuf>,
+    password: Option<String>,
+    admin_token: Option<String>,
+    burst: f64,
+    per_minute: f64,
+    max_body: usize,
+    max_frame: usize,
+    slow_mode: Duration,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            history_limit: 200,
+            history_file: None,
+            password: None,
+            admin_token: None,
+            burst: 5.0,
+            per_minute: 20.0,
+            max_body: 500,
+            max_frame: 16 * 1024,
+            slow_mode: Duration::ZERO,
+        }
+    }
+}
+
+impl Config {
+    fn from_env() -> Self {
+        let defaults = Self::default();
+        Self {
+            history_limit: env_parse("MMTUI_CHAT_HISTORY").unwrap_or(defaults.history_limit),
+            history_file: env_string("MMTUI_CHAT_HISTORY_FILE").map(PathBuf::from),
+            password: env_string("MMTUI_CHAT_PASSWORD"),
+            admin_token: env_string("MMTUI_CHAT_ADMIN_TOKEN"),
+            burst: env_parse("MMTUI_CHAT_BURST").unwrap_or(defaults.burst),
+            per_minute: env_parse("MMTUI_CHAT_PER_MINUTE").unwrap_or(defaults.per_minute),
+            max_body: env_parse("MMTUI_CHAT_MAX_BODY").unwrap_or(defaults.max_body),
+            max_frame: env_parse("MMTUI_CHAT_MAX_FRAME").unwrap_or(defaults.max_frame),
+            slow_mode: env_parse("MMTUI_CHAT_SLOW_MODE")
+                .map(Duration::from_secs)
+                .unwrap_or(defaults.slow_mode),
+        }
+    }
+}
+
+fn env_string(name: &str) -> Option<String> {
+    env::var(name).ok().filter(|v| !v.trim().is_empty())
+}
+
+fn env_parse<T: FromStr>(name: &str) -> Option<T> {
+    env_string(name)?.trim().parse().ok()
+}
+
+// ---------------------------------------------------------------------------
+// Relay state
+// ---------------------------------------------------------------------------
+
+struct Client {
+    tx: mpsc::Sender<String>,
+    /// Woken when `tx` is full, which ends the connection.
+    overflow: Arc<Notify>,
+    /// Room and display name once joined.
+    joined: Option<(String, String)>,
+    /// Joined with a `join` frame, so it understands acks. Older clients
+    /// only ever send bare messages.
+    acks: bool,
+    bucket: TokenBucket,
+    /// Joins and admin commands, limited apart from messages.
+    controls: TokenBucket,
+    admin_failures: u32,
+    last_post: Option<Instant>,
+}
+
+impl Client {
+    /// Queue a frame without waiting; a full queue disconnects the client
+    /// instead of buffering without bound.
+    fn send(&self, frame: String) {
+        if let Err(mpsc::error::TrySendError::Full(_)) = self.tx.try_send(frame) {
+            self.overflow.notify_one();
+        }
+    }
+}
+
+/// Flood control: `burst` messages at once, refilled at a steady rate.
+struct TokenBucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+impl TokenBucket {
+    fn take(&mut self, now: Instant, config: &Config) -> bool {
+        let refill = now.duration_since(self.updated).as_secs_f64() * config.per_minute / 60.0;
+        self.tokens = (self.tokens + refill).min(config.burst);
+        self.updated = now;
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// Who a mute applies to.
+#[derive(Clone, PartialEq, Eq, Hash)]
+enum Speaker {
+    Connection(u64),
+    Key(String),
+}
+
+/// Relay-wide bans, saved beside the history file.
+#[derive(Default, Serialize, Deserialize)]
+struct Bans {
+    names: HashSet<String>,
+    /// Keys pinned to a banned name when the ban was made.
+    keys: HashSet<String>,
+}
+
+struct Relay {
+    config: Config,
+    /// Author name → the public key that first signed as them.
+    keys: HashMap<String, String>,
+    history: HashMap<String, VecDeque<ChatWireMessage>>,
+    /// Last sequence number handed out per room.
+    seqs: HashMap<String, u64>,
+    clients: HashMap<u64, Client>,
+    next_id: u64,
+    /// (room, speaker) → muted until.
+    muted: HashMap<(String, Speaker), Instant>,
+    bans: Bans,
+    /// Per-room slow mode set by an admin; `config.slow_mode` otherwise.
+    slow_mode: HashMap<String, Duration>,
+    history_writer: Option<HistoryWriter>,
+}
+
+impl Relay {
+    /// Load saved pins, bans and history, then rewrite the history file with
+    /// only what was kept.
+    fn open(config: Config) -> anyhow::Result<Self> {
+        let mut relay = Self {
+            config,
+            keys: HashMap::new(),
+            history: HashMap::new(),
+            seqs: HashMap::new(),
+            clients: HashMap::new(),
+            next_id: 0,
+            muted: HashMap::new(),
+            bans: Bans::default(),
+            slow_mode: HashMap::new(),
+            history_writer: None,
+        };
+        if let Some(path) = relay.state_file("keys")
+            && let Ok(content) = std::fs::read_to_string(&path)
+        {
+            relay.keys = serde_json::from_str(&content)
+                .map_err(|e| anyhow::anyhow!("{}: {e}", path.display()))?;
+        }
+        if let Some(path) = relay.state_file("bans")
+            && let Ok(content) = std::fs::read_to_string(&path)
+        {
+            relay.bans = serde_json::from_str(&content)
+                .map_err(|e| anyhow::anyhow!("{}: {e}", path.display()))?;
+        }
+        if let Some(path) = relay.config.history_file.clone() {
+            let content = std::fs::read_to_string(&path).unwrap_or_default();
+            for line in content.lines().filter(|l| !l.trim().is_empty()) {
+                match serde_json::from_str::<ChatWireMessage>(line) {
+                    Ok(mut msg) => match relay.check_identity(&msg) {
+                        Ok(()) => {
+                            relay.sequence(&mut msg);
+                            relay.remember(msg);
+                        }
+                        Err(e) => eprintln!("skipping history line: {e}"),
+                    },
+                    Err(e) => eprintln!("skipping history line: {e}"),
+                }
+            }
+            relay.history_writer = Some(HistoryWriter::start(
+                path,
+                relay.config.history_limit,
+                &relay.history,
+            )?);
+        }
+        Ok(relay)
+    }
+
+    /// Register a connection, returning its id and the signal that it fell
+    /// too far behind.
+    fn connect(&mut self, tx: mpsc::Sender<String>) -> (u64, Arc<Notify>) {
+        self.next_id += 1;
+        let overflow = Arc::new(Notify::new());
+        let client = Client {
+            tx,
+            overflow: overflow.clone(),
+            joined: None,
+            acks: false,
+            bucket: TokenBucket {
+                tokens: self.config.burst,
+                updated: Instant::now(),
+            },
+            controls: TokenBucket {
+                tokens: self.config.burst,
+                updated: Instant::now(),
+            },
+            admin_failures: 0,
+            last_post: None,
+        };
+        self.clients.insert(self.next_id, client);
+        (self.next_id, overflow)
+    }
+
+    fn disconnect(&mut self, id: u64) {
+        self.muted
+            .retain(|(_, speaker), _| *speaker != Speaker::Connection(id));
+        if let Some(Client {
+            joined: Some((room, _)),
+            ..
+        }) = self.clients.remove(&id)
+        {
+            self.announce_presence(&room);
+        }
+    }
+
+    fn handle(&mut self, id: u64, frame: ClientFrame, now: Instant) {
+        let control = matches!(frame, ClientFrame::Join { .. } | ClientFrame::Admin { .. });
+        if control
+            && let Some(client) = self.clients.get_mut(&id)
+            && !client.controls.take(now, &self.config)
+        {
+            self.reject(id, None, "slow down: too many commands".into());
+            return;
+        }
+        match frame {
+            ClientFrame::Join {
+                room,
+                user,
+                password,
+                since,
+            } => {
+                if let Err(reason) = check_names(&room, &user) {
+                    self.reject(id, None, reason);
+                    return;
+                }
+                if let Some(expected) = &self.config.password
+                    && !password.is_some_and(|p| token_matches(&p, expected))
+                {
+                    self.reject(id, None, "wrong room password".into());
+                    return;
+                }
+                if self.is_banned(&user, None) {
+                    self.reject(id, None, format!("{user} is banned from this relay"));
+                    return;
+                }
+                if let Some(client) = self.clients.get_mut(&id) {
+                    client.acks = true;
+                }
+                self.join(id, room, user, since);
+            }
+            ClientFrame::Message(msg) => {
+                let msg_id = msg.id.clone();
+                match self.post(id, msg, now) {
+                    Ok(seq) => self.ack(id, msg_id, seq),
+                    Err(reason) => self.reject(id, Some(msg_id), reason),
+                }
+            }
+            ClientFrame::Admin { token, command } => {
+                let valid = match (&self.config.admin_token, &token) {
+                    (Some(expected), Some(token)) => token_matches(token, expected),
+                    _ => false,
+                };
+                if !valid {
+                    self.reject(id, None, "admin commands need a valid admin token".into());
+                    let failures = self.clients.get_mut(&id).map(|client| {
+                        client.admin_failures += 1;
+                        client.admin_failures
+                    });
+                    if failures.is_some_and(|n| n >= MAX_ADMIN_FAILURES) {
+                        // Dropping the sender ends the connection.
+                        self.disconnect(id);
                     }
-                    Err(broadcast::error::RecvError::Lagged(_)) => {}
-                    Err(broadcast::error::RecvError::Closed) => break,
+                    return;
+                }
+                if let Err(reason) = self.admin(id, &command, now) {
+                    self.reject(id, None, reason);
+                }
+            }
+        }
+    }
+
+    /// Check and relay a message, returning its sequence number.
+    fn post(&mut self, id: u64, mut msg: ChatWireMessage, now: Instant) -> Result<u64, String> {
+        if self.is_banned(&msg.author, msg.pubkey.as_ref()) {
+            return Err(format!("{} is banned from this relay", msg.author));
+        }
+        match self.clients.get(&id).and_then(|c| c.joined.clone()) {
+            // Joined clients can only post to their own room, as themselves.
+            Some((room, user)) => {
+                if msg.author != user {
+                    return Err(format!("you joined as {user}"));
+                }
+                msg.room = room;
+            }
+            None if self.config.password.is_some() => {
+                return Err("join with the room password first".into());
+            }
+            // A bare message is an older client announcing its room.
+            None => {
+                check_names(&msg.room, &msg.author)?;
+                self.join(id, msg.room.clone(), msg.author.clone(), None);
+            }
+        }
+        self.check_identity(&msg)?;
+        // A resend after a reconnect: the first copy already went out, so it
+        // is acked again without counting against flood or slow mode. Ids
+        // can be guessed, so only the same author and signature count.
+        if let Some(seq) = self
+            .history
+            .get(&msg.room)
+            .and_then(|h| {
+                h.iter().find(|m| {
+                    m.id == msg.id && m.author == msg.author && m.signature == msg.signature
+                })
+            })
+            .and_then(|m| m.seq)
+        {
+            return Ok(seq);
+        }
+        self.moderate(id, &msg, now)?;
+        if let Some(client) = self.clients.get_mut(&id) {
+            client.last_post = Some(now);
+        }
+        Ok(self.publish(msg))
+    }
+
+    /// Enter `room`, replaying its history (only what came after `since`,
+    /// when given) and telling the room.
+    fn join(&mut self, id: u64, room: String, user: String, since: Option<u64>) {
+        let Some(client) = self.clients.get_mut(&id) else {
+            return;
+        };
+        let previous = client.joined.replace((room.clone(), user));
+        let history = RelayFrame::History {
+            room: room.clone(),
+            messages: self
+                .history
+                .get(&room)
+                .map(|h| {
+                    h.iter()
+                        .filter(|m| since.is_none_or(|since| m.seq.is_some_and(|seq| seq > since)))
+                        .cloned()
+                        .collect()
+                })
+                .unwrap_or_default(),
+        };
+        client.send(history.to_json());
+
+        if let Some((old_room, _)) = previous
+            && old_room != room
+        {
+            self.announce_presence(&old_room);
+        }
+        self.announce_presence(&room);
+    }
+
+    /// Size cap, mutes, slow mode and the flood bucket, in that order.
+    fn moderate(&mut self, id: u64, msg: &ChatWireMessage, now: Instant) -> Result<(), String> {
+        if msg.body.chars().count() > self.config.max_body {
+            return Err(format!(
+                "message longer than {} characters",
+                self.config.max_body
+            ));
+        }
+        let speakers = [
+            Some(Speaker::Connection(id)),
+            msg.pubkey.clone().map(Speaker::Key),
+        ];
+        for speaker in speakers.into_iter().flatten() {
+            let muted_key = (msg.room.clone(), speaker);
+            match self.muted.get(&muted_key) {
+                Some(until) if *until > now => {
+                    let mins = until.duration_since(now).as_secs().div_ceil(60);
+                    return Err(format!("you are muted for {mins} more min"));
+                }
+                Some(_) => {
+                    self.muted.remove(&muted_key);
+                }
+                None => {}
+            }
+        }
+
+        let slow = self
+            .slow_mode
+            .get(&msg.room)
+            .copied()
+            .unwrap_or(self.config.slow_mode);
+        let Some(client) = self.clients.get_mut(&id) else {
+            return Err("not connected".into());
+        };
+        if let Some(last) = client.last_post
+            && now.duration_since(last) < slow
+        {
+            let wait = (slow - now.duration_since(last)).as_secs().max(1);
+            return Err(format!("slow mode: wait {wait}s"));
+        }
+        if !client.bucket.take(now, &self.config) {
+            return Err("slow down: too many messages".into());
+        }
+        Ok(())
+    }
+
+    /// Run `/mute <user> [minutes]`, `/unmute <user>`, `/kick <user>`,
+    /// `/ban <user>` or `/slow <seconds>` in the admin's room.
+    fn admin(&mut self, id: u64, command: &str, now: Instant) -> Result<(), String> {
+        let Some((room, admin)) = self.clients.get(&id).and_then(|c| c.joined.clone()) else {
+            return Err("join a room before moderating it".into());
+        };
+        let mut args = command.split_whitespace();
+        let verb = args.next().unwrap_or_default();
+        let target = args.next().map(str::to_string);
+        let extra = args.next();
+
+        let announcement = match (verb, target) {
+            ("/mute", Some(user)) => {
+                let duration = match extra {
+                    Some(mins) => Duration::from_secs(
+                        mins.parse::<u64>()
+                            .map_err(|_| format!("bad minutes: {mins}"))?
+                            * 60,
+                    ),
+                    None => DEFAULT_MUTE,
+                };
+                let speakers = self.speakers(&room, &user);
+                if speakers.is_empty() {
+                    return Err(format!("{user} is not in {room}"));
+                }
+                for speaker in speakers {
+                    self.muted.insert((room.clone(), speaker), now + duration);
+                }
+                format!(
+                    "{user} was muted for {} min by {admin}",
+                    duration.as_secs() / 60
+                )
+            }
+            ("/unmute", Some(user)) => {
+                for speaker in self.speakers(&room, &user) {
+                    self.muted.remove(&(room.clone(), speaker));
+                }
+                format!("{user} was unmuted by {admin}")
+            }
+            ("/kick", Some(user)) => {
+                if self.remove_user(Some(&room), &user, "you were kicked") == 0 {
+                    return Err(format!("{user} is not in {room}"));
+                }
+                format!("{user} was kicked by {admin}")
+            }
+            ("/ban", Some(user)) => {
+                if user == admin {
+                    return Err("you cannot ban yourself".into());
+                }
+                self.bans.names.insert(user.clone());
+                if let Some(key) = self.keys.get(&user) {
+                    self.bans.keys.insert(key.clone());
+                }
+                self.save("bans", &self.bans);
+                self.remove_user(None, &user, "you were banned");
+                format!("{user} was banned by {admin}")
+            }
+            ("/slow", Some(secs)) => {
+                let secs: u64 = secs.parse().map_err(|_| format!("bad seconds: {secs}"))?;
+                self.slow_mode
+                    .insert(room.clone(), Duration::from_secs(secs));
+                if secs == 0 {
+                    format!("slow mode turned off by {admin}")
+                } else {
+                    format!("slow mode set to {secs}s by {admin}")
                 }
             }
+            _ => return Err(format!("unknown admin command: {command}")),
+        };
+        self.announce(&room, announcement);
+        Ok(())
+    }
+
+    /// Connections joined as `user`, in `room` or anywhere.
+    fn connections(&self, room: Option<&str>, user: &str) -> Vec<u64> {
+        self.clients
+            .iter()
+            .filter(|(_, c)| {
+                c.joined
+                    .as_ref()
+                    .is_some_and(|(r, u)| room.is_none_or(|room| r == room) && u == user)
+            })
+            .map(|(id, _)| *id)
+            .collect()
+    }
+
+    /// Everything a mute on `user` in `room` should catch: their current
+    /// connections there and the key pinned to the name.
+    fn speakers(&self, room: &str, user: &str) -> Vec<Speaker> {
+        let connections = self.connections(Some(room), user).into_iter();
+        connections
+            .map(Speaker::Connection)
+            .chain(self.keys.get(user).cloned().map(Speaker::Key))
+            .collect()
+    }
+
+    /// Drop every connection joined as `user` (in `room`, when given),
+    /// returning how many there were.
+    fn remove_user(&mut self, room: Option<&str>, user: &str, reason: &str) -> usize {
+        let ids = self.connections(room, user);
+        let mut rooms = HashSet::new();
+        for id in &ids {
+            self.reject(*id, None, reason.to_string());
+            if let Some(Client {
+                joined: Some((room, _)),
+                ..
+            }) = self.clients.remove(id)
+            {
+                rooms.insert(room);
+            }
+        }
+        for room in rooms {
+            self.announce_presence(&room);
+        }
+        ids.len()
+    }
+
+    /// Banned by name, or by a key that is pinned to the name or that signed
+    /// the message.
+    fn is_banned(&self, user: &str, key: Option<&String>) -> bool {
+        self.bans.names.contains(user)
+            || self
+                .keys
+                .get(user)
+                .into_iter()
+                .chain(key)
+                .any(|key| self.bans.keys.contains(key))
+    }
+
+    /// Verify the signature and pin the author's first key.
+    fn check_identity(&mut self, msg: &ChatWireMessage) -> Result<(), String> {
+        let pinned = self.keys.get(&msg.author);
+        match (msg.authenticity(), &msg.pubkey, pinned) {
+            (Authenticity::Invalid, _, _) => Err("signature check failed".into()),
+            (Authenticity::Valid, Some(key), Some(pinned)) if key != pinned => {
+                Err(format!("{} is registered to another key", msg.author))
+            }
+            (Authenticity::Valid, Some(_), None) if self.keys.len() >= MAX_PINS => Err(format!(
+                "{} can't be registered: the relay's list of signed names is full",
+                msg.author
+            )),
+            (Authenticity::Valid, Some(key), None) => {
+                self.keys.insert(msg.author.clone(), key.clone());
+                self.save("keys", &self.keys);
+                Ok(())
+            }
+            (Authenticity::Unsigned, _, Some(_)) => Err(format!(
+                "{} signs their messages; unsigned message dropped",
+                msg.author
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Pins and bans live beside the history file, when there is one.
+    fn state_file(&self, kind: &str) -> Option<PathBuf> {
+        let history = self.config.history_file.as_ref()?;
+        Some(history.with_extension(format!("{kind}.json")))
+    }
+
+    /// Hand the pin or ban list to the writer thread.
+    fn save<T: Serialize>(&self, kind: &str, value: &T) {
+        if let (Some(path), Some(writer)) = (self.state_file(kind), &self.history_writer) {
+            writer.save(path, value);
+        }
+    }
+
+    fn reject(&self, id: u64, message_id: Option<String>, message: String) {
+        if let Some(client) = self.clients.get(&id) {
+            let frame = RelayFrame::Error {
+                message,
+                message_id,
+            };
+            client.send(frame.to_json());
+        }
+    }
+
+    fn ack(&self, id: u64, message_id: String, seq: u64) {
+        if let Some(client) = self.clients.get(&id).filter(|c| c.acks) {
+            let frame = RelayFrame::Ack {
+                id: message_id,
+                seq,
+            };
+            client.send(frame.to_json());
+        }
+    }
+
+    /// Tell everyone in `room` what the relay did.
+    fn announce(&self, room: &str, message: String) {
+        let frame = RelayFrame::System {
+            room: room.to_string(),
+            message,
+        }
+        .to_json();
+        for client in self.members(room) {
+            client.send(frame.clone());
+        }
+    }
+
+    fn publish(&mut self, mut msg: ChatWireMessage) -> u64 {
+        let seq = self.sequence(&mut msg);
+        if let Some(writer) = &self.history_writer {
+            writer.append(&msg);
+        }
+        let frame = RelayFrame::Message(msg.clone()).to_json();
+        for client in self.clients.values() {
+            let in_room = match &client.joined {
+                Some((room, _)) => *room == msg.room,
+                None => self.config.password.is_none() && msg.room == DEFAULT_ROOM,
+            };
+            if in_room {
+                client.send(frame.clone());
+            }
         }
+        self.remember(msg);
+        seq
     }
 
+    /// Number `msg` after the last message in its room. Numbers loaded from
+    /// the history file are kept as long as they keep increasing.
+    fn sequence(&mut self, msg: &mut ChatWireMessage) -> u64 {
+        let last = self.seqs.entry(msg.room.clone()).or_default();
+        let seq = msg.seq.filter(|seq| *seq > *last).unwrap_or(*last + 1);
+        *last = seq;
+        msg.seq = Some(seq);
+        seq
+    }
+
+    fn remember(&mut self, msg: ChatWireMessage) {
+        let history = self.history.entry(msg.room.clone()).or_default();
+        history.push_back(msg);
+        while history.len() > self.config.history_limit {
+            history.pop_front();
+        }
+    }
+
+    fn members(&self, room: &str) -> impl Iterator<Item = &Client> {
+        self.clients
+            .values()
+            .filter(move |c| c.joined.as_ref().is_some_and(|(r, _)| r == room))
+    }
+
+    fn announce_presence(&self, room: &str) {
+        let mut users: Vec<String> = self
+            .members(room)
+            .filter_map(|c| c.joined.as_ref().map(|(_, user)| user.clone()))
+            .collect();
+        users.sort();
+        users.dedup();
+        let frame = RelayFrame::Presence {
+            room: room.to_string(),
+            users,
+        }
+        .to_json();
+        for client in self.members(room) {
+            client.send(frame.clone());
+        }
+    }
+}
+
+/// Room and user names must fit [`MAX_NAME`].
+fn check_names(room: &str, user: &str) -> Result<(), String> {
+    if room.chars().count() > MAX_NAME || user.chars().count() > MAX_NAME {
+        return Err(format!(
+            "room and user names are limited to {MAX_NAME} characters"
+        ));
+    }
+    Ok(())
+}
+
+/// Compare tokens in constant time. Hashing first hides the length too.
+fn token_matches(given: &str, expected: &str) -> bool {
+    let given = sha256::Hash::hash(given.as_bytes()).to_byte_array();
+    let expected = sha256::Hash::hash(expected.as_bytes()).to_byte_array();
+    given
+        .iter()
+        .zip(expected)
+        .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+        == 0
+}
+
+/// Replace `path` via a temp file so a crash never leaves it half-written.
+fn replace_file(path: &Path, contents: &str) -> anyhow::Result<()> {
+    let tmp = path.with_extension("tmp");
+    std::fs::write(&tmp, contents)?;
+    std::fs::rename(&tmp, path)?;
     Ok(())
 }
+
+// ---------------------------------------------------------------------------
+// History file
+// ---------------------------------------------------------------------------
+
+/// Appends to the history file and saves pins and bans on its own thread, so
+/// a slow disk never holds up the relay lock. Dropping it finishes the queued
+/// writes.
+struct HistoryWriter {
+    tx: Option<std::sync::mpsc::Sender<Job>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+enum Job {
+    /// A message line for `room`'s history.
+    Line { room: String, line: String },
+    /// A whole state file.
+    File { path: PathBuf, contents: String },
+}
+
+impl HistoryWriter {
+    /// Rewrite `path` with `history`, then start appending to it.
+    fn start(
+        path: PathBuf,
+        limit: usize,
+        history: &HashMap<String, VecDeque<ChatWireMessage>>,
+    ) -> anyhow::Result<Self> {
+        let mut file = HistoryFile {
+            path,
+            limit,
+            rooms: HashMap::new(),
+            lines: 0,
+        };
+        for msg in history.values().flatten() {
+            file.remember(msg.room.clone(), serde_json::to_string(msg)?);
+        }
+        file.compact()?;
+
+        let (tx, rx) = std::sync::mpsc::channel::<Job>();
+        let thread = std::thread::spawn(move || {
+            for job in rx {
+                match job {
+                    Job::Line { room, line } => {
+                        if let Err(e) = file.append(room, line) {
+                            eprintln!("history write failed: {e}");
+                        }
+                    }
+                    Job::File { path, contents } => {
+                        if let Err(e) = replace_file(&path, &contents) {
+                            eprintln!("{} write failed: {e}", path.display());
+                        }
+                    }
+                }
+            }
+        });
+        Ok(Self {
+            tx: Some(tx),
+            thread: Some(thread),
+        })
+    }
+
+    fn append(&self, msg: &ChatWireMessage) {
+        let Some(tx) = &self.tx else { return };
+        match serde_json::to_string(msg) {
+            Ok(line) => {
+                let room = msg.room.clone();
+                let _ = tx.send(Job::Line { room, line });
+            }
+            Err(e) => eprintln!("history write failed: {e}"),
+        }
+    }
+
+    fn save<T: Serialize>(&self, path: PathBuf, value: &T) {
+        let Some(tx) = &self.tx else { return };
+        match serde_json::to_string_pretty(value) {
+            Ok(contents) => {
+                let _ = tx.send(Job::File { path, contents });
+            }
+            Err(e) => eprintln!("{} write failed: {e}", path.display()),
+        }
+    }
+}
+
+impl Drop for HistoryWriter {
+    fn drop(&mut self) {
+        self.tx.take();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// The writer thread's copy of the kept history, used to compact the file.
+struct HistoryFile {
+    path: PathBuf,
+    limit: usize,
+    rooms: HashMap<String, VecDeque<String>>,
+    /// Lines currently in the file.
+    lines: usize,
+}
+
+impl HistoryFile {
+    fn remember(&mut self, room: String, line: String) {
+        let kept = self.rooms.entry(room).or_default();
+        kept.push_back(line);
+        while kept.len() > self.limit {
+            kept.pop_front();
+        }
+    }
+
+    fn kept(&self) -> usize {
+        self.rooms.values().map(VecDeque::len).sum()
+    }
+
+    fn append(&mut self, room: String, line: String) -> anyhow::Result<()> {
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(file, "{line}")?;
+        self.lines += 1;
+        self.remember(room, line);
+        if self.lines > 2 * self.kept() {
+            self.compact()?;
+        }
+        Ok(())
+    }
+
+    /// Replace the file with only the kept lines.
+    fn compact(&mut self) -> anyhow::Result<()> {
+        let mut kept = String::new();
+        for line in self.rooms.values().flatten() {
+            kept.push_str(line);
+            kept.push('\n');
+        }
+        replace_file(&self.path, &kept)?;
+        self.lines = self.kept();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bitcoin::secp256k1::SecretKey;
+
+    fn msg(id: &str, room: &str, author: &str) -> ChatWireMessage {
+        ChatWireMessage {
+            id: id.into(),
+            room: room.into(),
+            author: author.into(),
+            body: format!("hi from {author}"),
+            timestamp: "19:05".into(),
+            ..Default::default()
+        }
+    }
+
+    fn join(room: &str, user: &str) -> ClientFrame {
+        ClientFrame::Join {
+            room: room.into(),
+            user: user.into(),
+            password: None,
+            since: None,
+        }
+    }
+
+    fn drain(rx: &mut mpsc::Receiver<String>) -> Vec<RelayFrame> {
+        std::iter::from_fn(|| rx.try_recv().ok())
+            .map(|text| RelayFrame::parse(&text).unwrap())
+            .collect()
+    }
+
+    fn client(relay: &mut Relay) -> (u64, mpsc::Receiver<String>) {
+        let (tx, rx) = mpsc::channel(CLIENT_QUEUE);
+        (relay.connect(tx).0, rx)
+    }
+
+    fn with_history(history_limit: usize) -> Config {
+        Config {
+            history_limit,
+            ..Config::default()
+        }
+    }
+
+    fn errors(rx: &mut mpsc::Receiver<String>) -> usize {
+        drain(rx)
+            .iter()
+            .filter(|f| matches!(f, RelayFrame::Error { .. }))
+            .count()
+    }
+
+    #[test]
+    fn routes_by_room_and_replays_bounded_history() {
+        let mut relay = Relay::open(with_history(2)).unwrap();
+        let (a, mut a_rx) = client(&mut relay);
+        let (b, mut b_rx) = client(&mut relay);
+        relay.handle(a, join("east", "alice"), Instant::now());
+        relay.handle(b, join("west", "bob"), Instant::now());
+        drain(&mut a_rx);
+        drain(&mut b_rx);
+
+        for id in ["1", "2", "3"] {
+            relay.handle(
+                a,
+                ClientFrame::Message(msg(id, "east", "alice")),
+                Instant::now(),
+            );
+        }
+        let relayed = drain(&mut a_rx)
+            .iter()
+            .filter(|f| matches!(f, RelayFrame::Message(_)))
+            .count();
+        assert_eq!(relayed, 3);
+        assert!(drain(&mut b_rx).is_empty());
+
+        let (c, mut c_rx) = client(&mut relay);
+        relay.handle(c, join("east", "carol"), Instant::now());
+        let RelayFrame::History { messages, .. } = &drain(&mut c_rx)[0] else {
+            panic!("history should come first");
+        };
+        let ids: Vec<_> = messages.iter().map(|m| m.id.as_str()).collect();
+        assert_eq!(ids, ["2", "3"]);
+    }
+
+    #[test]
+    fn clients_that_never_join_only_hear_the_default_room() {
+        let mut relay = Relay::open(Config::default()).unwrap();
+        let (a, _a_rx) = client(&mut relay);
+        let (_legacy, mut legacy_rx) = client(&mut relay);
+        relay.handle(a, join("east", "alice"), Instant::now());
+        relay.handle(
+            a,
+            ClientFrame::Message(msg("1", "east", "alice")),
+            Instant::now(),
+        );
+        relay.handle(a, join(DEFAULT_ROOM, "alice"), Instant::now());
+        relay.handle(
+            a,
+            ClientFrame::Message(msg("2", DEFAULT_ROOM, "alice")),
+            Instant::now(),
+        );
+
+        let ids: Vec<_> = drain(&mut legacy_rx)
+            .into_iter()
+            .filter_map(|f| match f {
+                RelayFrame::Message(m) => Some(m.id),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(ids, ["2"]);
+    }
+
+    #[test]
+    fn a_client_with_a_full_queue_is_told_to_disconnect() {
+        use futures_util::FutureExt;
+
+        let mut relay = Relay::open(Config::default()).unwrap();
+        let (a, _a_rx) = client(&mut relay);
+        let (tx, _slow_rx) = mpsc::channel(2);
+        let (slow, overflow) = relay.connect(tx);
+        relay.handle(slow, join("east", "bob"), Instant::now());
+        assert!(overflow.notified().now_or_never().is_none());
+
+        relay.handle(a, join("east", "alice"), Instant::now());
+        relay.handle(
+            a,
+            ClientFrame::Message(msg("1", "east", "alice")),
+            Instant::now(),
+        );
+        assert!(overflow.notified().now_or_never().is_some());
+    }
+
+    #[test]
+    fn presence_follows_joins_and_disconnects() {
+        let mut relay = Relay::open(Config::default()).unwrap();
+        let (a, mut a_rx) = client(&mut relay);
+        let (b, _b_rx) = client(&mut relay);
+        relay.handle(a, join("east", "alice"), Instant::now());
+        // An older client joins implicitly by posting.
+        relay.handle(
+            b,
+            ClientFrame::Message(msg("1", "east", "bob")),
+            Instant::now(),
+        );
+        relay.disconnect(b);
+
+        let presence: Vec<_> = drain(&mut a_rx)
+            .into_iter()
+            .filter_map(|f| match f {
+                RelayFrame::Presence { users, .. } => Some(users),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            presence,
+            [vec!["alice"], vec!["alice", "bob"], vec!["alice"]]
+        );
+    }
+
+    #[test]
+    fn history_file_is_reloaded_and_trimmed() {
+        let path = env::temp_dir().join(format!("mmtui-chat-{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let mut relay = Relay::open(Config {
+            history_file: Some(path.clone()),
+            ..Config::default()
+        })
+        .unwrap();
+        let (a, _rx) = client(&mut relay);
+        for id in ["1", "2", "3"] {
+            relay.handle(
+                a,
+                ClientFrame::Message(msg(id, "east", "alice")),
+                Instant::now(),
+            );
+        }
+        // Waits for the writer thread to finish.
+        drop(relay);
+
+        let reopened = Relay::open(Config {
+            history_file: Some(path.clone()),
+            ..with_history(2)
+        })
+        .unwrap();
+        let ids: Vec<_> = reopened.history["east"]
+            .iter()
+            .map(|m| m.id.as_str())
+            .collect();
+        assert_eq!(ids, ["2", "3"]);
+        assert_eq!(std::fs::read_to_string(&path).unwrap().lines().count(), 2);
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    fn history_file_is_compacted_while_running() {
+        let path = env::temp_dir().join(format!("mmtui-compact-{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let mut relay = Relay::open(Config {
+            history_file: Some(path.clone()),
+            burst: 100.0,
+            ..with_history(2)
+        })
+        .unwrap();
+        let (a, _rx) = client(&mut relay);
+        for id in 1..=20 {
+            relay.handle(
+                a,
+                ClientFrame::Message(msg(&id.to_string(), "east", "alice")),
+                Instant::now(),
+            );
+        }
+        drop(relay);
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert!(content.lines().count() <= 4, "{content}");
+        assert!(content.lines().last().unwrap().contains(r#""id":"20""#));
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    fn passwords_and_signed_names_are_enforced() {
+        let key = SecretKey::from_slice(&[5; 32]).unwrap();
+        let mut relay = Relay::open(Config {
+            password: Some("hoops".into()),
+            ..Config::default()
+        })
+        .unwrap();
+        let (a, mut a_rx) = client(&mut relay);
+        relay.handle(a, join("east", "alice"), Instant::now());
+        assert!(matches!(drain(&mut a_rx)[..], [RelayFrame::Error { .. }]));
+
+        let with_password = |user: &str| ClientFrame::Join {
+            room: "east".into(),
+            user: user.into(),
+            password: Some("hoops".into()),
+            since: None,
+        };
+        relay.handle(a, with_password("alice"), Instant::now());
+        let mut signed = msg("1", "east", "alice");
+        signed.sign(&key);
+        relay.handle(a, ClientFrame::Message(signed), Instant::now());
+        assert_eq!(relay.history["east"].len(), 1);
+
+        // A second client claiming alice's name without her key is dropped,
+        // as is a tampered message.
+        let (b, mut b_rx) = client(&mut relay);
+        relay.handle(b, with_password("alice"), Instant::now());
+        relay.handle(
+            b,
+            ClientFrame::Message(msg("2", "east", "alice")),
+            Instant::now(),
+        );
+        let mut tampered = msg("3", "east", "alice");
+        tampered.sign(&key);
+        tampered.body = "edited".into();
+        relay.handle(b, ClientFrame::Message(tampered), Instant::now());
+        relay.handle(
+            b,
+            ClientFrame::Message(msg("4", "east", "bob")),
+            Instant::now(),
+        );
+
+        assert_eq!(errors(&mut b_rx), 3);
+        assert_eq!(relay.history["east"].len(), 1);
+    }
+
+    #[test]
+    fn name_pins_survive_a_restart() {
+        let path = env::temp_dir().join(format!("mmtui-pins-{}.jsonl", std::process::id()));
+        let config = || Config {
+            history_file: Some(path.clone()),
+            ..with_history(0)
+        };
+        let key = SecretKey::from_slice(&[5; 32]).unwrap();
+        let mut relay = Relay::open(config()).unwrap();
+        let (a, _a_rx) = client(&mut relay);
+        relay.handle(a, join("east", "alice"), Instant::now());
+        let mut signed = msg("1", "east", "alice");
+        signed.sign(&key);
+        relay.handle(a, ClientFrame::Message(signed), Instant::now());
+        drop(relay);
+
+        // Nothing of alice is left in history, but her pin is.
+        let mut relay = Relay::open(config()).unwrap();
+        let (b, mut b_rx) = client(&mut relay);
+        relay.handle(b, join("east", "alice"), Instant::now());
+        drain(&mut b_rx);
+        relay.handle(
+            b,
+            ClientFrame::Message(msg("2", "east", "alice")),
+            Instant::now(),
+        );
+        assert_eq!(errors(&mut b_rx), 1);
+        let _ = std::fs::remove_file(&path);
+        let _ = std::fs::remove_file(path.with_extension("keys.json"));
+    }
+
+    #[test]
+    fn new_names_are_not_pinned_once_the_table_is_full() {
+        let key = SecretKey::from_slice(&[5; 32]).unwrap();
+        let mut relay = Relay::open(Config::default()).unwrap();
+        relay.keys = (0..MAX_PINS)
+            .map(|i| (format!("u{i}"), String::new()))
+            .collect();
+        let (a, mut a_rx) = client(&mut relay);
+        relay.handle(a, join("east", "alice"), Instant::now());
+        drain(&mut a_rx);
+        let mut signed = msg("1", "east", "alice");
+        signed.sign(&key);
+        relay.handle(a, ClientFrame::Message(signed), Instant::now());
+        assert_eq!(errors(&mut a_rx), 1);
+        assert_eq!(relay.keys.len(), MAX_PINS);
+    }
+
+    #[test]
+    fn floods_long_messages_and_slow_mode_are_refused() {
+        let mut relay = Relay::open(Config {
+            burst: 2.0,
+            per_minute: 60.0,
+            max_body: 20,
+            ..Config::default()
+        })
+        .unwrap();
+        let (a, mut a_rx) = client(&mut relay);
+        let start = Instant::now();
+        relay.handle(a, join("east", "alice"), start);
+        drain(&mut a_rx);
+
+        let post = |relay: &mut Relay, id: &str, at: Instant| {
+            relay.handle(a, ClientFrame::Message(msg(id, "east", "alice")), at);
+        };
+        for id in ["1", "2", "3"] {
+            post(&mut relay, id, start);
+        }
+        assert_eq!(errors(&mut a_rx), 1);
+        // One token back after a second.
+        post(&mut relay, "4", start + Duration::from_secs(1));
+        assert_eq!(errors(&mut a_rx), 0);
+
+        let mut long = msg("5", "east", "alice");
+        long.body = "x".repeat(21);
+        relay.handle(
+            a,
+            ClientFrame::Message(long),
+            start + Duration::from_secs(5),
+        );
+        assert_eq!(errors(&mut a_rx), 1);
+
+        relay
+            .slow_mode
+            .insert("east".into(), Duration::from_secs(30));
+        post(&mut relay, "6", start + Duration::from_secs(10));
+        assert_eq!(errors(&mut a_rx), 1);
+        assert_eq!(relay.history["east"].len(), 3);
+    }
+
+    #[test]
+    fn admins_can_mute_and_ban() {
+        let mut relay = Relay::open(Config {
+            admin_token: Some("s3cret".into()),
+            ..Config::default()
+        })
+        .unwrap();
+        let now = Instant::now();
+        let (admin, mut admin_rx) = client(&mut relay);
+        let (troll, mut troll_rx) = client(&mut relay);
+        relay.handle(admin, join("east", "ref"), now);
+        relay.handle(troll, join("east", "troll"), now);
+        drain(&mut admin_rx);
+        drain(&mut troll_rx);
+
+        let admin_cmd = |command: &str, token: &str| ClientFrame::Admin {
+            token: Some(token.into()),
+            command: command.into(),
+        };
+        relay.handle(troll, admin_cmd("/ban ref", "guess"), now);
+        assert_eq!(errors(&mut troll_rx), 1);
+
+        relay.handle(admin, admin_cmd("/mute troll 5", "s3cret"), now);
+        relay.handle(troll, ClientFrame::Message(msg("1", "east", "troll")), now);
+        assert_eq!(errors(&mut troll_rx), 1);
+        let after = now + Duration::from_secs(6 * 60);
+        relay.handle(
+            troll,
+            ClientFrame::Message(msg("2", "east", "troll")),
+            after,
+        );
+        assert_eq!(errors(&mut troll_rx), 0);
+
+        relay.handle(admin, admin_cmd("/ban troll", "s3cret"), after);
+        assert!(!relay.clients.contains_key(&troll));
+        let (again, mut again_rx) = client(&mut relay);
+        relay.handle(again, join("east", "troll"), after);
+        assert_eq!(errors(&mut again_rx), 1);
+
+        let announced: Vec<_> = drain(&mut admin_rx)
+            .into_iter()
+            .filter_map(|f| match f {
+                RelayFrame::System { message, .. } => Some(message),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            announced,
+            [
+                "troll was muted for 5 min by ref",
+                "troll was banned by ref"
+            ]
+        );
+
+        relay.handle(admin, admin_cmd("/ban ref", "s3cret"), after);
+        assert_eq!(errors(&mut admin_rx), 1);
+        assert!(relay.clients.contains_key(&admin));
+    }
+
+    #[test]
+    fn token_guessing_joins_and_long_names_are_limited() {
+        let mut relay = Relay::open(Config {
+            admin_token: Some("s3cret".into()),
+            burst: 6.0,
+            per_minute: 0.0,
+            ..Config::default()
+        })
+        .unwrap();
+        let now = Instant::now();
+        let (a, mut a_rx) = client(&mut relay);
+        relay.handle(a, join("east", &"x".repeat(MAX_NAME + 1)), now);
+        relay.handle(a, join(&"x".repeat(MAX_NAME + 1), "alice"), now);
+        assert_eq!(errors(&mut a_rx), 2);
+        relay.handle(a, join("east", "alice"), now);
+        drain(&mut a_rx);
+
+        let guess = |token: &str| ClientFrame::Admin {
+            token: Some(token.into()),
+            command: "/slow 0".into(),
+        };
+        relay.handle(a, guess("s3cre"), now);
+        relay.handle(a, guess("s3cret!"), now);
+        assert!(relay.clients.contains_key(&a));
+        relay.handle(a, guess("S3CRET"), now);
+        assert!(!relay.clients.contains_key(&a));
+
+        // Six control frames, then nothing until the bucket refills.
+        let (b, mut b_rx) = client(&mut relay);
+        for _ in 0..7 {
+            relay.handle(b, join("east", "bob"), now);
+        }
+        let refused = drain(&mut b_rx)
+            .into_iter()
+            .filter(
+                |f| matches!(f, RelayFrame::Error { message, .. } if message.contains("too many")),
+            )
+            .count();
+        assert_eq!(refused, 1);
+    }
+
+    #[test]
+    fn mutes_and_bans_follow_the_speaker_not_the_name() {
+        let path = env::temp_dir().join(format!("mmtui-bans-{}.jsonl", std::process::id()));
+        let config = || Config {
+            history_file: Some(path.clone()),
+            admin_token: Some("s3cret".into()),
+            ..Config::default()
+        };
+        let admin_cmd = |command: &str| ClientFrame::Admin {
+            token: Some("s3cret".into()),
+            command: command.into(),
+        };
+        let key = SecretKey::from_slice(&[9; 32]).unwrap();
+        let signed = |id: &str, author: &str| {
+            let mut m = msg(id, "east", author);
+            m.sign(&key);
+            ClientFrame::Message(m)
+        };
+        let now = Instant::now();
+        let mut relay = Relay::open(config()).unwrap();
+        let (admin, _admin_rx) = client(&mut relay);
+        let (troll, mut troll_rx) = client(&mut relay);
+        relay.handle(admin, join("east", "ref"), now);
+        relay.handle(troll, join("east", "troll"), now);
+        relay.handle(troll, signed("1", "troll"), now);
+        drain(&mut troll_rx);
+
+        relay.handle(admin, admin_cmd("/mute troll"), now);
+        relay.handle(troll, join("east", "troll2"), now);
+        drain(&mut troll_rx);
+        relay.handle(troll, ClientFrame::Message(msg("2", "east", "troll2")), now);
+        assert_eq!(errors(&mut troll_rx), 1);
+
+        relay.handle(admin, admin_cmd("/ban troll"), now);
+        assert!(relay.clients.contains_key(&troll));
+        drop(relay);
+
+        // After a restart, the same key is still refused under a new name.
+        let mut relay = Relay::open(config()).unwrap();
+        let (back, mut back_rx) = client(&mut relay);
+        relay.handle(back, join("east", "troll"), now);
+        assert_eq!(errors(&mut back_rx), 1);
+        relay.handle(back, join("east", "troll3"), now);
+        drain(&mut back_rx);
+        relay.handle(back, signed("3", "troll3"), now);
+        assert_eq!(errors(&mut back_rx), 1);
+        for kind in ["keys", "bans"] {
+            let _ = std::fs::remove_file(path.with_extension(format!("{kind}.json")));
+        }
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn a_taken_id_does_not_swallow_someone_elses_message() {
+        let mut relay = Relay::open(Config::default()).unwrap();
+        let (a, mut a_rx) = client(&mut relay);
+        let (b, mut b_rx) = client(&mut relay);
+        relay.handle(a, join("east", "alice"), Instant::now());
+        relay.handle(b, join("east", "bob"), Instant::now());
+        let id = "announce-401-final";
+        relay.handle(
+            a,
+            ClientFrame::Message(msg(id, "east", "alice")),
+            Instant::now(),
+        );
+        drain(&mut b_rx);
+        relay.handle(
+            b,
+            ClientFrame::Message(msg(id, "east", "bob")),
+            Instant::now(),
+        );
+        assert_eq!(relay.history["east"].len(), 2);
+        assert!(drain(&mut a_rx).iter().any(|f| matches!(
+            f,
+            RelayFrame::Message(m) if m.author == "bob"
+        )));
+    }
+
+    #[test]
+    fn acks_sequence_numbers_and_fills_gaps_on_rejoin() {
+        let mut relay = Relay::open(Config::default()).unwrap();
+        let (a, mut a_rx) = client(&mut relay);
+        let (b, mut b_rx) = client(&mut relay);
+        relay.handle(a, join("east", "alice"), Instant::now());
+        relay.handle(b, join("east", "bob"), Instant::now());
+        for id in ["1", "2", "3"] {
+            relay.handle(
+                a,
+                ClientFrame::Message(msg(id, "east", "alice")),
+                Instant::now(),
+            );
+        }
+        // A resend after a reconnect is acked again, not relayed twice.
+        relay.handle(
+            a,
+            ClientFrame::Message(msg("2", "east", "alice")),
+            Instant::now(),
+        );
+        let acks: Vec<_> = drain(&mut a_rx)
+            .into_iter()
+            .filter_map(|f| match f {
+                RelayFrame::Ack { id, seq } => Some((id, seq)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            acks,
+            [
+                ("1".into(), 1),
+                ("2".into(), 2),
+                ("3".into(), 3),
+                ("2".into(), 2)
+            ]
+        );
+        assert_eq!(relay.history["east"].len(), 3);
+
+        // Bob dropped after seq 1 and asks for the rest.
+        relay.disconnect(b);
+        drain(&mut b_rx);
+        let (b, mut b_rx) = client(&mut relay);
+        let rejoin = ClientFrame::Join {
+            room: "east".into(),
+            user: "bob".into(),
+            password: None,
+            since: Some(1),
+        };
+        relay.handle(b, rejoin, Instant::now());
+        let RelayFrame::History { messages, .. } = &drain(&mut b_rx)[0] else {
+            panic!("history should come first");
+        };
+        let seqs: Vec<_> = messages.iter().map(|m| m.seq).collect();
+        assert_eq!(seqs, [Some(2), Some(3)]);
+    }
+
+    #[test]
+    fn resends_after_a_reconnect_skip_flood_and_slow_mode() {
+        let mut relay = Relay::open(Config {
+            burst: 2.0,
+            slow_mode: Duration::from_secs(30),
+            ..Config::default()
+        })
+        .unwrap();
+        let start = Instant::now();
+        let (a, mut a_rx) = client(&mut relay);
+        relay.handle(a, join("east", "alice"), start);
+        for (i, id) in ["1", "2", "3", "4"].into_iter().enumerate() {
+            let at = start + Duration::from_secs(60 * i as u64);
+            relay.handle(a, ClientFrame::Message(msg(id, "east", "alice")), at);
+        }
+        assert_eq!(relay.history["east"].len(), 4);
+        relay.disconnect(a);
+        drain(&mut a_rx);
+
+        // The client comes back and resends everything it never saw acked,
+        // all at once and well inside slow mode.
+        let later = start + Duration::from_secs(4 * 60);
+        let (a, mut a_rx) = client(&mut relay);
+        relay.handle(a, join("east", "alice"), later);
+        for id in ["1", "2", "3", "4"] {
+            relay.handle(a, ClientFrame::Message(msg(id, "east", "alice")), later);
+        }
+        let acks: Vec<_> = drain(&mut a_rx)
+            .into_iter()
+            .filter_map(|f| match f {
+                RelayFrame::Ack { id, seq } => Some((id, seq)),
+                RelayFrame::Error { message, .. } => panic!("resend refused: {message}"),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            acks,
+            [
+                ("1".into(), 1),
+                ("2".into(), 2),
+                ("3".into(), 3),
+                ("4".into(), 4)
+            ]
+        );
+        assert_eq!(relay.history["east"].len(), 4);
+    }
+}
@@ -0,0 +1,696 @@
+//! Pool server: hosts bracket entries and a live leaderboard over plain HTTP.
+//!
+//! - `POST /picks` submit (or replace) a signed `BracketPicks` entry until tip-off
+//! - `GET /picks` every entry as a JSON array (usable as a Compare source)
+//! - `GET /picks/<user>` one entry
+//! - `GET /leaderboard` entries scored against the live bracket
+//!
+//! Entries are stored as `<MMTUI_POOL_DIR>/<year>/<user>.json`; unreadable
+//! ones are skipped with a warning. The bracket comes from the same settings
+//! as the TUI (`MMTUI_TOURNAMENT`, `MMTUI_BRACKET_SOURCES`,
+//! `MMTUI_HENRYGD_URL`, `MMTUI_ESPN_URL`, `MMTUI_BRACKET_JSON`). Give each
+//! pool its own `MMTUI_POOL_DIR`.
+//!
+//! Submissions must be sealed and signed by the key listed for their
+//! `user_id` in `MMTUI_POOL_SIGNERS` (`user_id pubkey` per line); without that
+//! file the pool is read-only. The lock is checked against the server's clock,
+//! not the entry's `submitted_at`.
+use bitcoin::secp256k1::PublicKey;
+use chrono::{DateTime, Utc};
+use mmtui::seal::{self, SealPolicy};
+use ncaa_api::client::NcaaApi;
+use ncaa_api::scoring::{ScoringRule, score_bracket};
+use ncaa_api::source::{ESPN_URL, HENRYGD_URL, SourceKind};
+use ncaa_api::{BracketPicks, Gender, RoundKind, Tournament};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::env;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::RwLock;
+
+const MAX_BODY_BYTES: usize = 256 * 1024;
+/// Longest request or header line, including its line ending.
+const MAX_LINE_BYTES: usize = 8 * 1024;
+const MAX_HEADER_LINES: usize = 64;
+const MAX_USER_ID_LEN: usize = 32;
+/// A client gets this long to send its request, and again to take the reply.
+const IO_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let addr = env::var("MMTUI_POOL_BIND").unwrap_or_else(|_| "0.0.0.0:8788".to_string());
+    let dir = PathBuf::from(env::var("MMTUI_POOL_DIR").unwrap_or_else(|_| "pool-data".into()));
+    let rule = match env::var("MMTUI_SCORING_RULE") {
+        Ok(raw) if !raw.trim().is_empty() => raw.parse().map_err(anyhow::Error::msg)?,
+        _ => ScoringRule::default(),
+    };
+    let lock_at = match env::var("MMTUI_POOL_LOCK_AT") {
+        Ok(raw) if !raw.trim().is_empty() => Some(raw.trim().parse::<DateTime<Utc>>()?),
+        _ => None,
+    };
+    let refresh = env::var("MMTUI_POOL_REFRESH_SECS")
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(60);
+    let signers = match env::var("MMTUI_POOL_SIGNERS") {
+        Ok(raw) if !raw.trim().is_empty() => {
+            Some(seal::load_signers(Path::new(raw.trim())).map_err(anyhow::Error::msg)?)
+        }
+        _ => None,
+    };
+
+    let api = bracket_api()?;
+    let tournament = api
+        .fetch_tournament()
+        .await
+        .map_err(|e| anyhow::anyhow!("{e}"))?;
+    let pool = Pool::open(dir, tournament, lock_at, rule, signers)?;
+    match &pool.signers {
+        Some(signers) => eprintln!("{} signers may submit picks", signers.len()),
+        None => eprintln!("MMTUI_POOL_SIGNERS not set; submissions are disabled"),
+    }
+    match pool.lock_at() {
+        Some(at) => eprintln!(
+            "{} entries loaded; entries lock at {at}",
+            pool.entries.len()
+        ),
+        None => eprintln!(
+            "{} entries loaded; no tip-off time known",
+            pool.entries.len()
+        ),
+    }
+    let pool = Arc::new(RwLock::new(pool));
+
+    tokio::spawn(refresh_scores(
+        api,
+        pool.clone(),
+        Duration::from_secs(refresh),
+    ));
+
+    let listener = TcpListener::bind(&addr).await?;
+    eprintln!("pool server listening on {addr}");
+
+    loop {
+        let (stream, peer) = listener.accept().await?;
+        let pool = pool.clone();
+        tokio::spawn(async move {
+            if let Err(e) = handle_client(stream, pool).await {
+                eprintln!("client {peer}: {e}");
+            }
+        });
+    }
+}
+
+/// The TUI's tournament and bracket sources, read from the same variables.
+fn bracket_api() -> anyhow::Result<NcaaApi> {
+    let var = |name: &str| env::var(name).ok().filter(|v| !v.trim().is_empty());
+    let gender = match var("MMTUI_TOURNAMENT") {
+        Some(raw) => raw.parse::<Gender>().map_err(anyhow::Error::msg)?,
+        None => Gender::default(),
+    };
+    let order = match var("MMTUI_BRACKET_SOURCES") {
+        Some(raw) => raw
+            .split(',')
+            .filter(|name| !name.trim().is_empty())
+            .map(str::parse)
+            .collect::<Result<Vec<SourceKind>, _>>()
+            .map_err(anyhow::Error::msg)?,
+        None => SourceKind::DEFAULT_ORDER.to_vec(),
+    };
+    let henrygd_url = var("MMTUI_HENRYGD_URL").unwrap_or_else(|| HENRYGD_URL.into());
+    let espn_url = var("MMTUI_ESPN_URL").unwrap_or_else(|| ESPN_URL.into());
+    Ok(
+        NcaaApi::with_sources(gender, &order, &henrygd_url, &espn_url)
+            .with_bracket_json(var("MMTUI_BRACKET_JSON")),
+    )
+}
+
+/// Keep the bracket live so the leaderboard follows the games.
+async fn refresh_scores(api: NcaaApi, pool: Arc<RwLock<Pool>>, every: Duration) {
+    let mut interval = tokio::time::interval(every);
+    interval.tick().await;
+    loop {
+        interval.tick().await;
+        match api.fetch_scoreboard().await {
+            Ok(games) => pool.write().await.tournament.merge_updates(games),
+            Err(e) => eprintln!("score refresh failed: {e}"),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Pool state
+// ---------------------------------------------------------------------------
+
+struct Pool {
+    dir: PathBuf,
+    tournament: Tournament,
+    /// Explicit lock time; tip-off of the Round of 64 otherwise.
+    lock_override: Option<DateTime<Utc>>,
+    rule: ScoringRule,
+    /// Key each user must sign with; `None` refuses every submission.
+    signers: Option<HashMap<String, PublicKey>>,
+    entries: BTreeMap<String, BracketPicks>,
+}
+
+#[derive(Debug, Serialize)]
+struct Standing {
+    rank: usize,
+    user_id: String,
+    points: u32,
+    max_points: u32,
+    correct: u32,
+    total: u32,
+}
+
+impl Pool {
+    /// Load any entries already saved for the tournament's year, skipping
+    /// files that can't be read or parsed.
+    fn open(
+        dir: PathBuf,
+        tournament: Tournament,
+        lock_override: Option<DateTime<Utc>>,
+        rule: ScoringRule,
+        signers: Option<HashMap<String, PublicKey>>,
+    ) -> anyhow::Result<Self> {
+        let mut entries = BTreeMap::new();
+        let year_dir = dir.join(tournament.year.to_string());
+        if let Ok(files) = std::fs::read_dir(&year_dir) {
+            for file in files.flatten() {
+                let path = file.path();
+                if path.extension().and_then(|e| e.to_str()) != Some("json") {
+                    continue;
+                }
+                let content = match std::fs::read_to_string(&path) {
+                    Ok(content) => content,
+                    Err(e) => {
+                        eprintln!("skipping {}: {e}", path.display());
+                        continue;
+                    }
+                };
+                match serde_json::from_str::<BracketPicks>(&content) {
+                    Ok(picks) => {
+                        entries.insert(picks.user_id.clone(), picks);
+                    }
+                    Err(e) => eprintln!("skipping {}: {e}", path.display()),
+                }
+            }
+        }
+        Ok(Self {
+            dir,
+            tournament,
+            lock_override,
+            rule,
+            signers,
+            entries,
+        })
+    }
+
+    fn lock_at(&self) -> Option<DateTime<Utc>> {
+        self.lock_override.or_else(|| tip_off(&self.tournament))
+    }
+
+    /// Validate and store an entry. Resubmitting before the lock replaces it.
+    fn submit(&mut self, picks: BracketPicks, now: DateTime<Utc>) -> Result<(), (u16, String)> {
+        if let Some(at) = self.lock_at()
+            && now >= at
+        {
+            return Err((403, format!("entries locked at tip-off ({at})")));
+        }
+        validate_user_id(&picks.user_id).map_err(|e| (400, e))?;
+        let Some(signers) = &self.signers else {
+            return Err((403, "this pool does not accept submissions".into()));
+        };
+        let policy = SealPolicy {
+            signers: Some(signers.clone()),
+            allow_unsealed: false,
+        };
+        seal::check(picks.clone(), &self.tournament, &policy).map_err(|e| {
+            (
+                401,
+                format!("picks must be sealed with {}'s key: {e}", picks.user_id),
+            )
+        })?;
+        if picks.year != self.tournament.year {
+            return Err((
+                400,
+                format!(
+                    "picks are for {}, pool is {}",
+                    picks.year, self.tournament.year
+                ),
+            ));
+        }
+        if let Some(unknown) = picks
+            .selections
+            .keys()
+            .find(|id| self.tournament.find_game(id).is_none())
+        {
+            return Err((400, format!("unknown game id {unknown}")));
+        }
+
+        save_entry(&self.entry_path(&picks.user_id), &picks).map_err(|e| (500, e))?;
+        self.entries.insert(picks.user_id.clone(), picks);
+        Ok(())
+    }
+
+    fn entry_path(&self, user_id: &str) -> PathBuf {
+        self.dir
+            .join(self.tournament.year.to_string())
+            .join(format!("{user_id}.json"))
+    }
+
+    fn leaderboard(&self) -> Vec<Standing> {
+        let mut rows: Vec<Standing> = self
+            .entries
+            .values()
+            .map(|picks| {
+                let card = score_bracket(&self.tournament, picks, self.rule);
+                Standing {
+                    rank: 0,
+                    user_id: picks.user_id.clone(),
+                    points: card.points,
+                    max_points: card.max_points,
+                    correct: card.correct,
+                    total: card.total,
+                }
+            })
+            .collect();
+        rows.sort_by(|a, b| {
+            b.points
+                .cmp(&a.points)
+                .then_with(|| b.correct.cmp(&a.correct))
+                .then_with(|| a.user_id.cmp(&b.user_id))
+        });
+        // Ties share a rank.
+        for i in 0..rows.len() {
+            rows[i].rank = if i > 0 && rows[i].points == rows[i - 1].points {
+                rows[i - 1].rank
+            } else {
+                i + 1
+            };
+        }
+        rows
+    }
+}
+
+/// Earliest scheduled Round of 64 game; the play-in games don't lock entries.
+fn tip_off(tournament: &Tournament) -> Option<DateTime<Utc>> {
+    tournament
+        .regions
+        .iter()
+        .flat_map(|r| &r.rounds)
+        .filter(|r| r.kind != RoundKind::FirstFour)
+        .flat_map(|r| &r.games)
+        .filter_map(|g| g.start_time)
+        .min()
+}
+
+/// User ids double as file names, so keep them to a safe alphabet.
+fn validate_user_id(user_id: &str) -> Result<(), String> {
+    if user_id.is_empty() || user_id.len() > MAX_USER_ID_LEN {
+        return Err(format!("user_id must be 1-{MAX_USER_ID_LEN} characters"));
+    }
+    if !user_id
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.')
+        || user_id.starts_with('.')
+    {
+        return Err("user_id may only use letters, digits, '-', '_' and '.'".into());
+    }
+    Ok(())
+}
+
+/// Write via a temp file so a crash never leaves a half-written entry.
+fn save_entry(path: &Path, picks: &BracketPicks) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| format!("create dir failed: {e}"))?;
+    }
+    let payload =
+        serde_json::to_string_pretty(picks).map_err(|e| format!("serialize failed: {e}"))?;
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, payload).map_err(|e| format!("write failed: {e}"))?;
+    std::fs::rename(&tmp, path).map_err(|e| format!("write failed: {e}"))
+}
+
+// ---------------------------------------------------------------------------
+// HTTP
+// ---------------------------------------------------------------------------
+
+struct Request {
+    method: String,
+    path: String,
+    body: Vec<u8>,
+}
+
+async fn handle_client(stream: TcpStream, pool: Arc<RwLock<Pool>>) -> anyhow::Result<()> {
+    let mut reader = BufReader::new(stream);
+    let request = tokio::time::timeout(IO_TIMEOUT, read_request(&mut reader))
+        .await
+        .unwrap_or_else(|_| Err((400, "timed out reading request".into())));
+    let (status, body) = match request {
+        Ok(req) => route(&pool, req, Utc::now()).await,
+        Err((status, e)) => (status, error_body(&e)),
+    };
+    let response = format!(
+        "HTTP/1.1 {status} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\
+         Connection: close\r\n\r\n{body}",
+        reason(status),
+        body.len()
+    );
+    let mut stream = reader.into_inner();
+    tokio::time::timeout(IO_TIMEOUT, async {
+        stream.write_all(response.as_bytes()).await?;
+        stream.shutdown().await
+    })
+    .await
+    .map_err(|_| anyhow::anyhow!("timed out writing response"))??;
+    Ok(())
+}
+
+/// Read one request. Errors carry the status to answer with.
+async fn read_request<R: AsyncBufRead + Unpin>(reader: &mut R) -> Result<Request, (u16, String)> {
+    let mut line = String::new();
+    read_line(reader, &mut line, 414).await?;
+    let mut parts = line.split_whitespace();
+    let (Some(method), Some(path)) = (parts.next(), parts.next()) else {
+        return Err((400, "malformed request line".into()));
+    };
+    let (method, path) = (method.to_string(), path.to_string());
+
+    let mut content_length = 0usize;
+    let mut ended = false;
+    for _ in 0..MAX_HEADER_LINES {
+        line.clear();
+        read_line(reader, &mut line, 431).await?;
+        let header = line.trim_end();
+        if header.is_empty() {
+            ended = true;
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':')
+            && name.eq_ignore_ascii_case("content-length")
+        {
+            content_length = value
+                .trim()
+                .parse()
+                .map_err(|_| (400, "invalid Content-Length".to_string()))?;
+        }
+    }
+    if !ended {
+        return Err((431, format!("more than {MAX_HEADER_LINES} header lines")));
+    }
+    if content_length > MAX_BODY_BYTES {
+        return Err((413, format!("body larger than {MAX_BODY_BYTES} bytes")));
+    }
+
+    let mut body = vec![0; content_length];
+    reader
+        .read_exact(&mut body)
+        .await
+        .map_err(|e| (400, e.to_string()))?;
+    Ok(Request { method, path, body })
+}
+
+/// Append one line to `line`, reading at most [`MAX_LINE_BYTES`]; a longer
+/// line is answered with `too_long`.
+async fn read_line<R: AsyncBufRead + Unpin>(
+    reader: &mut R,
+    line: &mut String,
+    too_long: u16,
+) -> Result<(), (u16, String)> {
+    let read = (&mut *reader)
+        .take(MAX_LINE_BYTES as u64)
+        .read_line(line)
+        .await
+        .map_err(|e| (400, e.to_string()))?;
+    if read == MAX_LINE_BYTES && !line.ends_with('\n') {
+        return Err((too_long, format!("line longer than {MAX_LINE_BYTES} bytes")));
+    }
+    Ok(())
+}
+
+async fn route(pool: &RwLock<Pool>, req: Request, now: DateTime<Utc>) -> (u16, String) {
+    let path = req.path.split('?').next().unwrap_or_default();
+    match (req.method.as_str(), path) {
+        ("POST", "/picks") => {
+            let picks: BracketPicks = match serde_json::from_slice(&req.body) {
+                Ok(picks) => picks,
+                Err(e) => return (400, error_body(&format!("invalid picks json: {e}"))),
+            };
+            let user_id = picks.user_id.clone();
+            match pool.write().await.submit(picks, now) {
+                Ok(()) => (201, serde_json::json!({ "user_id": user_id }).to_string()),
+                Err((status, e)) => (status, error_body(&e)),
+            }
+        }
+        ("GET", "/picks") => {
+            let pool = pool.read().await;
+            json_body(&pool.entries.values().collect::<Vec<_>>())
+        }
+        ("GET", "/leaderboard") => json_body(&pool.read().await.leaderboard()),
+        ("GET", _) if path.starts_with("/picks/") => {
+            let user_id = &path["/picks/".len()..];
+            match pool.read().await.entries.get(user_id) {
+                Some(picks) => json_body(picks),
+                None => (404, error_body(&format!("no entry for {user_id}"))),
+            }
+        }
+        _ => (404, error_body("not found")),
+    }
+}
+
+fn json_body<T: Serialize + ?Sized>(value: &T) -> (u16, String) {
+    match serde_json::to_string(value) {
+        Ok(body) => (200, body),
+        Err(e) => (500, error_body(&e.to_string())),
+    }
+}
+
+fn error_body(message: &str) -> String {
+    serde_json::json!({ "error": message }).to_string()
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        201 => "Created",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        413 => "Content Too Large",
+        414 => "URI Too Long",
+        431 => "Request Header Fields Too Large",
+        _ => "Internal Server Error",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bitcoin::secp256k1::{Secp256k1, SecretKey};
+    use ncaa_api::{Game, Region, Round};
+
+    fn tournament(tip_off: DateTime<Utc>) -> Tournament {
+        Tournament {
+            year: 2025,
+            regions: vec![Region {
+                name: "East".into(),
+                rounds: vec![Round {
+                    kind: RoundKind::First,
+                    games: vec![Game {
+                        id: "g1".into(),
+                        start_time: Some(tip_off),
+                        ..Default::default()
+                    }],
+                }],
+                ..Default::default()
+            }],
+            ..Default::default()
+        }
+    }
+
+    fn key(byte: u8) -> SecretKey {
+        SecretKey::from_slice(&[byte; 32]).unwrap()
+    }
+
+    fn signers() -> Option<HashMap<String, PublicKey>> {
+        let pubkey = key(7).public_key(&Secp256k1::new());
+        Some(
+            ["alice", "bob", "carol"]
+                .map(|u| (u.to_string(), pubkey))
+                .into(),
+        )
+    }
+
+    fn unsigned(user: &str) -> BracketPicks {
+        BracketPicks {
+            user_id: user.into(),
+            year: 2025,
+            selections: [("g1".to_string(), "top:g1".to_string())].into(),
+            ..Default::default()
+        }
+    }
+
+    fn picks(user: &str) -> BracketPicks {
+        let mut picks = unsigned(user);
+        let at = "2025-03-19T00:00:00Z".parse().unwrap();
+        seal::seal(&mut picks, at, Some(&key(7)));
+        picks
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("mmtui-pool-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn entries_lock_at_tip_off_and_survive_restart() {
+        let tip = "2025-03-20T16:15:00Z".parse().unwrap();
+        let dir = temp_dir("lock");
+        let mut pool = Pool::open(
+            dir.clone(),
+            tournament(tip),
+            None,
+            ScoringRule::default(),
+            signers(),
+        )
+        .unwrap();
+
+        let before = "2025-03-20T16:00:00Z".parse().unwrap();
+        assert!(pool.submit(picks("alice"), before).is_ok());
+        let err = pool.submit(picks("bob"), tip).unwrap_err();
+        assert_eq!(err.0, 403);
+
+        let reopened = Pool::open(
+            dir.clone(),
+            tournament(tip),
+            None,
+            ScoringRule::default(),
+            signers(),
+        );
+        let names: Vec<_> = reopened.unwrap().entries.into_keys().collect();
+        assert_eq!(names, ["alice"]);
+
+        // An entry that can't be read is skipped, not fatal.
+        std::fs::create_dir_all(dir.join("2025").join("bob.json")).unwrap();
+        let reopened = Pool::open(
+            dir.clone(),
+            tournament(tip),
+            None,
+            ScoringRule::default(),
+            signers(),
+        );
+        assert_eq!(reopened.unwrap().entries.len(), 1);
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn rejects_unsafe_user_ids_and_unknown_games() {
+        let tip = "2025-03-20T16:15:00Z".parse().unwrap();
+        let mut pool = Pool::open(
+            temp_dir("reject"),
+            tournament(tip),
+            None,
+            ScoringRule::default(),
+            signers(),
+        )
+        .unwrap();
+        let before = "2025-03-19T00:00:00Z".parse().unwrap();
+
+        assert_eq!(pool.submit(picks("../etc"), before).unwrap_err().0, 400);
+        let mut bad = unsigned("carol");
+        bad.selections.insert("nope".into(), "x".into());
+        seal::seal(&mut bad, before, Some(&key(7)));
+        assert_eq!(pool.submit(bad, before).unwrap_err().0, 400);
+        assert!(pool.entries.is_empty());
+    }
+
+    #[test]
+    fn submissions_need_the_users_listed_key() {
+        let tip = "2025-03-20T16:15:00Z".parse().unwrap();
+        let open = |name: &str, signers| {
+            Pool::open(
+                temp_dir(name),
+                tournament(tip),
+                None,
+                ScoringRule::default(),
+                signers,
+            )
+            .unwrap()
+        };
+        let before = "2025-03-19T00:00:00Z".parse().unwrap();
+
+        let mut pool = open("auth", signers());
+        assert_eq!(pool.submit(unsigned("alice"), before).unwrap_err().0, 401);
+        let mut forged = unsigned("alice");
+        seal::seal(&mut forged, before, Some(&key(9)));
+        assert_eq!(pool.submit(forged, before).unwrap_err().0, 401);
+        assert_eq!(pool.submit(picks("dave"), before).unwrap_err().0, 401);
+        assert!(pool.entries.is_empty());
+
+        let mut read_only = open("read-only", None);
+        assert_eq!(read_only.submit(picks("alice"), before).unwrap_err().0, 403);
+    }
+
+    #[tokio::test]
+    async fn routes_unknown_paths_to_404() {
+        let tip = "2025-03-20T16:15:00Z".parse().unwrap();
+        let pool = Pool::open(
+            temp_dir("route"),
+            tournament(tip),
+            None,
+            ScoringRule::default(),
+            signers(),
+        );
+        let pool = RwLock::new(pool.unwrap());
+        let req = |method: &str, path: &str| Request {
+            method: method.into(),
+            path: path.into(),
+            body: Vec::new(),
+        };
+
+        assert_eq!(route(&pool, req("GET", "/nope"), tip).await.0, 404);
+        assert_eq!(route(&pool, req("GET", "/picks/alice"), tip).await.0, 404);
+        assert_eq!(
+            route(&pool, req("GET", "/leaderboard"), tip).await,
+            (200, "[]".into())
+        );
+    }
+
+    #[tokio::test]
+    async fn oversized_requests_are_refused() {
+        let status = |raw: String| async move {
+            let mut reader = raw.as_bytes();
+            read_request(&mut reader)
+                .await
+                .err()
+                .map(|(status, _)| status)
+        };
+        let ok = "GET /picks HTTP/1.1\r\nHost: pool\r\n\r\n".to_string();
+        assert_eq!(status(ok).await, None);
+
+        let long_path = format!("GET /{} HTTP/1.1\r\n\r\n", "a".repeat(MAX_LINE_BYTES));
+        assert_eq!(status(long_path).await, Some(414));
+        let long_header = format!(
+            "GET / HTTP/1.1\r\nX: {}\r\n\r\n",
+            "a".repeat(MAX_LINE_BYTES)
+        );
+        assert_eq!(status(long_header).await, Some(431));
+        let many_headers = format!(
+            "GET / HTTP/1.1\r\n{}\r\n",
+            "X: 1\r\n".repeat(MAX_HEADER_LINES + 1)
+        );
+        assert_eq!(status(many_headers).await, Some(431));
+        let big_body = format!(
+            "POST /picks HTTP/1.1\r\nContent-Length: {}\r\n\r\n",
+            MAX_BODY_BYTES + 1
+        );
+        assert_eq!(status(big_body).await, Some(413));
+    }
+}
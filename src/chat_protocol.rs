@@ -0,0 +1,220 @@
+//! Frames exchanged between chat clients and `chat-relay`.
+//!
+//! Every frame is a JSON object with a `type` tag. Older clients send and
+//! expect a bare [`ChatWireMessage`]; the relay still accepts those, and
+//! since a `message` frame is a `ChatWireMessage` plus the tag, older clients
+//! can read what the relay sends them.
+//!
+//! Messages may be signed with a secp256k1 key. The relay drops messages whose
+//! signature fails, and clients show which authors verified.
+use std::str::FromStr;
+
+use bitcoin::hashes::{Hash, sha256};
+use bitcoin::hex::{DisplayHex, FromHex};
+use bitcoin::secp256k1::{Message, PublicKey, Secp256k1, SecretKey, ecdsa::Signature};
+use serde::{Deserialize, Serialize};
+
+/// Bumped if the signed layout ever changes.
+const SIGNING_DOMAIN: &str = "mmtui-chat-v1";
+
+/// Room clients start in. Clients that never join a room only get this one's
+/// traffic from the relay.
+pub const DEFAULT_ROOM: &str = "march-madness";
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChatWireMessage {
+    pub id: String,
+    pub room: String,
+    pub author: String,
+    pub body: String,
+    pub timestamp: String,
+    /// Signer's compressed public key, hex.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pubkey: Option<String>,
+    /// Compact ECDSA signature over every other field, hex.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+    /// Position in the room, assigned by the relay; not signed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seq: Option<u64>,
+}
+
+/// Result of checking a message's signature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Authenticity {
+    Unsigned,
+    Valid,
+    Invalid,
+}
+
+impl ChatWireMessage {
+    pub fn sign(&mut self, key: &SecretKey) {
+        let secp = Secp256k1::new();
+        let sig = secp.sign_ecdsa(&self.digest(), key);
+        self.pubkey = Some(key.public_key(&secp).to_string());
+        self.signature = Some(sig.serialize_compact().to_lower_hex_string());
+    }
+
+    pub fn authenticity(&self) -> Authenticity {
+        let (Some(pubkey), Some(signature)) = (&self.pubkey, &self.signature) else {
+            return Authenticity::Unsigned;
+        };
+        let verified = PublicKey::from_str(pubkey).ok().zip(
+            Vec::<u8>::from_hex(signature)
+                .ok()
+                .and_then(|bytes| Signature::from_compact(&bytes).ok()),
+        );
+        match verified {
+            Some((pubkey, sig))
+                if Secp256k1::verification_only()
+                    .verify_ecdsa(&self.digest(), &sig, &pubkey)
+                    .is_ok() =>
+            {
+                Authenticity::Valid
+            }
+            _ => Authenticity::Invalid,
+        }
+    }
+
+    fn digest(&self) -> Message {
+        let data = [
+            SIGNING_DOMAIN,
+            &self.id,
+            &self.room,
+            &self.author,
+            &self.body,
+            &self.timestamp,
+        ]
+        .join("\n");
+        Message::from_digest(sha256::Hash::hash(data.as_bytes()).to_byte_array())
+    }
+}
+
+/// Client → relay.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ClientFrame {
+    /// Enter `room` as `user`, leaving any previous room. `password` is
+    /// required when the relay has one set. With `since`, only messages after
+    /// that sequence number are replayed.
+    Join {
+        room: String,
+        user: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        password: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        since: Option<u64>,
+    },
+    Message(ChatWireMessage),
+    /// Moderation command such as `/mute <user> [minutes]`; the relay checks
+    /// `token` against its admin token.
+    Admin {
+        token: Option<String>,
+        command: String,
+    },
+}
+
+impl ClientFrame {
+    /// Parse a tagged frame, or a bare message from an older client.
+    pub fn parse(text: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(text)
+            .or_else(|_| serde_json::from_str::<ChatWireMessage>(text).map(ClientFrame::Message))
+    }
+}
+
+/// Relay → client.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RelayFrame {
+    Message(ChatWireMessage),
+    /// Recent messages for a room, oldest first, sent right after joining.
+    History {
+        room: String,
+        messages: Vec<ChatWireMessage>,
+    },
+    /// Everyone currently in the room, sorted.
+    Presence {
+        room: String,
+        users: Vec<String>,
+    },
+    /// The relay stored and relayed message `id` as `seq`.
+    Ack {
+        id: String,
+        seq: u64,
+    },
+    /// Why the relay refused a join or dropped a message; `message_id` names
+    /// the dropped message.
+    Error {
+        message: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        message_id: Option<String>,
+    },
+    /// Something the relay did that the whole room should see, such as a
+    /// moderation action.
+    System {
+        room: String,
+        message: String,
+    },
+}
+
+impl RelayFrame {
+    /// Parse a tagged frame, or a bare message from an older relay.
+    pub fn parse(text: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(text)
+            .or_else(|_| serde_json::from_str::<ChatWireMessage>(text).map(RelayFrame::Message))
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message() -> ChatWireMessage {
+        ChatWireMessage {
+            id: "m1".into(),
+            room: "march-madness".into(),
+            author: "alice".into(),
+            body: "go heels".into(),
+            timestamp: "19:05".into(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn bare_messages_from_older_peers_still_parse() {
+        let legacy = serde_json::to_string(&message()).unwrap();
+        assert_eq!(
+            ClientFrame::parse(&legacy).unwrap(),
+            ClientFrame::Message(message())
+        );
+        assert_eq!(
+            RelayFrame::parse(&legacy).unwrap(),
+            RelayFrame::Message(message())
+        );
+    }
+
+    #[test]
+    fn older_clients_can_read_tagged_messages() {
+        let tagged = RelayFrame::Message(message()).to_json();
+        assert!(tagged.contains(r#""type":"message""#));
+        let read: ChatWireMessage = serde_json::from_str(&tagged).unwrap();
+        assert_eq!(read, message());
+    }
+
+    #[test]
+    fn signatures_cover_the_body() {
+        let key = SecretKey::from_slice(&[3; 32]).unwrap();
+        let mut signed = message();
+        assert_eq!(signed.authenticity(), Authenticity::Unsigned);
+        signed.sign(&key);
+        assert_eq!(signed.authenticity(), Authenticity::Valid);
+
+        let mut edited = signed.clone();
+        edited.body = "go duke".into();
+        assert_eq!(edited.authenticity(), Authenticity::Invalid);
+    }
+}
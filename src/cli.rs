@@ -0,0 +1,591 @@
+//! Headless subcommands for scripts and cron jobs: print the bracket, scores,
+//! a game or the pool leaderboard, and move saved picks in and out, without
+//! starting the TUI.
+//!
+//! Text output is meant for people; `--format json` for other tools. Results
+//! go to stdout, warnings such as unreadable Compare sources to stderr.
+use crate::app::{App, pick_wizard_path};
+use crate::state::app_settings::AppSettings;
+use crate::state::messages::NetworkRequest;
+use crate::state::network::load_compare_sources;
+use chrono::Utc;
+use mmtui::seal;
+use ncaa_api::client::{NcaaApi, season_tournament_year};
+use ncaa_api::{BracketPicks, Game, GameDetail, GameStatus, Gender, TeamSeed, Tournament};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+/// Plays shown by `mmtui game` in text form.
+const RECENT_PLAYS: usize = 10;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Format {
+    #[default]
+    Text,
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            other => Err(format!("unknown format {other:?} (expected text or json)")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    /// The bracket with results; the live one unless `year` is given.
+    Bracket { year: Option<u16>, format: Format },
+    /// Today's tournament games from the scoreboard.
+    Scores { format: Format },
+    /// Box score and plays for an ESPN event ID.
+    Game { id: String, format: Format },
+    /// Saved Pick Wizard picks, to stdout or `out`.
+    PicksExport {
+        year: Option<u16>,
+        out: Option<PathBuf>,
+    },
+    /// Save a picks file as your picks for its year.
+    PicksImport { file: PathBuf, force: bool },
+    /// Every Compare source scored against the live bracket.
+    Leaderboard { format: Format },
+}
+
+impl Command {
+    /// `name` is the subcommand, `args` everything after it.
+    pub fn parse(name: &str, args: &[String]) -> Result<Self, String> {
+        match name {
+            "bracket" => {
+                let flags = Flags::parse(args, &["--year", "--format"], 0)?;
+                Ok(Command::Bracket {
+                    year: flags.year,
+                    format: flags.format,
+                })
+            }
+            "scores" => {
+                let flags = Flags::parse(args, &["--format"], 0)?;
+                Ok(Command::Scores {
+                    format: flags.format,
+                })
+            }
+            "game" => {
+                let mut flags = Flags::parse(args, &["--format"], 1)
+                    .map_err(|e| format!("{e}\nusage: mmtui game <id> [--format text|json]"))?;
+                Ok(Command::Game {
+                    id: flags.positional.remove(0),
+                    format: flags.format,
+                })
+            }
+            "picks" => match args.split_first() {
+                Some((action, rest)) if action == "export" => {
+                    let flags = Flags::parse(rest, &["--year", "--out"], 0)?;
+                    Ok(Command::PicksExport {
+                        year: flags.year,
+                        out: flags.out,
+                    })
+                }
+                Some((action, rest)) if action == "import" => {
+                    let mut flags = Flags::parse(rest, &["--force"], 1)
+                        .map_err(|e| format!("{e}\nusage: mmtui picks import <file> [--force]"))?;
+                    Ok(Command::PicksImport {
+                        file: PathBuf::from(flags.positional.remove(0)),
+                        force: flags.force,
+                    })
+                }
+                _ => Err("usage: mmtui picks export|import".to_string()),
+            },
+            "leaderboard" => {
+                let flags = Flags::parse(args, &["--format"], 0)?;
+                Ok(Command::Leaderboard {
+                    format: flags.format,
+                })
+            }
+            other => Err(format!("Unknown command: {other}")),
+        }
+    }
+}
+
+/// Options shared by the subcommands; each accepts only some of them.
+#[derive(Debug, Default)]
+struct Flags {
+    year: Option<u16>,
+    format: Format,
+    out: Option<PathBuf>,
+    force: bool,
+    positional: Vec<String>,
+}
+
+impl Flags {
+    /// `--flag value` and `--flag=value` are both accepted.
+    fn parse(args: &[String], allowed: &[&str], positional: usize) -> Result<Self, String> {
+        let mut flags = Self::default();
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            if !arg.starts_with("--") {
+                flags.positional.push(arg.clone());
+                continue;
+            }
+            let (name, inline) = match arg.split_once('=') {
+                Some((name, value)) => (name, Some(value.to_string())),
+                None => (arg.as_str(), None),
+            };
+            if !allowed.contains(&name) {
+                return Err(format!("unknown option {name}"));
+            }
+            let mut value = || {
+                inline
+                    .clone()
+                    .or_else(|| args.next().cloned())
+                    .ok_or_else(|| format!("{name} needs a value"))
+            };
+            match name {
+                "--year" => {
+                    flags.year = Some(
+                        value()?
+                            .parse()
+                            .map_err(|_| "--year must be a year such as 2025".to_string())?,
+                    )
+                }
+                "--format" => flags.format = value()?.parse()?,
+                "--out" => flags.out = Some(PathBuf::from(value()?)),
+                "--force" => flags.force = true,
+                _ => {}
+            }
+        }
+        if flags.positional.len() != positional {
+            return Err(format!(
+                "expected {positional} argument(s), got {}",
+                flags.positional.len()
+            ));
+        }
+        Ok(flags)
+    }
+}
+
+pub async fn run(command: Command, settings: AppSettings) -> Result<(), String> {
+    let api = settings.api(settings.gender);
+    match command {
+        Command::Bracket { year, format } => {
+            let tournament = load_tournament(&api, year).await?;
+            print(format, &tournament, bracket_text)
+        }
+        Command::Scores { format } => {
+            let games = api.fetch_scoreboard().await.map_err(|e| e.to_string())?;
+            print(format, &games, |games| {
+                games
+                    .iter()
+                    .map(|g| format!("{:>10}  {}", g.id, game_line(g, settings.gender)))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            })
+        }
+        Command::Game { id, format } => {
+            let detail = api
+                .fetch_game_detail(&id)
+                .await
+                .map_err(|e| e.to_string())?;
+            if detail.plays.is_empty() && detail.home_box.team.is_none() {
+                return Err(format!("no game found with id {id}"));
+            }
+            print(format, &detail, game_text)
+        }
+        Command::PicksExport { year, out } => {
+            let year = year.unwrap_or_else(|| season_tournament_year(Utc::now()) as u16);
+            let path = pick_wizard_path(settings.gender, year);
+            let picks = read_picks(&path).map_err(|e| format!("no saved picks for {year}: {e}"))?;
+            let json = to_json(&picks)?;
+            match out {
+                Some(out) => std::fs::write(&out, json + "\n")
+                    .map_err(|e| format!("could not write {}: {e}", out.display())),
+                None => {
+                    println!("{json}");
+                    Ok(())
+                }
+            }
+        }
+        Command::PicksImport { file, force } => {
+            let picks = read_picks(&file)?;
+            if picks.year == 0 {
+                return Err(format!("{}: picks have no year", file.display()));
+            }
+            let path = pick_wizard_path(settings.gender, picks.year);
+            if path.exists() && !force {
+                return Err(format!(
+                    "{} already exists; use --force to replace it",
+                    path.display()
+                ));
+            }
+            for warning in import_warnings(&picks, &settings)
+                .map_err(|e| format!("{}: {e}", file.display()))?
+            {
+                eprintln!("warning: {}: {warning}", file.display());
+            }
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent).map_err(|e| format!("create dir failed: {e}"))?;
+            }
+            std::fs::write(&path, to_json(&picks)?)
+                .map_err(|e| format!("could not write {}: {e}", path.display()))?;
+            eprintln!(
+                "saved {} picks for {} to {}",
+                picks.selections.len(),
+                picks.user_id,
+                path.display()
+            );
+            Ok(())
+        }
+        Command::Leaderboard { format } => {
+            let tournament = load_tournament(&api, None).await?;
+            let mut app = App::new(settings);
+            app.on_bracket_loaded(tournament);
+            let Some(NetworkRequest::LoadCompareSources { sources }) = app.compare_load_request()
+            else {
+                return Err("no bracket loaded".to_string());
+            };
+            let (entries, errors) = load_compare_sources(sources).await;
+            app.on_compare_sources_loaded(entries, errors);
+
+            let compare = &app.state.compare;
+            for error in &compare.source_errors {
+                eprintln!("{error}");
+            }
+            let rows: Vec<LeaderboardRow> = compare
+                .rows
+                .iter()
+                .enumerate()
+                .map(|(i, row)| LeaderboardRow {
+                    rank: i + 1,
+                    user: &row.user_id,
+                    source: &row.source,
+                    points: row.points,
+                    max_points: row.max_points,
+                    correct: row.correct,
+                    total: row.total,
+                    round_points: row
+                        .round_points
+                        .iter()
+                        .map(|(round, points)| (round.abbrev(), *points))
+                        .collect(),
+                    unsealed: row.unsealed,
+                })
+                .collect();
+            print(format, &rows, |rows| leaderboard_text(rows))
+        }
+    }
+}
+
+/// The live bracket with today's scores merged in, or a past year's bracket.
+async fn load_tournament(api: &NcaaApi, year: Option<u16>) -> Result<Tournament, String> {
+    if let Some(year) = year {
+        return api
+            .fetch_ncaa_bracket(year)
+            .await
+            .map_err(|e| e.to_string());
+    }
+    let mut tournament = api.fetch_tournament().await.map_err(|e| e.to_string())?;
+    match api.fetch_scoreboard().await {
+        Ok(games) => tournament.merge_updates(games),
+        Err(e) => eprintln!("scores not refreshed: {e}"),
+    }
+    Ok(tournament)
+}
+
+fn read_picks(path: &std::path::Path) -> Result<BracketPicks, String> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("could not read {}: {e}", path.display()))?;
+    serde_json::from_str(&content)
+        .map_err(|e| format!("{}: invalid picks json: {e}", path.display()))
+}
+
+/// Check picks about to be imported as yours. Only the seal and signature
+/// are checked; tip-off times are for Compare. Unsealed picks are imported
+/// with a warning (refused when not allowed), and never stamped with our key
+/// and today's date.
+fn import_warnings(picks: &BracketPicks, settings: &AppSettings) -> Result<Vec<String>, String> {
+    let (_, note) = seal::check(picks.clone(), &Tournament::default(), &settings.seal_policy)
+        .map_err(|e| match picks.content_hash {
+            None => format!("{e}; set MMTUI_ALLOW_UNSEALED_PICKS=1 to import it anyway"),
+            Some(_) => e,
+        })?;
+    let mut warnings: Vec<String> = note.into_iter().collect();
+    if picks.user_id != settings.chat.username {
+        warnings.push(format!(
+            "these are {}'s picks, saved as yours ({})",
+            picks.user_id, settings.chat.username
+        ));
+    }
+    Ok(warnings)
+}
+
+fn to_json<T: Serialize>(value: &T) -> Result<String, String> {
+    serde_json::to_string_pretty(value).map_err(|e| e.to_string())
+}
+
+fn print<T: Serialize>(
+    format: Format,
+    value: &T,
+    text: impl Fn(&T) -> String,
+) -> Result<(), String> {
+    match format {
+        Format::Text => println!("{}", text(value)),
+        Format::Json => println!("{}", to_json(value)?),
+    }
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Text output
+// ---------------------------------------------------------------------------
+
+fn bracket_text(tournament: &Tournament) -> String {
+    let mut out = vec![format!("{} {}", tournament.year, tournament.name)];
+    for region in &tournament.regions {
+        out.push(String::new());
+        out.push(region.name.clone());
+        for round in &region.rounds {
+            out.push(format!("  {}", round.kind.label()));
+            out.extend(
+                round
+                    .games
+                    .iter()
+                    .map(|g| format!("    {}", game_line(g, tournament.gender))),
+            );
+        }
+    }
+    out.join("\n")
+}
+
+/// `(1) Duke 45 - 40 (2) North Carolina · 2H 10:23`
+fn game_line(game: &Game, gender: Gender) -> String {
+    let (top, bottom) = (seeded(&game.top), seeded(&game.bottom));
+    let (top_score, bottom_score) = game.score.unwrap_or_default();
+    match game.status {
+        GameStatus::InProgress => format!(
+            "{top} {top_score} - {bottom_score} {bottom} · {} {}",
+            gender.period_label(game.period.unwrap_or_default()),
+            game.clock.as_deref().unwrap_or_default()
+        ),
+        GameStatus::Final => format!("{top} {top_score} - {bottom_score} {bottom} · Final"),
+        GameStatus::Postponed => format!("{top} vs {bottom} · Postponed"),
+        GameStatus::Scheduled => format!(
+            "{top} vs {bottom} · {}",
+            game.start_time
+                .map(|t| t.format("%b %-d %H:%M UTC").to_string())
+                .unwrap_or_else(|| "time TBD".to_string())
+        ),
+    }
+}
+
+fn seeded(slot: &TeamSeed) -> String {
+    let name = slot
+        .team
+        .as_ref()
+        .map(|t| t.short_name.as_str())
+        .or(slot.placeholder.as_deref())
+        .unwrap_or("TBD");
+    if slot.seed > 0 {
+        format!("({}) {name}", slot.seed)
+    } else {
+        name.to_string()
+    }
+}
+
+fn game_text(detail: &GameDetail) -> String {
+    let name = |b: &ncaa_api::BoxScore| {
+        b.team
+            .as_ref()
+            .map(|t| t.short_name.clone())
+            .unwrap_or_else(|| "TBD".to_string())
+    };
+    let (away, home) = (name(&detail.away_box), name(&detail.home_box));
+    let (away_score, home_score) = detail
+        .plays
+        .last()
+        .map(|p| (p.away_score, p.home_score))
+        .unwrap_or_default();
+    let mut out = vec![format!("{away} {away_score} - {home_score} {home}")];
+
+    for (team, box_score) in [(&away, &detail.away_box), (&home, &detail.home_box)] {
+        out.push(String::new());
+        out.push(format!(
+            "{team:<24} {:>3} {:>3} {:>3} {:>6} {:>6} {:>4}",
+            "PTS", "REB", "AST", "FG", "3PT", "MIN"
+        ));
+        let mut players: Vec<_> = box_score.players.iter().collect();
+        players.sort_by(|a, b| b.points.cmp(&a.points));
+        out.extend(players.iter().map(|p| {
+            format!(
+                "{:<24} {:>3} {:>3} {:>3} {:>6} {:>6} {:>4}",
+                p.name, p.points, p.rebounds, p.assists, p.fg, p.fg3, p.minutes
+            )
+        }));
+    }
+
+    if !detail.plays.is_empty() {
+        out.push(String::new());
+        out.push("Recent plays".to_string());
+        let skip = detail.plays.len().saturating_sub(RECENT_PLAYS);
+        out.extend(detail.plays.iter().skip(skip).map(|p| {
+            format!(
+                "  {}H {:>5}  {:>3}-{:<3} {}",
+                p.period, p.clock, p.away_score, p.home_score, p.description
+            )
+        }));
+    }
+    out.join("\n")
+}
+
+#[derive(Debug, Serialize)]
+struct LeaderboardRow<'a> {
+    rank: usize,
+    user: &'a str,
+    source: &'a str,
+    points: u32,
+    max_points: u32,
+    correct: u32,
+    total: u32,
+    /// Points per round, keyed by round abbreviation (`R64`, `S16`, ...).
+    round_points: BTreeMap<&'static str, u32>,
+    unsealed: bool,
+}
+
+fn leaderboard_text(rows: &[LeaderboardRow]) -> String {
+    if rows.is_empty() {
+        return "no Compare entries found".to_string();
+    }
+    let mut out = vec![format!(
+        "{:>3}  {:<20} {:>6} {:>6} {:>9}",
+        "#", "User", "Points", "Max", "Correct"
+    )];
+    out.extend(rows.iter().map(|r| {
+        format!(
+            "{:>3}  {:<20} {:>6} {:>6} {:>9}{}",
+            r.rank,
+            r.user,
+            r.points,
+            r.max_points,
+            format!("{}/{}", r.correct, r.total),
+            if r.unsealed { "  unsealed" } else { "" }
+        )
+    }));
+    out.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ncaa_api::Team;
+
+    fn args(raw: &str) -> Vec<String> {
+        raw.split_whitespace().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn parses_subcommands_and_flags() {
+        assert_eq!(
+            Command::parse("bracket", &args("--year 2025 --format=json")),
+            Ok(Command::Bracket {
+                year: Some(2025),
+                format: Format::Json
+            })
+        );
+        assert_eq!(
+            Command::parse("game", &args("401745972")),
+            Ok(Command::Game {
+                id: "401745972".into(),
+                format: Format::Text
+            })
+        );
+        assert_eq!(
+            Command::parse("picks", &args("import alice.json --force")),
+            Ok(Command::PicksImport {
+                file: "alice.json".into(),
+                force: true
+            })
+        );
+        assert!(Command::parse("scores", &args("--year 2025")).is_err());
+        assert!(Command::parse("bracket", &args("--format yaml")).is_err());
+        assert!(Command::parse("game", &[]).is_err());
+        assert!(Command::parse("picks", &args("delete")).is_err());
+    }
+
+    #[test]
+    fn game_lines_read_like_the_scoreboard() {
+        let team = |seed, name: &str| TeamSeed {
+            seed,
+            team: Some(Team {
+                short_name: name.into(),
+                ..Default::default()
+            }),
+            placeholder: None,
+        };
+        let game = Game {
+            top: team(1, "Duke"),
+            bottom: team(16, "Mount St. Mary's"),
+            status: GameStatus::Final,
+            score: Some((93, 49)),
+            ..Default::default()
+        };
+        assert_eq!(
+            game_line(&game, Gender::Men),
+            "(1) Duke 93 - 49 (16) Mount St. Mary's · Final"
+        );
+
+        let tbd = Game {
+            bottom: TeamSeed {
+                placeholder: Some("Winner of #42".into()),
+                ..Default::default()
+            },
+            status: GameStatus::Scheduled,
+            score: None,
+            ..game.clone()
+        };
+        assert!(game_line(&tbd, Gender::Men).starts_with("(1) Duke vs Winner of #42 · "));
+
+        let live = Game {
+            status: GameStatus::InProgress,
+            period: Some(3),
+            clock: Some("4:12".into()),
+            score: Some((50, 41)),
+            ..game
+        };
+        assert!(game_line(&live, Gender::Men).ends_with("· OT 4:12"));
+        assert!(game_line(&live, Gender::Women).ends_with("· Q3 4:12"));
+    }
+
+    #[test]
+    fn imports_are_checked_not_sealed_for_you() {
+        let mut settings = AppSettings::default();
+        settings.chat.username = "bob".into();
+        let mut picks = BracketPicks {
+            user_id: "alice".into(),
+            year: 2025,
+            ..Default::default()
+        };
+        let warnings = import_warnings(&picks, &settings).unwrap();
+        assert_eq!(warnings[0], "unsealed; submission time unknown");
+        let mut strict = settings.clone();
+        strict.seal_policy.allow_unsealed = false;
+        let unsealed = import_warnings(&picks, &strict).unwrap_err();
+        assert!(unsealed.starts_with("unsealed"), "{unsealed}");
+
+        seal::seal(&mut picks, Utc::now(), None);
+        let warnings = import_warnings(&picks, &settings).unwrap();
+        assert_eq!(
+            warnings,
+            [
+                "unsigned; submission time unverified",
+                "these are alice's picks, saved as yours (bob)"
+            ]
+        );
+
+        picks.user_id = "bob".into();
+        assert!(import_warnings(&picks, &settings).is_err());
+    }
+}
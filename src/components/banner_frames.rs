@@ -1,4 +1,5 @@
 use ncaa_api::RoundKind;
+use std::str::FromStr;
 use tui::style::{Color, Modifier, Style};
 
 pub const FRAME_COUNT: usize = 4;
@@ -17,15 +18,39 @@ pub enum BannerColor {
 pub enum BannerTheme {
     #[default]
     Dark,
+    /// Darker accents for light terminal backgrounds.
+    Light,
 }
 
-pub fn resolve(color: BannerColor, _theme: BannerTheme) -> Style {
-    match color {
-        BannerColor::Primary => Style::default().fg(Color::Rgb(0, 122, 195)),
-        BannerColor::Secondary => Style::default().fg(Color::Rgb(255, 103, 31)),
-        BannerColor::Accent => Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
-        BannerColor::Shadow | BannerColor::Dim => Style::default().fg(Color::Indexed(240)),
-        BannerColor::Winner => Style::default().fg(Color::Green).add_modifier(Modifier::BOLD),
+impl FromStr for BannerTheme {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "dark" => Ok(BannerTheme::Dark),
+            "light" => Ok(BannerTheme::Light),
+            other => Err(format!("unknown theme {other:?} (expected dark or light)")),
+        }
+    }
+}
+
+pub fn resolve(color: BannerColor, theme: BannerTheme) -> Style {
+    match (color, theme) {
+        (BannerColor::Accent, BannerTheme::Light) => Style::default()
+            .fg(Color::Rgb(160, 100, 0))
+            .add_modifier(Modifier::BOLD),
+        (BannerColor::Shadow | BannerColor::Dim, BannerTheme::Light) => {
+            Style::default().fg(Color::Indexed(245))
+        }
+        (BannerColor::Primary, _) => Style::default().fg(Color::Rgb(0, 122, 195)),
+        (BannerColor::Secondary, _) => Style::default().fg(Color::Rgb(255, 103, 31)),
+        (BannerColor::Accent, _) => Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD),
+        (BannerColor::Shadow | BannerColor::Dim, _) => Style::default().fg(Color::Indexed(240)),
+        (BannerColor::Winner, _) => Style::default()
+            .fg(Color::Green)
+            .add_modifier(Modifier::BOLD),
     }
 }
 
@@ -1,10 +1,11 @@
-use ncaa_api::{Game, GameStatus, RoundKind, TeamSeed};
+use ncaa_api::{Game, GameStatus, Gender, RoundKind, TeamSeed};
 use tui::buffer::Buffer;
 use tui::layout::Rect;
 use tui::style::{Color, Modifier, Style};
 use tui::widgets::Widget;
 
 use crate::components::banner_frames::{BannerColor, BannerTheme, resolve};
+use crate::state::follow::Follows;
 
 // ---------------------------------------------------------------------------
 // Layout constants
@@ -31,6 +32,9 @@ pub const CONNECTOR_WIDTH: u16 = 3;
 /// Maximum game cell width in wider terminals.
 const CELL_W_FULL: u16 = 22;
 
+/// Followed teams stand out in every bracket and scoreboard view.
+pub const FOLLOWED: Color = Color::Cyan;
+
 // ---------------------------------------------------------------------------
 // GameCell — pre-computed position for one game
 // ---------------------------------------------------------------------------
@@ -70,17 +74,16 @@ pub struct BracketGrid {
     /// Index: [0=First, 1=Second, 2=Sweet16, 3=Elite8].
     pub round_cols: [u16; 4],
     /// Total grid width in terminal columns.
-    #[allow(dead_code)]
     pub total_width: u16,
     /// Total grid height in terminal rows (= REGION_HEIGHT = 31).
-    #[allow(dead_code)]
     pub total_height: u16,
     /// Cell width used (chosen by terminal_width at compute time).
     pub cell_width: u16,
     /// When true, depth 0 (R64) is on the right and depth 3 (E8) is on the left.
     pub mirrored: bool,
     /// When true, row positions are flipped vertically (R64 at bottom, E8 at top).
-    /// Used for bottom panes so the bracket points up toward the Final Four.
+    /// Used for bottom panes so the bracket points up toward the Final Four;
+    /// their region title moves below the grid to match.
     pub flipped: bool,
 }
 
@@ -131,7 +134,7 @@ impl BracketGrid {
     fn compute_inner(terminal_width: u16, mirrored: bool, flipped: bool) -> Self {
         let connector_total = CONNECTOR_WIDTH * 3;
         let per_col = terminal_width.saturating_sub(connector_total) / 4;
-        let cell_width: u16 = per_col.max(1).min(CELL_W_FULL);
+        let cell_width: u16 = per_col.clamp(1, CELL_W_FULL);
         let stride = cell_width + CONNECTOR_WIDTH;
         let round_cols = if mirrored {
             [stride * 3, stride * 2, stride, 0u16]
@@ -194,8 +197,10 @@ pub struct BracketView<'a> {
     pub scroll_offset: u16,
     /// Color theme.
     pub theme: BannerTheme,
-    /// Mirror the bracket so depth 0 (R64) is on the right (for right-side panes).
-    pub mirrored: bool,
+    /// Halves or quarters, for the live period label.
+    pub gender: Gender,
+    /// Teams drawn in [`FOLLOWED`] color.
+    pub follows: &'a Follows,
 }
 
 impl<'a> Widget for BracketView<'a> {
@@ -208,9 +213,18 @@ impl<'a> Widget for BracketView<'a> {
         for cell in &self.grid.cells {
             let depth = round_to_depth(cell.round);
             let game = self.rounds[depth].get(cell.game_idx);
-            let selected =
-                depth == self.selected_depth && cell.game_idx == self.selected_game;
-            draw_game_cell(game, cell, selected, area, self.scroll_offset, self.theme, buf);
+            let selected = depth == self.selected_depth && cell.game_idx == self.selected_game;
+            draw_game_cell(
+                game,
+                cell,
+                selected,
+                area,
+                self.scroll_offset,
+                self.theme,
+                self.gender,
+                self.follows,
+                buf,
+            );
         }
 
         // Pass 2: draw box-drawing connectors between adjacent rounds.
@@ -220,7 +234,7 @@ impl<'a> Widget for BracketView<'a> {
             let parent_cells = self.grid.cells_for_depth(depth + 1);
             // Normal: connector zone is to the right of the child column.
             // Mirrored: connector zone is to the left of the child column.
-            let conn_x_base = if self.mirrored {
+            let conn_x_base = if self.grid.mirrored {
                 area.x + self.grid.round_cols[depth].saturating_sub(CONNECTOR_WIDTH)
             } else {
                 area.x + self.grid.round_cols[depth] + self.grid.cell_width
@@ -239,7 +253,7 @@ impl<'a> Widget for BracketView<'a> {
                     area,
                     self.scroll_offset,
                     self.theme,
-                    self.mirrored,
+                    self.grid.mirrored,
                     buf,
                 );
             }
@@ -268,6 +282,7 @@ pub struct FinalFourView<'a> {
     /// 0 = semi_left, 1 = semi_right, 2 = championship.
     pub selected_idx: usize,
     pub theme: BannerTheme,
+    pub gender: Gender,
 }
 
 impl<'a> Widget for FinalFourView<'a> {
@@ -310,9 +325,39 @@ impl<'a> Widget for FinalFourView<'a> {
         let center_y = area.y + 2; // leave row 0 for title, row 1 blank
 
         // Draw game cells
-        draw_ff_game_at(self.semi_left, col_left, center_y, cell_w, self.selected_idx == 0, self.theme, buf, area);
-        draw_ff_game_at(self.championship, col_mid, center_y, cell_w, self.selected_idx == 2, self.theme, buf, area);
-        draw_ff_game_at(self.semi_right, col_right, center_y, cell_w, self.selected_idx == 1, self.theme, buf, area);
+        draw_ff_game_at(
+            self.semi_left,
+            col_left,
+            center_y,
+            cell_w,
+            self.selected_idx == 0,
+            self.theme,
+            self.gender,
+            buf,
+            area,
+        );
+        draw_ff_game_at(
+            self.championship,
+            col_mid,
+            center_y,
+            cell_w,
+            self.selected_idx == 2,
+            self.theme,
+            self.gender,
+            buf,
+            area,
+        );
+        draw_ff_game_at(
+            self.semi_right,
+            col_right,
+            center_y,
+            cell_w,
+            self.selected_idx == 1,
+            self.theme,
+            self.gender,
+            buf,
+            area,
+        );
 
         // Horizontal connectors at center_y (score row):
         //   [semi_left right edge] ──── [champ left edge]
@@ -333,7 +378,7 @@ impl<'a> Widget for FinalFourView<'a> {
         }
 
         // T-junction marks at championship borders on the score row
-        if col_mid > 0 && col_mid - 1 >= area.x && col_mid - 1 < limit_x {
+        if col_mid > area.x && col_mid - 1 < limit_x {
             put_char(buf, col_mid - 1, center_y, '┤', dim);
         }
         let rj = col_mid + cell_w;
@@ -361,8 +406,10 @@ fn render_ff_vertical(view: &FinalFourView, area: Rect, buf: &mut Buffer) {
         y += 1;
 
         for slot in 0u8..3 {
-            if y >= area.y + area.height { break; }
-            let content = format_game_row(game, slot, cell_w, view.theme);
+            if y >= area.y + area.height {
+                break;
+            }
+            let content = format_game_row(game, slot, cell_w, view.theme, view.gender);
             let style = if selected {
                 Style::default().fg(Color::White).add_modifier(Modifier::BOLD)
             } else {
@@ -376,6 +423,7 @@ fn render_ff_vertical(view: &FinalFourView, area: Rect, buf: &mut Buffer) {
 }
 
 /// Draw a 3-row game cell at absolute screen coordinates (no scroll).
+#[allow(clippy::too_many_arguments)]
 fn draw_ff_game_at(
     game: Option<&Game>,
     x: u16,
@@ -383,6 +431,7 @@ fn draw_ff_game_at(
     cell_w: u16,
     selected: bool,
     theme: BannerTheme,
+    gender: Gender,
     buf: &mut Buffer,
     area: Rect,
 ) {
@@ -403,7 +452,7 @@ fn draw_ff_game_at(
         if y < area.y || y >= area.y + area.height { continue; }
         if x >= limit_x { continue; }
 
-        let content = format_game_row(game, slot_idx, cell_w as usize, theme);
+        let content = format_game_row(game, slot_idx, cell_w as usize, theme, gender);
         let text: String = content.chars().take(avail).collect();
 
         let style = match slot_idx {
@@ -426,6 +475,222 @@ fn draw_ff_game_at(
     }
 }
 
+// ---------------------------------------------------------------------------
+// FullBracketView widget
+// ---------------------------------------------------------------------------
+
+/// Columns between a regional pane and the Final Four column.
+const FULL_GAP: u16 = 2;
+
+/// Width of the Final Four column between the left and right regions.
+const FULL_CENTER_W: u16 = CELL_W_FULL + 4;
+
+/// Rows of the stacked Final Four: title, then label + 3 rows + spacer per game.
+const FULL_CENTER_H: u16 = 1 + 3 * (GAME_HEIGHT + 2);
+
+/// A region's name and its games per bracket depth, as in [`BracketView::rounds`].
+pub type RegionRounds<'a> = (&'a str, [&'a [Game]; 4]);
+
+/// The highlighted game in the full bracket.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FullBracketFocus {
+    /// Pane index (0–3), bracket depth (0–3) and game index within the depth.
+    Region {
+        pane: usize,
+        depth: usize,
+        game: usize,
+    },
+    /// 0 = semi_left, 1 = semi_right, 2 = championship.
+    FinalFour(usize),
+    None,
+}
+
+/// Renders the whole tournament — four regions at full cell width, connector
+/// lines and the Final Four in the middle:
+///
+/// ```text
+///  East  R64 → E8 ┐              ┌ E8 ← R64  West
+///                 ├ FINAL FOUR ──┤
+///  South R64 → E8 ┘              └ E8 ← R64  Midwest
+/// ```
+///
+/// The tree is laid out on an off-screen canvas and the window that fits
+/// `area` is copied in, scrolled so the focused game stays in view.
+pub struct FullBracketView<'a> {
+    /// Region name and rounds per pane: top-left, top-right, bottom-left, bottom-right.
+    pub regions: [Option<RegionRounds<'a>>; 4],
+    pub semi_left: Option<&'a Game>,
+    pub semi_right: Option<&'a Game>,
+    pub championship: Option<&'a Game>,
+    pub focus: FullBracketFocus,
+    pub theme: BannerTheme,
+    pub gender: Gender,
+    pub follows: &'a Follows,
+}
+
+/// Where each part of the full bracket sits on the canvas.
+struct FullBracketLayout {
+    grids: [BracketGrid; 4],
+    /// Top-left corner of each pane; the region title takes its outside row.
+    panes: [(u16, u16); 4],
+    center: Rect,
+    size: (u16, u16),
+}
+
+impl FullBracketLayout {
+    fn new() -> Self {
+        let grids = [
+            BracketGrid::compute(u16::MAX),
+            BracketGrid::compute_mirrored(u16::MAX),
+            BracketGrid::compute_flipped(u16::MAX),
+            BracketGrid::compute_flipped_mirrored(u16::MAX),
+        ];
+        let pane_w = grids[0].total_width;
+        let pane_h = grids[0].total_height + 1;
+        let right_x = pane_w + FULL_GAP * 2 + FULL_CENTER_W;
+        let bottom_y = pane_h + 1;
+        let size = (right_x + pane_w, bottom_y + pane_h);
+        Self {
+            grids,
+            panes: [(0, 0), (right_x, 0), (0, bottom_y), (right_x, bottom_y)],
+            center: Rect::new(
+                pane_w + FULL_GAP,
+                (size.1 - FULL_CENTER_H) / 2,
+                FULL_CENTER_W,
+                FULL_CENTER_H,
+            ),
+            size,
+        }
+    }
+
+    /// Canvas origin of a pane's grid: under the title, or above it when the
+    /// pane is flipped.
+    fn grid_origin(&self, pane: usize) -> (u16, u16) {
+        let (x, y) = self.panes[pane];
+        (x, if self.grids[pane].flipped { y } else { y + 1 })
+    }
+
+    /// Canvas row of a pane's region title.
+    fn title_row(&self, pane: usize) -> u16 {
+        let grid = &self.grids[pane];
+        let y = self.panes[pane].1;
+        if grid.flipped {
+            y + grid.total_height
+        } else {
+            y
+        }
+    }
+
+    /// Canvas rect of the focused game.
+    fn focus_rect(&self, focus: FullBracketFocus) -> Option<Rect> {
+        match focus {
+            FullBracketFocus::Region { pane, depth, game } => {
+                let cell = self
+                    .grids
+                    .get(pane)?
+                    .cells_for_depth(depth.min(3))
+                    .get(game)?;
+                let (x, y) = self.grid_origin(pane);
+                // A game's box starts a row above its center.
+                Some(Rect::new(
+                    x + cell.col,
+                    y + cell.center_row - 1,
+                    cell.cell_width,
+                    GAME_HEIGHT,
+                ))
+            }
+            FullBracketFocus::FinalFour(_) => Some(self.center),
+            FullBracketFocus::None => None,
+        }
+    }
+}
+
+/// Scroll offsets that center `focus` in a `view`-sized window, clamped to the canvas.
+fn scroll_to(canvas: (u16, u16), view: (u16, u16), focus: Option<Rect>) -> (u16, u16) {
+    let Some(focus) = focus else {
+        return (0, 0);
+    };
+    let axis = |canvas: u16, view: u16, start: u16, len: u16| {
+        (start + len / 2)
+            .saturating_sub(view / 2)
+            .min(canvas.saturating_sub(view))
+    };
+    (
+        axis(canvas.0, view.0, focus.x, focus.width),
+        axis(canvas.1, view.1, focus.y, focus.height),
+    )
+}
+
+impl<'a> Widget for FullBracketView<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let layout = FullBracketLayout::new();
+        let mut canvas = Buffer::empty(Rect::new(0, 0, layout.size.0, layout.size.1));
+        let accent = resolve(BannerColor::Accent, self.theme);
+
+        for (pane, region) in self.regions.iter().enumerate() {
+            let Some((name, rounds)) = region else {
+                continue;
+            };
+            let grid = &layout.grids[pane];
+            let (x, y) = layout.grid_origin(pane);
+            // Titles sit at the first-round column, on the outside edge.
+            canvas.set_string(x + grid.round_cols[0], layout.title_row(pane), name, accent);
+
+            let (selected_depth, selected_game) = match self.focus {
+                FullBracketFocus::Region {
+                    pane: p,
+                    depth,
+                    game,
+                } if p == pane => (depth, game),
+                _ => (usize::MAX, 0),
+            };
+            BracketView {
+                rounds: *rounds,
+                grid,
+                selected_depth,
+                selected_game,
+                scroll_offset: 0,
+                theme: self.theme,
+                gender: self.gender,
+                follows: self.follows,
+            }
+            .render(
+                Rect::new(x, y, grid.total_width, grid.total_height),
+                &mut canvas,
+            );
+        }
+
+        FinalFourView {
+            semi_left: self.semi_left,
+            semi_right: self.semi_right,
+            championship: self.championship,
+            selected_idx: match self.focus {
+                FullBracketFocus::FinalFour(idx) => idx,
+                _ => usize::MAX,
+            },
+            theme: self.theme,
+            gender: self.gender,
+        }
+        .render(layout.center, &mut canvas);
+
+        let (sx, sy) = scroll_to(
+            layout.size,
+            (area.width, area.height),
+            layout.focus_rect(self.focus),
+        );
+        for dy in 0..area.height.min(layout.size.1 - sy) {
+            for dx in 0..area.width.min(layout.size.0 - sx) {
+                if let (Some(src), Some(dst)) = (
+                    canvas.cell((sx + dx, sy + dy)),
+                    buf.cell_mut((area.x + dx, area.y + dy)),
+                ) {
+                    *dst = src.clone();
+                }
+            }
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Shared drawing helpers
 // ---------------------------------------------------------------------------
@@ -454,6 +719,7 @@ fn screen_y(bracket_row: u16, scroll: u16, area: Rect) -> Option<u16> {
 }
 
 /// Draw one game cell (3 rows) into the buffer, with scroll + clip handling.
+#[allow(clippy::too_many_arguments)]
 fn draw_game_cell(
     game: Option<&Game>,
     cell: &GameCell,
@@ -461,6 +727,8 @@ fn draw_game_cell(
     area: Rect,
     scroll: u16,
     theme: BannerTheme,
+    gender: Gender,
+    follows: &Follows,
     buf: &mut Buffer,
 ) {
     let primary = resolve(BannerColor::Primary, theme);
@@ -491,7 +759,7 @@ fn draw_game_cell(
             continue;
         };
 
-        let content = format_game_row(game, slot_idx, cell.cell_width as usize, theme);
+        let content = format_game_row(game, slot_idx, cell.cell_width as usize, theme, gender);
         let text: String = content.chars().take(avail_w).collect();
 
         let style = match slot_idx {
@@ -500,16 +768,17 @@ fn draw_game_cell(
                 _ => dim,
             },
             _ => {
-                let is_winner = game.map(|g| {
-                    let ts = if slot_idx == 0 { &g.top } else { &g.bottom };
-                    ts.team
-                        .as_ref()
-                        .and_then(|t| g.winner_id.as_deref().map(|wid| t.id == wid))
-                        .unwrap_or(false)
-                }).unwrap_or(false);
+                let team =
+                    game.and_then(|g| if slot_idx == 0 { &g.top } else { &g.bottom }.team.as_ref());
+                let is_winner = game
+                    .zip(team)
+                    .and_then(|(g, t)| g.winner_id.as_deref().map(|wid| t.id == wid))
+                    .unwrap_or(false);
 
                 if is_winner {
                     winner_style.add_modifier(Modifier::BOLD)
+                } else if team.is_some_and(|t| follows.contains(t)) {
+                    base_style.fg(FOLLOWED)
                 } else {
                     base_style
                 }
@@ -522,14 +791,20 @@ fn draw_game_cell(
 
 /// Format a single game cell row.
 /// `slot_idx`: 0 = top-team line, 1 = score/status line, 2 = bottom-team line.
-fn format_game_row(game: Option<&Game>, slot_idx: u8, width: usize, theme: BannerTheme) -> String {
+fn format_game_row(
+    game: Option<&Game>,
+    slot_idx: u8,
+    width: usize,
+    theme: BannerTheme,
+    gender: Gender,
+) -> String {
     let _ = theme; // reserved for future per-theme score color variations
     match game {
         None => " ".repeat(width),
         Some(g) => match slot_idx {
             0 => format_team_line(&g.top, g.score.map(|(s, _)| s), width),
             2 => format_team_line(&g.bottom, g.score.map(|(_, s)| s), width),
-            _ => format_status_line(g, width),
+            _ => format_status_line(g, width, gender),
         },
     }
 }
@@ -559,7 +834,7 @@ fn format_team_line(ts: &TeamSeed, score: Option<u16>, width: usize) -> String {
 }
 
 /// Format the center score/status row.
-fn format_status_line(game: &Game, width: usize) -> String {
+fn format_status_line(game: &Game, width: usize, gender: Gender) -> String {
     let raw = match &game.status {
         GameStatus::Scheduled => game
             .start_time
@@ -568,7 +843,7 @@ fn format_status_line(game: &Game, width: usize) -> String {
         GameStatus::InProgress => {
             let period = game
                 .period
-                .map(|p| format!("{}H", p))
+                .map(|p| gender.period_label(p))
                 .unwrap_or_default();
             let clock = game.clock.as_deref().unwrap_or("");
             format!(" {} {}", period, clock)
@@ -593,6 +868,7 @@ fn format_status_line(game: &Game, width: usize) -> String {
 ///               │         (col_b='│')
 ///  child_bot  ──┘         (col_a='─'  col_b='┘')
 /// ```
+#[allow(clippy::too_many_arguments)]
 fn draw_connector(
     r_top: u16,
     r_mid: u16,
@@ -778,6 +1054,79 @@ mod tests {
         assert_eq!(grid.cell_width, CELL_W_FULL);
     }
 
+    #[test]
+    fn test_scroll_to_centers_focus_within_canvas() {
+        let focus = Rect::new(100, 30, 22, 3);
+        assert_eq!(scroll_to((224, 65), (80, 20), Some(focus)), (71, 21));
+        // Clamped at both ends of the canvas.
+        assert_eq!(
+            scroll_to((224, 65), (80, 20), Some(Rect::new(0, 0, 22, 3))),
+            (0, 0)
+        );
+        assert_eq!(
+            scroll_to((224, 65), (80, 20), Some(Rect::new(210, 60, 22, 3))),
+            (144, 45)
+        );
+        assert_eq!(scroll_to((224, 65), (300, 90), Some(focus)), (0, 0));
+    }
+
+    #[test]
+    fn test_flipped_panes_put_their_title_below_the_grid() {
+        let layout = FullBracketLayout::new();
+        assert_eq!(layout.title_row(0), 0);
+        assert_eq!(layout.title_row(2), layout.size.1 - 1);
+        let first = layout.focus_rect(FullBracketFocus::Region {
+            pane: 2,
+            depth: 0,
+            game: 0,
+        });
+        assert_eq!(first.unwrap().bottom(), layout.title_row(2));
+    }
+
+    #[test]
+    fn test_full_bracket_scrolls_to_focused_region() {
+        use ncaa_api::{Team, TeamSeed};
+        let elite8 = [Game {
+            top: TeamSeed {
+                seed: 1,
+                team: Some(Team {
+                    id: "248".into(),
+                    short_name: "Houston".into(),
+                    ..Default::default()
+                }),
+                placeholder: None,
+            },
+            ..Default::default()
+        }];
+        let follows = Follows::default();
+        let view = FullBracketView {
+            regions: [
+                Some(("East", [&[], &[], &[], &[]])),
+                Some(("West", [&[], &[], &[], &[]])),
+                Some(("South", [&[], &[], &[], &[]])),
+                Some(("Midwest", [&[], &[], &[], &elite8])),
+            ],
+            semi_left: None,
+            semi_right: None,
+            championship: None,
+            focus: FullBracketFocus::Region {
+                pane: 3,
+                depth: 3,
+                game: 0,
+            },
+            theme: BannerTheme::default(),
+            gender: Gender::Men,
+            follows: &follows,
+        };
+        let area = Rect::new(0, 0, 60, 12);
+        let mut buf = Buffer::empty(area);
+        view.render(area, &mut buf);
+
+        let text: String = buf.content().iter().map(|c| c.symbol()).collect();
+        assert!(text.contains("Houston"));
+        assert!(!text.contains("East"));
+    }
+
     #[test]
     fn test_format_team_line_width() {
         use ncaa_api::{Team, TeamSeed};
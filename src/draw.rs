@@ -5,13 +5,19 @@ use tui::text::{Line, Span};
 use tui::widgets::{Block, BorderType, Borders, Clear, Paragraph, Tabs};
 use tui::{Frame, Terminal};
 
-use crate::app::{App, MenuItem};
+use crate::app::{App, MenuItem, pick_wizard_path};
 use crate::components::banner::AnimatedBanner;
-use crate::components::banner_frames::BannerTheme;
-use crate::components::bracket::FinalFourView;
+use crate::components::bracket::{
+    FOLLOWED, FinalFourView, FullBracketFocus, FullBracketView, round_to_depth,
+};
+use crate::state::app_state::{Delivery, DetailTab, FeedView, PlayerSort};
+use crate::state::follow::Follows;
 use crate::state::network::{ERROR_CHAR, LoadingState};
+use crate::state::slash;
 use crate::ui::layout::LayoutAreas;
-use ncaa_api::{Game, GameStatus, Round, RoundKind, TeamSeed};
+use ncaa_api::{
+    BoxScore, Game, GameDetail, GameStatus, PlayerLine, Round, RoundKind, TeamSeed, made_attempted,
+};
 
 static TABS: &[&str; 7] = &[
     "Bracket",
@@ -58,7 +64,7 @@ where
                 MenuItem::Help => draw_placeholder(
                     f,
                     layout.main,
-                    "Help: q=quit  1=Bracket 2=Scoreboard 3=GameDetail 4=Chat 5=Wizard 6=Compare 7=PrizePool  ←/→=round ↑/↓=game Enter=select r=region",
+                    "Help: q=quit  1=Bracket 2=Scoreboard 3=GameDetail 4=Chat 5=Wizard 6=Compare 7=PrizePool  ←/→=round ↑/↓=game Enter=select r=region b=full bracket s/a=live feed view/team",
                 ),
             }
 
@@ -94,7 +100,7 @@ fn draw_intro(f: &mut Frame, area: Rect, app: &App) {
         AnimatedBanner {
             frame: app.state.animation.frame,
             tick: app.state.animation.tick,
-            theme: BannerTheme::Dark,
+            theme: app.settings.theme,
             view_round: app.state.bracket.view_round,
             current_round: app.state.bracket.current_round,
         },
@@ -147,12 +153,15 @@ fn draw_tabs(f: &mut Frame, tab_bar: [Rect; 2], app: &App) {
 }
 
 fn draw_bracket(f: &mut Frame, area: Rect, app: &App) {
-    let block = default_border(Color::White).title(" Bracket ");
+    let block =
+        default_border(Color::White).title(format!(" {} Bracket ", app.state.gender.label()));
     let inner = block.inner(area);
     f.render_widget(block, area);
 
     let Some(tournament) = app.state.bracket.tournament.as_ref() else {
-        let msg = if let Some(err) = app.state.last_error.as_deref() {
+        let msg = if let Some(gender) = app.state.pending_gender {
+            format!("Switching to the {} tournament...", gender.label())
+        } else if let Some(err) = app.state.last_error.as_deref() {
             format!("Bracket load failed:\n{err}")
         } else {
             "Loading bracket data...".to_string()
@@ -192,11 +201,18 @@ fn draw_bracket(f: &mut Frame, area: Rect, app: &App) {
     );
     f.render_widget(Paragraph::new(header_text), header);
     f.render_widget(
-        Paragraph::new("Keys: h/l=round  j/k=move  r=region  Enter=details  ?=help  q=quit")
-            .style(Style::default().fg(Color::DarkGray)),
+        Paragraph::new(
+            "Keys: h/l=round  j/k=move  r=region  b=full bracket  Enter=details  t=men/women  ?=help  q=quit",
+        )
+        .style(Style::default().fg(Color::DarkGray)),
         key_legend,
     );
 
+    if app.state.bracket.full_view {
+        draw_full_bracket_view(f, content, tournament, app);
+        return;
+    }
+
     let mut bracket_area = content;
     let mut live_feed_area: Option<Rect> = None;
     if content.width >= 90 {
@@ -243,7 +259,14 @@ fn draw_first_four_all_view(f: &mut Frame, area: Rect, tournament: &ncaa_api::To
     let inner = block.inner(area);
     f.render_widget(block, area);
 
-    draw_round_compact(f, inner, &all_games, true, app.state.bracket.selected_game);
+    draw_round_compact(
+        f,
+        inner,
+        &all_games,
+        true,
+        app.state.bracket.selected_game,
+        &app.settings.follow.teams,
+    );
 }
 
 fn draw_all_regions_view(f: &mut Frame, area: Rect, tournament: &ncaa_api::Tournament, app: &App) {
@@ -307,6 +330,7 @@ fn draw_all_regions_view(f: &mut Frame, area: Rect, tournament: &ncaa_api::Tourn
                 round_games(region.rounds.as_slice(), app.state.bracket.view_round).unwrap_or(&[]),
                 idx == app.state.bracket.selected_region,
                 app.state.bracket.selected_game,
+                &app.settings.follow.teams,
             );
         }
     }
@@ -322,6 +346,7 @@ fn draw_round_compact(
     games: &[Game],
     selected_region: bool,
     selected_game: usize,
+    follows: &Follows,
 ) {
     if area.height == 0 || area.width == 0 {
         return;
@@ -336,7 +361,7 @@ fn draw_round_compact(
         return;
     }
 
-    let entries = build_round_entries(games);
+    let entries = build_round_entries(games, follows);
     let rows = area.height as usize;
     let cols = entries.len().max(1).div_ceil(rows.max(1));
     let col_width = (area.width as usize / cols.max(1)).max(1);
@@ -344,7 +369,7 @@ fn draw_round_compact(
 
     f.render_widget(Clear, area);
 
-    for (idx, mut entry) in entries.into_iter().enumerate() {
+    for (idx, (mut entry, followed)) in entries.into_iter().enumerate() {
         let row = idx % rows.max(1);
         let col = idx / rows.max(1);
         if col >= cols {
@@ -363,11 +388,20 @@ fn draw_round_compact(
         let line = format!("{marker} {clipped}");
         let x = area.x + (col * col_width) as u16;
         let y = area.y + row as u16;
-        f.render_widget(Paragraph::new(line), Rect::new(x, y, col_width as u16, 1));
+        let style = if followed {
+            Style::default().fg(FOLLOWED)
+        } else {
+            Style::default()
+        };
+        f.render_widget(
+            Paragraph::new(line).style(style),
+            Rect::new(x, y, col_width as u16, 1),
+        );
     }
 }
 
-fn build_round_entries(games: &[Game]) -> Vec<String> {
+/// One line per team, flagged when the team is followed.
+fn build_round_entries(games: &[Game], follows: &Follows) -> Vec<(String, bool)> {
     let mut entries = Vec::with_capacity(games.len() * 2);
     for g in games {
         let status = match g.status {
@@ -376,13 +410,20 @@ fn build_round_entries(games: &[Game]) -> Vec<String> {
             GameStatus::Final => "FNL",
             GameStatus::Postponed => "PPD",
         };
-        entries.push(format!(
-            "{} {status}",
-            compact_team(g.top.seed, &g.top, g.score.map(|(s, _)| s), false)
+        let followed = |slot: &TeamSeed| slot.team.as_ref().is_some_and(|t| follows.contains(t));
+        entries.push((
+            format!(
+                "{} {status}",
+                compact_team(g.top.seed, &g.top, g.score.map(|(s, _)| s), false)
+            ),
+            followed(&g.top),
         ));
-        entries.push(format!(
-            "{} {status}",
-            compact_team(g.bottom.seed, &g.bottom, g.score.map(|(_, s)| s), false)
+        entries.push((
+            format!(
+                "{} {status}",
+                compact_team(g.bottom.seed, &g.bottom, g.score.map(|(_, s)| s), false)
+            ),
+            followed(&g.bottom),
         ));
     }
     entries
@@ -499,7 +540,60 @@ fn draw_final_four_view(f: &mut Frame, area: Rect, tournament: &ncaa_api::Tourna
             semi_right: semifinals.and_then(|g| g.get(1)),
             championship: championship.and_then(|g| g.first()),
             selected_idx,
-            theme: BannerTheme::Dark,
+            theme: app.settings.theme,
+            gender: tournament.gender,
+        },
+        area,
+    );
+}
+
+fn draw_full_bracket_view(f: &mut Frame, area: Rect, tournament: &ncaa_api::Tournament, app: &App) {
+    let regions: Vec<_> = tournament
+        .regions
+        .iter()
+        .filter(|r| r.name != "National")
+        .collect();
+    let panes = std::array::from_fn(|idx| {
+        regions.get(idx).map(|r| {
+            let rounds = [
+                RoundKind::First,
+                RoundKind::Second,
+                RoundKind::Sweet16,
+                RoundKind::Elite8,
+            ]
+            .map(|kind| round_games(r.rounds.as_slice(), kind).unwrap_or(&[]));
+            (r.name.as_str(), rounds)
+        })
+    });
+
+    let national = tournament.regions.iter().find(|r| r.name == "National");
+    let semifinals = national.and_then(|r| round_games(r.rounds.as_slice(), RoundKind::FinalFour));
+    let championship =
+        national.and_then(|r| round_games(r.rounds.as_slice(), RoundKind::Championship));
+
+    let bracket = &app.state.bracket;
+    let focus = match bracket.view_round {
+        RoundKind::FirstFour => FullBracketFocus::None,
+        RoundKind::Championship => FullBracketFocus::FinalFour(2),
+        RoundKind::FinalFour => FullBracketFocus::FinalFour(bracket.selected_game.min(1)),
+        round => FullBracketFocus::Region {
+            pane: bracket.selected_region,
+            depth: round_to_depth(round),
+            game: bracket.selected_game,
+        },
+    };
+
+    f.render_widget(Clear, area);
+    f.render_widget(
+        FullBracketView {
+            regions: panes,
+            semi_left: semifinals.and_then(|g| g.first()),
+            semi_right: semifinals.and_then(|g| g.get(1)),
+            championship: championship.and_then(|g| g.first()),
+            focus,
+            theme: app.settings.theme,
+            gender: tournament.gender,
+            follows: &app.settings.follow.teams,
         },
         area,
     );
@@ -524,7 +618,9 @@ fn draw_championship_view(f: &mut Frame, area: Rect, tournament: &ncaa_api::Tour
         GameStatus::Final => "FINAL".to_string(),
         GameStatus::InProgress => format!(
             "LIVE {} {}",
-            game.period.unwrap_or_default(),
+            tournament
+                .gender
+                .period_label(game.period.unwrap_or_default()),
             game.clock.clone().unwrap_or_default()
         ),
         GameStatus::Postponed => "PPD".to_string(),
@@ -543,7 +639,8 @@ fn draw_championship_view(f: &mut Frame, area: Rect, tournament: &ncaa_api::Tour
 }
 
 fn draw_scoreboard(f: &mut Frame, area: Rect, app: &App) {
-    let block = default_border(Color::White).title(" Scoreboard ");
+    let block =
+        default_border(Color::White).title(format!(" {} Scoreboard ", app.state.gender.label()));
     let inner = block.inner(area);
     f.render_widget(block, area);
 
@@ -578,15 +675,27 @@ fn draw_scoreboard(f: &mut Frame, area: Rect, app: &App) {
     }
 
     let mut lines = Vec::with_capacity(games.len() + 3);
-    lines.push(format!("{} | {}", region.name, app.state.bracket.view_round.label()));
-    lines.push("j/k to move, Enter for detail, r to cycle region".to_string());
-    lines.push(String::new());
+    lines.push(Line::from(format!(
+        "{} | {}",
+        region.name,
+        app.state.bracket.view_round.label()
+    )));
+    lines.push(Line::from(
+        "j/k to move, Enter for detail, r to cycle region",
+    ));
+    lines.push(Line::from(""));
 
     for (idx, game) in games.iter().enumerate() {
         let marker = if idx == app.state.bracket.selected_game { ">" } else { " " };
         let status = match game.status {
             GameStatus::Final => "FINAL".to_string(),
-            GameStatus::InProgress => format!("LIVE {} {}", game.period.unwrap_or_default(), game.clock.clone().unwrap_or_default()),
+            GameStatus::InProgress => format!(
+                "LIVE {} {}",
+                app.state
+                    .gender
+                    .period_label(game.period.unwrap_or_default()),
+                game.clock.clone().unwrap_or_default()
+            ),
             GameStatus::Postponed => "PPD".to_string(),
             GameStatus::Scheduled => game
                 .start_time
@@ -596,10 +705,18 @@ fn draw_scoreboard(f: &mut Frame, area: Rect, app: &App) {
 
         let top = format_seed_team(&game.top, game.score.map(|(s, _)| s));
         let bot = format_seed_team(&game.bottom, game.score.map(|(_, s)| s));
-        lines.push(format!("{marker} {top} vs {bot}  [{status}]"));
+        let line = format!("{marker} {top} vs {bot}  [{status}]");
+        if app.settings.follow.teams.in_game(game) {
+            lines.push(Line::styled(
+                format!("{line} ★"),
+                Style::default().fg(FOLLOWED),
+            ));
+        } else {
+            lines.push(Line::from(line));
+        }
     }
 
-    f.render_widget(Paragraph::new(lines.join("\n")), inner);
+    f.render_widget(Paragraph::new(lines), inner);
 }
 
 fn draw_game_detail(f: &mut Frame, area: Rect, app: &App) {
@@ -617,35 +734,232 @@ fn draw_game_detail(f: &mut Frame, area: Rect, app: &App) {
         return;
     };
 
-    let mut lines = Vec::new();
-    lines.push(format!("Game ID: {}", detail.game_id));
-    lines.push(String::new());
+    let [header, tabs, content] = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Length(2),
+        Constraint::Fill(1),
+    ])
+    .areas(inner);
 
-    let away_name = detail
-        .away_box
-        .team
-        .as_ref()
-        .map(|t| t.short_name.as_str())
-        .unwrap_or("Away");
-    let home_name = detail
-        .home_box
+    let (top, bottom) = (detail.top_box(), detail.bottom_box());
+    f.render_widget(
+        Paragraph::new(format!(
+            "{} {} - {} {}  |  Game ID: {}",
+            box_team_name(top),
+            top.totals.points,
+            bottom.totals.points,
+            box_team_name(bottom),
+            detail.game_id
+        )),
+        header,
+    );
+
+    let state = &app.state.game_detail;
+    let mut spans = Vec::new();
+    for tab in DetailTab::ALL {
+        if !spans.is_empty() {
+            spans.push(Span::styled(" | ", Style::default().fg(Color::DarkGray)));
+        }
+        let style = if tab == state.tab {
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::DarkGray)
+        };
+        spans.push(Span::styled(tab.label(), style));
+    }
+    let mut hint = "   h/l=tab  j/k=scroll".to_string();
+    if state.tab == DetailTab::BoxScore {
+        hint.push_str(&format!("  s/S=sort ({})", state.sort.label()));
+    }
+    spans.push(Span::styled(hint, Style::default().fg(Color::DarkGray)));
+    f.render_widget(Paragraph::new(Line::from(spans)), tabs);
+
+    let lines = match state.tab {
+        DetailTab::BoxScore => box_score_lines(detail, state.sort),
+        DetailTab::PlayByPlay if detail.plays.is_empty() => vec![Line::styled(
+            "No plays yet",
+            Style::default().fg(Color::DarkGray),
+        )],
+        DetailTab::PlayByPlay => detail
+            .plays
+            .iter()
+            .map(|p| {
+                let (top, bottom) = detail.slot_scores(p);
+                Line::from(format!(
+                    "P{} {}  {top}-{bottom}  {}",
+                    p.period, p.clock, p.description
+                ))
+            })
+            .collect(),
+        DetailTab::TeamStats => team_stats_lines(detail),
+    };
+    let offset = (state.scroll_offset as usize).min(lines.len().saturating_sub(1));
+    let visible: Vec<Line> = lines
+        .into_iter()
+        .skip(offset)
+        .take(content.height as usize)
+        .collect();
+    f.render_widget(Paragraph::new(visible), content);
+}
+
+fn box_team_name(box_score: &BoxScore) -> &str {
+    box_score
         .team
         .as_ref()
         .map(|t| t.short_name.as_str())
-        .unwrap_or("Home");
+        .unwrap_or("TBD")
+}
 
-    lines.push(format!("{} totals: {} PTS, {} REB, {} AST", away_name, detail.away_box.totals.points, detail.away_box.totals.rebounds, detail.away_box.totals.assists));
-    lines.push(format!("{} totals: {} PTS, {} REB, {} AST", home_name, detail.home_box.totals.points, detail.home_box.totals.rebounds, detail.home_box.totals.assists));
-    lines.push(String::new());
-    lines.push("Recent Plays: (j/k scroll)".to_string());
+/// Name column width in the Box Score tables.
+const BOX_NAME_W: usize = 22;
 
-    let max_lines = inner.height.saturating_sub(lines.len() as u16) as usize;
-    let offset = app.state.game_detail.scroll_offset as usize;
-    for p in detail.plays.iter().skip(offset).take(max_lines.max(1)) {
-        lines.push(format!("P{} {}  {}-{}  {}", p.period, p.clock, p.away_score, p.home_score, p.description));
+fn box_column_width(column: PlayerSort) -> usize {
+    match column {
+        PlayerSort::FieldGoals | PlayerSort::Threes | PlayerSort::FreeThrows => 7,
+        PlayerSort::OffensiveRebounds | PlayerSort::DefensiveRebounds | PlayerSort::PlusMinus => 5,
+        _ => 4,
+    }
+}
+
+fn box_cell(column: PlayerSort, p: &PlayerLine) -> String {
+    match column {
+        PlayerSort::Lineup => p.name.clone(),
+        PlayerSort::Minutes => p.minutes.clone(),
+        PlayerSort::Points => p.points.to_string(),
+        PlayerSort::FieldGoals => p.fg.clone(),
+        PlayerSort::Threes => p.fg3.clone(),
+        PlayerSort::FreeThrows => p.ft.clone(),
+        PlayerSort::OffensiveRebounds => p.offensive_rebounds.to_string(),
+        PlayerSort::DefensiveRebounds => p.defensive_rebounds.to_string(),
+        PlayerSort::Rebounds => p.rebounds.to_string(),
+        PlayerSort::Assists => p.assists.to_string(),
+        PlayerSort::Steals => p.steals.to_string(),
+        PlayerSort::Blocks => p.blocks.to_string(),
+        PlayerSort::Turnovers => p.turnovers.to_string(),
+        PlayerSort::Fouls => p.fouls.to_string(),
+        PlayerSort::PlusMinus => p
+            .plus_minus
+            .map(|v| format!("{v:+}"))
+            .unwrap_or_else(|| "-".to_string()),
+    }
+}
+
+/// Both teams' player tables, starters and bench listed apart, then totals.
+fn box_score_lines(detail: &GameDetail, sort: PlayerSort) -> Vec<Line<'static>> {
+    let columns = &PlayerSort::ALL[1..];
+    let header = |title: &str| {
+        let mut spans = vec![Span::raw(format!("{title:<BOX_NAME_W$}"))];
+        spans.extend(columns.iter().map(|&c| {
+            let style = if c == sort {
+                Style::default().fg(Color::Yellow)
+            } else {
+                Style::default()
+            };
+            Span::styled(format!("{:>w$}", c.label(), w = box_column_width(c)), style)
+        }));
+        Line::from(spans).style(Style::default().add_modifier(Modifier::UNDERLINED))
+    };
+    let row = |p: &PlayerLine| {
+        let name: String = p.name.chars().take(BOX_NAME_W - 1).collect();
+        let mut text = format!("{name:<BOX_NAME_W$}");
+        for &c in columns {
+            text.push_str(&format!("{:>w$}", box_cell(c, p), w = box_column_width(c)));
+        }
+        text
+    };
+
+    let mut lines = Vec::new();
+    for box_score in [detail.top_box(), detail.bottom_box()] {
+        if !lines.is_empty() {
+            lines.push(Line::from(""));
+        }
+        lines.push(Line::styled(
+            box_team_name(box_score).to_string(),
+            Style::default().add_modifier(Modifier::BOLD),
+        ));
+        lines.push(header("Starters"));
+        lines.extend(
+            sort.sorted(box_score.starters())
+                .into_iter()
+                .map(|p| Line::from(row(p))),
+        );
+        lines.push(header("Bench"));
+        lines.extend(
+            sort.sorted(box_score.bench())
+                .into_iter()
+                .map(|p| Line::from(row(p))),
+        );
+        lines.push(Line::styled(
+            row(&box_score.totals),
+            Style::default().add_modifier(Modifier::BOLD),
+        ));
     }
+    lines
+}
+
+/// Team totals side by side, with shooting percentages.
+fn team_stats_lines(detail: &GameDetail) -> Vec<Line<'static>> {
+    let (top, bottom) = (&detail.top_box().totals, &detail.bottom_box().totals);
+    let pct = |stat: &str| match made_attempted(stat) {
+        Some((made, attempted)) if attempted > 0 => {
+            format!("{:.1}%", made as f64 * 100.0 / attempted as f64)
+        }
+        _ => "-".to_string(),
+    };
+    let rows: [(&str, String, String); 15] = [
+        ("Field goals", top.fg.clone(), bottom.fg.clone()),
+        ("FG%", pct(&top.fg), pct(&bottom.fg)),
+        ("Three pointers", top.fg3.clone(), bottom.fg3.clone()),
+        ("3P%", pct(&top.fg3), pct(&bottom.fg3)),
+        ("Free throws", top.ft.clone(), bottom.ft.clone()),
+        ("FT%", pct(&top.ft), pct(&bottom.ft)),
+        (
+            "Off. rebounds",
+            top.offensive_rebounds.to_string(),
+            bottom.offensive_rebounds.to_string(),
+        ),
+        (
+            "Def. rebounds",
+            top.defensive_rebounds.to_string(),
+            bottom.defensive_rebounds.to_string(),
+        ),
+        (
+            "Rebounds",
+            top.rebounds.to_string(),
+            bottom.rebounds.to_string(),
+        ),
+        (
+            "Assists",
+            top.assists.to_string(),
+            bottom.assists.to_string(),
+        ),
+        ("Steals", top.steals.to_string(), bottom.steals.to_string()),
+        ("Blocks", top.blocks.to_string(), bottom.blocks.to_string()),
+        (
+            "Turnovers",
+            top.turnovers.to_string(),
+            bottom.turnovers.to_string(),
+        ),
+        ("Fouls", top.fouls.to_string(), bottom.fouls.to_string()),
+        ("Points", top.points.to_string(), bottom.points.to_string()),
+    ];
 
-    f.render_widget(Paragraph::new(lines.join("\n")), inner);
+    let mut lines = vec![Line::styled(
+        format!(
+            "{:<16}{:>12}{:>12}",
+            "",
+            box_team_name(detail.top_box()),
+            box_team_name(detail.bottom_box())
+        ),
+        Style::default().add_modifier(Modifier::BOLD),
+    )];
+    lines.extend(
+        rows.into_iter()
+            .map(|(label, t, b)| Line::from(format!("{label:<16}{t:>12}{b:>12}"))),
+    );
+    lines
 }
 
 fn draw_chat(f: &mut Frame, area: Rect, app: &App) {
@@ -666,25 +980,64 @@ fn draw_chat(f: &mut Frame, area: Rect, app: &App) {
         Span::styled("room ", Style::default().fg(Color::DarkGray)),
         Span::styled(app.state.chat.room.as_str(), Style::default().fg(Color::Gray)),
         Span::styled("  status ", Style::default().fg(Color::DarkGray)),
-        Span::styled(status, Style::default().fg(if app.state.chat.connected { Color::Green } else { Color::Red })),
+        Span::styled(
+            status,
+            Style::default().fg(if app.state.chat.connected {
+                Color::Green
+            } else {
+                Color::Red
+            }),
+        ),
+        Span::styled(
+            format!("  here ({}) ", app.state.chat.members.len()),
+            Style::default().fg(Color::DarkGray),
+        ),
+        Span::styled(
+            app.state.chat.members.join(", "),
+            Style::default().fg(Color::Gray),
+        ),
     ]));
     lines.push(Line::from(""));
 
     for msg in &app.state.chat.messages {
-        let prefix = format!("[{}] {}: ", msg.timestamp, msg.author);
+        // Verified authors get a check mark and their own colour.
+        let check = if msg.verified { " ✓" } else { "" };
+        let (prefix, body) = match slash::emote(&msg.body) {
+            Some(action) if !msg.is_system => (
+                format!("[{}] * {}{check} ", msg.timestamp, msg.author),
+                action,
+            ),
+            _ => (
+                format!("[{}] {}{check}: ", msg.timestamp, msg.author),
+                msg.body.as_str(),
+            ),
+        };
         let style = if msg.is_system {
             Style::default().fg(Color::DarkGray)
         } else {
             Style::default().fg(Color::White)
         };
+        let prefix_style = if msg.verified {
+            Style::default().fg(Color::Green)
+        } else {
+            style
+        };
+        // Our own messages: sending, delivered or refused by the relay.
+        let (status, status_color) = match msg.delivery {
+            Some(Delivery::Sending) => (" …", Color::DarkGray),
+            Some(Delivery::Delivered) => (" ✔", Color::DarkGray),
+            Some(Delivery::Failed) => (" ✗", Color::Red),
+            None => ("", Color::DarkGray),
+        };
         let body_width = messages_area
             .width
-            .saturating_sub(prefix.chars().count() as u16)
+            .saturating_sub((prefix.chars().count() + status.chars().count()) as u16)
             .max(8) as usize;
-        let clipped: String = msg.body.chars().take(body_width).collect();
+        let clipped: String = body.chars().take(body_width).collect();
         lines.push(Line::from(vec![
-            Span::styled(prefix, style),
+            Span::styled(prefix, prefix_style),
             Span::styled(clipped, style),
+            Span::styled(status, Style::default().fg(status_color)),
         ]));
     }
 
@@ -806,37 +1159,71 @@ fn draw_live_feed(f: &mut Frame, area: Rect, app: &App) {
         return;
     }
 
-    let mut lines = Vec::new();
-    lines.push(Line::from(vec![
-        Span::styled("Game: ", Style::default().fg(Color::Gray)),
-        Span::raw(game_id),
-    ]));
-    lines.push(Line::from(""));
+    let feed = &app.state.live_feed;
+    let team = feed
+        .team_name(feed.team_filter.as_deref())
+        .unwrap_or("both teams");
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled("Game: ", Style::default().fg(Color::Gray)),
+            Span::raw(game_id),
+        ]),
+        Line::styled(
+            format!("{}, {team}  s=view a=team", feed.view.label()),
+            Style::default().fg(Color::DarkGray),
+        ),
+    ];
 
-    let max_plays = inner.height.saturating_sub(2) as usize;
-    for play in app.state.live_feed.plays.iter().rev().take(max_plays) {
+    let width = inner.width.saturating_sub(1) as usize;
+    let clip = |text: String| -> String { text.chars().take(width).collect() };
+    for (idx, play) in feed.visible().rev() {
+        if lines.len() >= inner.height as usize {
+            break;
+        }
+        // Newest first, so a run's banner sits above the basket that ended it.
+        if let Some(run) = feed
+            .run_ending_at(idx)
+            .filter(|_| feed.view != FeedView::LeadChanges)
+        {
+            let side = if run.home { "Home" } else { "Away" };
+            let name = feed.team_name(run.team_id.as_deref()).unwrap_or(side);
+            lines.push(Line::styled(
+                clip(format!("▲ {name} {}-0 run", run.points)),
+                Style::default()
+                    .fg(Color::Magenta)
+                    .add_modifier(Modifier::BOLD),
+            ));
+        }
         let style = if play.is_new {
             Style::default().fg(Color::Yellow)
+        } else if play.lead_change {
+            Style::default().fg(Color::Green)
+        } else if play.scoring && feed.in_run(idx) {
+            Style::default().fg(Color::Magenta)
         } else {
             Style::default().fg(Color::White)
         };
+        let tag = if play.lead_change { " ⇄ lead" } else { "" };
         let text = format!(
-            "{} P{} {}-{} {}",
+            "{} P{} {}-{} {}{tag}",
             play.clock, play.period, play.away_score, play.home_score, play.description
         );
-        let clipped: String = text.chars().take(inner.width.saturating_sub(1) as usize).collect();
-        lines.push(Line::from(Span::styled(clipped, style)));
+        lines.push(Line::styled(clip(text), style));
     }
 
     f.render_widget(Paragraph::new(lines), inner);
 }
 
 fn draw_pick_wizard(f: &mut Frame, area: Rect, app: &App) {
-    let block = default_border(Color::White).title(" Pick Wizard (2025 Template) ");
+    let wizard = &app.state.pick_wizard;
+    let block = default_border(Color::White).title(format!(
+        " Pick Wizard ({} {}) ",
+        wizard.gender.label(),
+        wizard.year
+    ));
     let inner = block.inner(area);
     f.render_widget(block, area);
 
-    let wizard = &app.state.pick_wizard;
     if wizard.games.is_empty() {
         f.render_widget(
             Paragraph::new("No wizard games loaded yet. Load bracket then press 5 again.")
@@ -852,12 +1239,17 @@ fn draw_pick_wizard(f: &mut Frame, area: Rect, app: &App) {
         wizard.selections.len(),
         wizard.games.len()
     )));
-    lines.push(Line::from("Keys: 1=top  2=bottom  j/k=next/prev  s=save  r=reset  Esc=back"));
+    lines.push(Line::from(
+        "Keys: 1=top  2=bottom  j/k=next/prev  s=save  u=submit  r=reset  Esc=back",
+    ));
     lines.push(Line::from(""));
 
     if wizard.completed {
         lines.push(Line::from(Span::styled(
-            "Wizard complete. Picks saved to ~/.config/mmtui/picks_2025.json",
+            format!(
+                "Wizard complete. Picks saved to {}",
+                pick_wizard_path(wizard.gender, wizard.year).display()
+            ),
             Style::default().fg(Color::Green),
         )));
     } else if let Some(game) = wizard.current_game() {
@@ -869,16 +1261,9 @@ fn draw_pick_wizard(f: &mut Frame, area: Rect, app: &App) {
         )));
         lines.push(Line::from(""));
 
-        let top_selected = wizard
-            .selections
-            .get(&game.game_id)
-            .and_then(|w| game.top_team_id.as_ref().map(|id| id == w))
-            .unwrap_or(false);
-        let bottom_selected = wizard
-            .selections
-            .get(&game.game_id)
-            .and_then(|w| game.bottom_team_id.as_ref().map(|id| id == w))
-            .unwrap_or(false);
+        let picked = wizard.selections.get(&game.game_id);
+        let top_selected = picked == Some(&game.top_pick_id());
+        let bottom_selected = picked == Some(&game.bottom_pick_id());
 
         lines.push(Line::from(vec![
             Span::styled(
@@ -914,6 +1299,14 @@ fn draw_pick_wizard(f: &mut Frame, area: Rect, app: &App) {
 }
 
 fn draw_compare(f: &mut Frame, area: Rect, app: &App) {
+    let area = if app.state.compare.detail_open {
+        let [board, detail] =
+            Layout::vertical([Constraint::Fill(1), Constraint::Length(9)]).areas(area);
+        draw_compare_detail(f, detail, app);
+        board
+    } else {
+        area
+    };
     let block = default_border(Color::White).title(" Compare ");
     let inner = block.inner(area);
     f.render_widget(block, area);
@@ -925,33 +1318,68 @@ fn draw_compare(f: &mut Frame, area: Rect, app: &App) {
         .last_loaded_at
         .clone()
         .unwrap_or_else(|| "-".to_string());
+    let sim_status = if app.state.compare.loading {
+        "  |  Loading sources..."
+    } else if app.state.compare.simulating {
+        "  |  Simulating..."
+    } else {
+        ""
+    };
     lines.push(Line::from(format!(
-        "Loaded: {loaded}  |  Entries: {}  |  r=reload j/k=scroll",
-        app.state.compare.rows.len()
+        "Loaded: {loaded}  |  Entries: {}  |  Scoring: {}{sim_status}  |  r=reload j/k=select Enter=paths",
+        app.state.compare.rows.len(),
+        app.settings.scoring_rule.label()
     )));
-    lines.push(Line::from(
-        "Rank  User                 Pts/Max  Correct  Source",
-    ));
-    lines.push(Line::from(
-        "------------------------------------------------------",
-    ));
+    let round_header: String = RoundKind::ALL
+        .iter()
+        .map(|r| format!("{:>5}", r.abbrev()))
+        .collect();
+    lines.push(Line::from(format!(
+        "Rank  User             Pts/Max    Correct   Win%   xPts{round_header}  Source"
+    )));
+    lines.push(Line::from("-".repeat(inner.width as usize)));
 
-    let offset = app.state.compare.scroll_offset as usize;
+    let visible = (inner.height as usize).saturating_sub(lines.len());
+    let offset = app.state.compare.first_visible(visible);
     for (idx, row) in app.state.compare.rows.iter().enumerate().skip(offset) {
         if lines.len() >= inner.height as usize {
             break;
         }
         let source_short: String = row.source.chars().take(26).collect();
-        lines.push(Line::from(format!(
-            "{:>2}. {:<20} {:>3}/{:<3}   {:>3}/{:<3}  {}",
-            idx + 1,
-            row.user_id.chars().take(20).collect::<String>(),
-            row.points,
-            row.max_points,
-            row.correct,
-            row.total,
-            source_short
-        )));
+        let unsealed = if row.unsealed { "  unsealed" } else { "" };
+        let by_round: String = RoundKind::ALL
+            .iter()
+            .map(|r| match row.round_points.get(r) {
+                Some(pts) => format!("{pts:>5}"),
+                None => format!("{:>5}", "-"),
+            })
+            .collect();
+        let win_pct = row
+            .win_probability
+            .map(|p| format!("{:>6.1}", p * 100.0))
+            .unwrap_or_else(|| format!("{:>6}", "-"));
+        let expected = row
+            .expected_points
+            .map(|p| format!("{p:>7.1}"))
+            .unwrap_or_else(|| format!("{:>7}", "-"));
+        let style = if idx == app.state.compare.selected {
+            Style::default().fg(Color::Yellow)
+        } else {
+            Style::default()
+        };
+        lines.push(Line::styled(
+            format!(
+                "{:>2}. {:<16} {:>4}/{:<4}   {:>3}/{:<3}{win_pct}{expected}{by_round}  {}{unsealed}",
+                idx + 1,
+                row.user_id.chars().take(16).collect::<String>(),
+                row.points,
+                row.max_points,
+                row.correct,
+                row.total,
+                source_short
+            ),
+            style,
+        ));
     }
 
     if lines.len() + 1 < inner.height as usize && !app.state.compare.source_errors.is_empty() {
@@ -960,6 +1388,71 @@ fn draw_compare(f: &mut Frame, area: Rect, app: &App) {
             format!("Source errors: {}", app.state.compare.source_errors.len()),
             Style::default().fg(Color::DarkGray),
         )));
+        let room = (inner.height as usize).saturating_sub(lines.len());
+        for err in app.state.compare.source_errors.iter().take(room) {
+            lines.push(Line::styled(
+                format!("  {err}"),
+                Style::default().fg(Color::DarkGray),
+            ));
+        }
+    }
+
+    f.render_widget(Paragraph::new(lines), inner);
+}
+
+fn draw_compare_detail(f: &mut Frame, area: Rect, app: &App) {
+    let compare = &app.state.compare;
+    let row = compare.selected_row();
+    let title = match row {
+        Some(row) => format!(" Path to 1st: {} ", row.user_id),
+        None => " Path to 1st ".to_string(),
+    };
+    let block = default_border(Color::Yellow).title(title);
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let dim = Style::default().fg(Color::DarkGray);
+    let mut lines = Vec::new();
+    let path = row.and_then(|row| match &compare.paths {
+        Some(Ok(paths)) => paths
+            .iter()
+            .find(|(source, _)| *source == row.source)
+            .map(|(_, p)| p),
+        _ => None,
+    });
+    match (&compare.paths, path) {
+        _ if compare.analyzing => {
+            lines.push(Line::styled("Working through remaining scenarios...", dim));
+        }
+        (Some(Err(note)), _) => lines.push(Line::styled(note.clone(), dim)),
+        (_, Some(path)) if path.eliminated => {
+            lines.push(Line::styled(
+                "Eliminated: no remaining set of results puts this entry first.",
+                Style::default().fg(Color::Red),
+            ));
+        }
+        (_, Some(path)) => {
+            lines.push(Line::from(vec![
+                Span::styled("Alive", Style::default().fg(Color::Green)),
+                Span::raw(format!(
+                    " in {} of {} scenarios",
+                    path.winning_scenarios, path.total_scenarios
+                )),
+            ]));
+            if path.needs.is_empty() {
+                lines.push(Line::styled("No single result is required.", dim));
+            } else {
+                lines.push(Line::from("Needs:"));
+                for need in &path.needs {
+                    lines.push(Line::from(format!(
+                        "  {:<4} {}",
+                        need.round.abbrev(),
+                        need.describe()
+                    )));
+                }
+            }
+        }
+        _ => lines.push(Line::styled("No entry selected.", dim)),
     }
 
     f.render_widget(Paragraph::new(lines), inner);
@@ -125,15 +125,10 @@ pub async fn handle_key_bindings(
                 guard.state.chat.input.clear();
             }
             (KeyCode::Enter, _) => {
-                let outbound = guard.state.chat.submit_input();
+                let command = guard.submit_chat_input();
                 drop(guard);
-                if let Some(outbound) = outbound {
-                    let _ = chat_commands
-                        .send(ChatCommand::Send {
-                            body: outbound.body,
-                            message_id: outbound.id,
-                        })
-                        .await;
+                if let Some(command) = command {
+                    let _ = chat_commands.send(command).await;
                 }
             }
             (KeyCode::Backspace, _) => {
@@ -162,7 +157,16 @@ pub async fn handle_key_bindings(
         (_, Char('3'), _) => guard.update_tab(MenuItem::GameDetail),
         (_, Char('4'), _) => guard.update_tab(MenuItem::Chat),
         (_, Char('5'), _) => guard.update_tab(MenuItem::PickWizard),
-        (_, Char('6'), _) => guard.update_tab(MenuItem::Compare),
+        (tab, Char('6'), _) => {
+            guard.update_tab(MenuItem::Compare);
+            if tab != MenuItem::Compare
+                && let Some(request) = guard.compare_load_request()
+            {
+                drop(guard);
+                let _ = network_requests.send(request).await;
+                return;
+            }
+        }
         (_, Char('7'), _) => {
             guard.update_tab(MenuItem::PrizePool);
             let address = guard.state.prize_pool.address.clone();
@@ -210,6 +214,9 @@ pub async fn handle_key_bindings(
             guard.bracket_cycle_region();
             refresh_live_feed = true;
         }
+        (MenuItem::Bracket, Char('b'), _) => guard.bracket_toggle_full_view(),
+        (MenuItem::Bracket, Char('s'), _) => guard.state.live_feed.cycle_view(),
+        (MenuItem::Bracket, Char('a'), _) => guard.state.live_feed.cycle_team_filter(),
         (MenuItem::Bracket, KeyCode::Enter, _) => {
             if let Some((bracket_id, espn_id)) = guard.bracket_select_game() {
                 drop(guard);
@@ -229,6 +236,14 @@ pub async fn handle_key_bindings(
             guard.state.game_detail.scroll_offset =
                 guard.state.game_detail.scroll_offset.saturating_sub(1);
         }
+        (MenuItem::GameDetail, Char('l') | KeyCode::Right | KeyCode::Tab, _) => {
+            guard.state.game_detail.cycle_tab(true)
+        }
+        (MenuItem::GameDetail, Char('h') | KeyCode::Left | KeyCode::BackTab, _) => {
+            guard.state.game_detail.cycle_tab(false)
+        }
+        (MenuItem::GameDetail, Char('s'), _) => guard.state.game_detail.cycle_sort(true),
+        (MenuItem::GameDetail, Char('S'), _) => guard.state.game_detail.cycle_sort(false),
         (MenuItem::GameDetail, KeyCode::Esc, _) => guard.update_tab(MenuItem::Bracket),
 
         // Chat controls
@@ -256,13 +271,37 @@ pub async fn handle_key_bindings(
                 guard.on_error(e);
             }
         }
+        (MenuItem::PickWizard, Char('u'), _) => match guard.submit_picks_request() {
+            Ok(request) => {
+                drop(guard);
+                let _ = network_requests.send(request).await;
+                return;
+            }
+            Err(e) => guard.on_error(e),
+        },
         (MenuItem::PickWizard, Char('r'), _) => guard.reset_pick_wizard(),
         (MenuItem::PickWizard, KeyCode::Esc, _) => guard.update_tab(MenuItem::Bracket),
 
         // Compare
-        (MenuItem::Compare, Char('r'), _) => guard.load_compare_sources(),
-        (MenuItem::Compare, Char('j') | KeyCode::Down, _) => guard.compare_scroll_down(),
-        (MenuItem::Compare, Char('k') | KeyCode::Up, _) => guard.compare_scroll_up(),
+        (MenuItem::Compare, Char('r'), _) => {
+            if let Some(request) = guard.compare_load_request() {
+                drop(guard);
+                let _ = network_requests.send(request).await;
+                return;
+            }
+        }
+        (MenuItem::Compare, Char('j') | KeyCode::Down, _) => guard.state.compare.select_next(),
+        (MenuItem::Compare, Char('k') | KeyCode::Up, _) => guard.state.compare.select_previous(),
+        (MenuItem::Compare, KeyCode::Enter, _) => {
+            if let Some(request) = guard.toggle_compare_detail() {
+                drop(guard);
+                let _ = network_requests.send(request).await;
+                return;
+            }
+        }
+        (MenuItem::Compare, KeyCode::Esc, _) if guard.state.compare.detail_open => {
+            guard.state.compare.detail_open = false;
+        }
         (MenuItem::Compare, KeyCode::Esc, _) => guard.update_tab(MenuItem::Bracket),
 
         // Scoreboard navigation
@@ -290,6 +329,16 @@ pub async fn handle_key_bindings(
 
         // Global
         (_, Char('f'), _) => guard.toggle_full_screen(),
+        (_, Char('t'), _) => {
+            let next = guard
+                .state
+                .pending_gender
+                .unwrap_or(guard.state.gender)
+                .toggle();
+            if let Some(request) = guard.select_tournament(next) {
+                let _ = network_requests.send(request).await;
+            }
+        }
         (_, Char('"'), _) => guard.toggle_show_logs(),
 
         _ => {}
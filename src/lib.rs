@@ -0,0 +1,4 @@
+//! Pieces shared between the `mmtui` TUI and its companion binaries.
+
+pub mod chat_protocol;
+pub mod seal;
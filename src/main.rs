@@ -1,4 +1,5 @@
 mod app;
+mod cli;
 mod components;
 mod draw;
 mod keys;
@@ -6,14 +7,20 @@ mod state;
 mod ui;
 
 use crate::app::App;
+use crate::state::app_settings::AppSettings;
+use crate::state::app_state::Delivery;
+use crate::state::archive::{self, Recorder, Replay};
 use crate::state::chat::{ChatCommand, ChatEvent, ChatWorker};
+use crate::state::follow;
 use crate::state::messages::{NetworkRequest, NetworkResponse, UiEvent};
 use crate::state::network::{LoadingState, NetworkWorker};
 use crate::state::refresher::PeriodicRefresher;
 use crossterm::event::{self as crossterm_event, Event};
 use crossterm::{cursor, execute, terminal};
 use log::error;
+use ncaa_api::Gender;
 use std::io::Stdout;
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::{io, panic};
 use tokio::sync::{Mutex, mpsc};
@@ -22,9 +29,41 @@ use tui::{Terminal, backend::CrosstermBackend};
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
-    if handle_cli_args() {
+    let Some(cli) = handle_cli_args() else {
         return Ok(());
+    };
+    let mut settings = match AppSettings::load(cli.config.as_deref()) {
+        Ok(settings) => settings,
+        Err(e) => {
+            eprintln!("mmtui: invalid configuration\n{e}");
+            std::process::exit(2);
+        }
+    };
+    if let Some(gender) = cli.tournament {
+        settings.gender = gender;
     }
+    if let Some(command) = cli.command {
+        if let Err(e) = cli::run(command, settings).await {
+            eprintln!("mmtui: {e}");
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+    let api_settings = settings.clone();
+    let refresh = settings.refresh;
+    let recorder = cli.record.as_deref().map(Recorder::create).transpose();
+    let replay = cli
+        .replay
+        .as_deref()
+        .map(|path| Replay::open(path, cli.speed))
+        .transpose();
+    let (recorder, replay) = match (recorder, replay) {
+        (Ok(recorder), Ok(replay)) => (recorder, replay),
+        (Err(e), _) | (_, Err(e)) => {
+            eprintln!("mmtui: {e}");
+            std::process::exit(1);
+        }
+    };
 
     better_panic::install();
 
@@ -37,7 +76,7 @@ async fn main() -> anyhow::Result<()> {
     tui_logger::init_logger(log::LevelFilter::Error)?;
     tui_logger::set_default_level(log::LevelFilter::Error);
 
-    let app = Arc::new(Mutex::new(App::new()));
+    let app = Arc::new(Mutex::new(App::new(settings)));
 
     let (ui_event_tx, ui_event_rx) = mpsc::channel::<UiEvent>(100);
     let (network_req_tx, network_req_rx) = mpsc::channel::<NetworkRequest>(100);
@@ -49,29 +88,39 @@ async fn main() -> anyhow::Result<()> {
     let input_handler = tokio::spawn(input_handler_task(ui_event_tx.clone()));
 
     // Network thread
-    let network_worker = NetworkWorker::new(network_req_rx, network_resp_tx);
+    let mut network_worker = NetworkWorker::new(
+        network_req_rx,
+        network_resp_tx,
+        api_settings.gender,
+        move |gender| api_settings.api(gender),
+    );
+    if let Some(recorder) = recorder {
+        network_worker = network_worker.with_recorder(recorder);
+    }
+    if let Some(replay) = replay {
+        network_worker = network_worker.with_replay(replay);
+    }
     let network_task = tokio::spawn(network_worker.run());
 
     // Chat thread
-    let (chat_endpoint, chat_room, chat_username) = {
+    let chat_worker = {
         let guard = app.lock().await;
-        (
-            guard.state.chat.endpoint.clone(),
-            guard.state.chat.room.clone(),
-            guard.state.chat.username.clone(),
-        )
-    };
-    let chat_worker = ChatWorker {
-        url: chat_endpoint,
-        room: chat_room,
-        username: chat_username,
-        commands: chat_cmd_rx,
-        events: chat_evt_tx,
+        let chat = &guard.state.chat;
+        ChatWorker {
+            url: chat.endpoint.clone(),
+            room: chat.room.clone(),
+            username: chat.username.clone(),
+            password: chat.password.clone(),
+            admin_token: chat.admin_token.clone(),
+            signing_key: chat.signing_key,
+            commands: chat_cmd_rx,
+            events: chat_evt_tx,
+        }
     };
     let chat_task = tokio::spawn(chat_worker.run());
 
-    // Periodic score refresh thread (every 30s)
-    let periodic_updater = PeriodicRefresher::new(network_req_tx.clone());
+    // Periodic score refresh thread
+    let periodic_updater = PeriodicRefresher::new(network_req_tx.clone(), refresh);
     let periodic_task = tokio::spawn(periodic_updater.run());
 
     // Animation tick thread — 80ms ≈ 12.5 FPS
@@ -109,40 +158,149 @@ async fn main() -> anyhow::Result<()> {
     Ok(())
 }
 
-fn handle_cli_args() -> bool {
-    let mut args = std::env::args().skip(1);
-    let Some(arg) = args.next() else {
-        return false;
-    };
+struct Cli {
+    /// Config file given with `--config`.
+    config: Option<PathBuf>,
+    /// Tournament given with `--tournament`, over the configured one.
+    tournament: Option<Gender>,
+    /// Archive to record live data to.
+    record: Option<PathBuf>,
+    /// Archive to replay instead of fetching live data.
+    replay: Option<PathBuf>,
+    /// Replay speed multiplier.
+    speed: f64,
+    /// Subcommand to run instead of the TUI.
+    command: Option<cli::Command>,
+}
 
-    match arg.as_str() {
-        "-h" | "--help" => {
-            println!("{}", usage_text());
-            true
-        }
-        "-V" | "--version" => {
-            println!("mmtui {}", env!("CARGO_PKG_VERSION"));
-            true
-        }
-        _ => {
-            eprintln!("Unknown argument: {arg}\n\n{}", usage_text());
-            std::process::exit(2);
+/// Parse the command line; `None` once help or the version has been printed.
+fn handle_cli_args() -> Option<Cli> {
+    let mut cli = Cli {
+        config: None,
+        tournament: None,
+        record: None,
+        replay: None,
+        speed: 1.0,
+        command: None,
+    };
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let (flag, inline) = match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => (flag, Some(value.to_string())),
+            _ => (arg.as_str(), None),
+        };
+        let mut value = |what: &str| {
+            inline
+                .clone()
+                .or_else(|| args.next())
+                .unwrap_or_else(|| usage_error(&format!("{flag} needs {what}")))
+        };
+        match flag {
+            "-h" | "--help" => {
+                println!("{}", usage_text());
+                return None;
+            }
+            "-V" | "--version" => {
+                println!("mmtui {}", env!("CARGO_PKG_VERSION"));
+                return None;
+            }
+            "-c" | "--config" => cli.config = Some(PathBuf::from(value("a file path"))),
+            "-t" | "--tournament" => match value("men or women").parse() {
+                Ok(gender) => cli.tournament = Some(gender),
+                Err(e) => usage_error(&e),
+            },
+            "--record" => cli.record = Some(PathBuf::from(value("a file path"))),
+            "--replay" => cli.replay = Some(PathBuf::from(value("a file path"))),
+            "--speed" => match archive::parse_speed(&value("a speed such as 10x")) {
+                Ok(speed) => cli.speed = speed,
+                Err(e) => usage_error(&e),
+            },
+            _ => {
+                if arg.starts_with('-') {
+                    usage_error(&format!("Unknown argument: {arg}"));
+                }
+                let rest: Vec<String> = args.collect();
+                match cli::Command::parse(&arg, &rest) {
+                    Ok(command) => cli.command = Some(command),
+                    Err(e) => usage_error(&e),
+                }
+                break;
+            }
         }
     }
+    if cli.record.is_some() && cli.replay.is_some() {
+        usage_error("--record and --replay cannot be used together");
+    }
+    if cli.command.is_some() && (cli.record.is_some() || cli.replay.is_some()) {
+        usage_error("--record and --replay only apply to the TUI");
+    }
+    Some(cli)
+}
+
+fn usage_error(message: &str) -> ! {
+    eprintln!("{message}\n\n{}", usage_text());
+    std::process::exit(2);
 }
 
 fn usage_text() -> &'static str {
     "mmtui - NCAA March Madness terminal UI
 
 Usage:
-  mmtui
+  mmtui [--config <file>] [--tournament men|women]
+        [--record <archive> | --replay <archive> [--speed <n>x]]
+  mmtui [--config <file>] [--tournament men|women] <command>
   mmtui --help
   mmtui --version
 
+Options:
+  -c, --config <file>  Config file (default ~/.config/mmtui/config.toml)
+  -t, --tournament <t> men or women; press t in the TUI to switch (default men)
+  --record <archive>   Save every bracket, scoreboard and game detail loaded
+  --replay <archive>   Play back a recorded archive instead of fetching live data
+  --speed <n>x         Replay speed, e.g. 10x (default 1x)
+
+Commands (run without the TUI):
+  bracket [--year <year>] [--format text|json]  Bracket with results
+  scores [--format text|json]                   Today's tournament games and their ids
+  game <id> [--format text|json]                Box score and recent plays for a game id
+  picks export [--year <year>] [--out <file>]   Print or write your saved picks
+  picks import <file> [--force]                 Save a picks file as your picks for its year
+  leaderboard [--format text|json]              Score every Compare source
+
+Settings come from environment variables, then the config file, then
+defaults.
+
 Environment:
-  MMTUI_BRACKET_JSON   Path to local tournament JSON snapshot
-  MMTUI_CHAT_WS        WebSocket chat relay URL (default ws://127.0.0.1:8787)
-  MMTUI_CHAT_ROOM      Chat room name (default march-madness)"
+  MMTUI_TOURNAMENT            Tournament shown at startup: men or women (default men)
+  MMTUI_BRACKET_JSON          Path to local tournament JSON snapshot
+  MMTUI_BRACKET_SOURCES       Bracket fallback order (default henrygd,espn,embedded)
+  MMTUI_HENRYGD_URL           henrygd NCAA API base URL
+  MMTUI_ESPN_URL              ESPN API base URL
+  MMTUI_REFRESH_SECS          Seconds between score refreshes (default 30, minimum 5)
+  MMTUI_THEME                 Color theme: dark or light (default dark)
+  MMTUI_LOG_LEVEL             Log level: off, error, warn, info, debug or trace
+  MMTUI_USERNAME              Chat name (default $USER)
+  MMTUI_CHAT_WS               WebSocket chat relay URL (default ws://127.0.0.1:8787)
+  MMTUI_CHAT_ROOM             Chat room name (default march-madness)
+  MMTUI_CHAT_PASSWORD         Shared password for a relay that requires one
+  MMTUI_CHAT_KEY              Hex secp256k1 secret key used to sign chat messages
+  MMTUI_CHAT_ADMIN_TOKEN      Relay admin token; enables /mute /unmute /kick /ban /slow
+  MMTUI_SCORING_RULE          Compare scoring: espn, fibonacci, seed, upset or round-plus-seed
+  MMTUI_COMPARE_SOURCES       Comma-separated extra Compare files or URLs
+  MMTUI_RATINGS               Team ratings file for pool odds (default: seeds)
+  MMTUI_POOL_URL              Pool server URL for submitting picks
+  MMTUI_PICKS_KEY             Hex secp256k1 secret key used to sign saved picks
+  MMTUI_PICKS_SIGNERS         File of `user_id pubkey` lines trusted to sign Compare entries
+  MMTUI_ALLOW_UNSEALED_PICKS  Score unsealed Compare entries, flagged (default true)
+  MMTUI_PRIZE_POOL_KEYS       Comma-separated custodian public keys for the prize pool
+  MMTUI_ANNOUNCE              Game events posted to chat (default tipoff,halftime,final,upset,buzzer; none to disable)
+  MMTUI_ANNOUNCE_UPSET_SEEDS  Seed lines apart for an upset (default 1)
+  MMTUI_ANNOUNCE_BUZZER_SECS  Seconds left for a late lead change to count (default 5)
+  MMTUI_ANNOUNCE_BROADCAST    Set to 1 to also send announcements to the chat room
+  MMTUI_FOLLOW                Comma-separated teams to follow, added to ~/.config/mmtui/follow.txt
+  MMTUI_NOTIFY                Followed-game alerts: bell, osc9, osc777 or off (default bell)
+  MMTUI_NOTIFY_HOOK           Shell command run per alert with MMTUI_NOTIFY_TITLE/BODY set
+  MMTUI_NOTIFY_CLOSE_MARGIN   Points margin for a close-game alert (default 5)"
 }
 
 async fn main_ui_loop(
@@ -168,7 +326,7 @@ async fn main_ui_loop(
 
             Some(response) = network_responses.recv() => {
                 let should_redraw =
-                    handle_network_response(response, &app, &network_requests, &mut loading).await;
+                    handle_network_response(response, &app, &network_requests, &chat_commands, &mut loading).await;
                 if should_redraw {
                     let mut app_guard = app.lock().await;
                     draw::draw(&mut terminal, &mut app_guard, loading);
@@ -216,6 +374,13 @@ async fn handle_chat_response(response: ChatEvent, app: &Arc<Mutex<App>>) -> boo
         ChatEvent::Connected => guard.on_chat_connected(),
         ChatEvent::Disconnected => guard.on_chat_disconnected(),
         ChatEvent::Message(msg) => guard.on_chat_message(msg),
+        ChatEvent::History(messages) => messages
+            .into_iter()
+            .for_each(|msg| guard.on_chat_message(msg)),
+        ChatEvent::Presence(users) => guard.state.chat.members = users,
+        ChatEvent::System(message) => guard.state.chat.push_system(message),
+        ChatEvent::Acked(id) => guard.state.chat.set_delivery(&id, Delivery::Delivered),
+        ChatEvent::Rejected { id, reason } => guard.on_chat_rejected(id, reason),
         ChatEvent::Error(message) => guard.on_chat_error(message),
     }
     true
@@ -225,18 +390,36 @@ async fn handle_network_response(
     response: NetworkResponse,
     app: &Arc<Mutex<App>>,
     network_requests: &mpsc::Sender<NetworkRequest>,
+    chat_commands: &mpsc::Sender<ChatCommand>,
     loading: &mut LoadingState,
 ) -> bool {
+    let switching = app.lock().await.state.pending_gender.is_some();
     match response {
         NetworkResponse::LoadingStateChanged { loading_state } => {
             *loading = loading_state;
             return true;
         }
+        // Late data for the tournament being switched away from.
+        NetworkResponse::BracketLoaded { .. }
+        | NetworkResponse::BracketUpdated { .. }
+        | NetworkResponse::GameDetailLoaded { .. }
+            if switching => {}
+        NetworkResponse::TournamentSelected { gender } => {
+            let mut guard = app.lock().await;
+            let changed = guard.on_tournament_selected(gender);
+            drop(guard);
+            if changed {
+                let _ = network_requests.send(NetworkRequest::LoadBracket).await;
+            }
+        }
         NetworkResponse::BracketLoaded { tournament } => {
             let mut guard = app.lock().await;
             guard.on_bracket_loaded(tournament);
             let selected_game = guard.selected_game_id();
             drop(guard);
+            // Pull live scores right away so the ESPN bridge can populate espn_id
+            // instead of waiting for the first periodic refresh.
+            let _ = network_requests.send(NetworkRequest::RefreshScores).await;
             if let Some((bracket_id, espn_id)) = selected_game {
                 let _ = network_requests
                     .send(NetworkRequest::LoadGameDetail { bracket_id, espn_id })
@@ -245,18 +428,59 @@ async fn handle_network_response(
         }
         NetworkResponse::BracketUpdated { games } => {
             let mut guard = app.lock().await;
-            guard.on_scores_updated(games);
+            let rescored = guard.on_scores_updated(games);
             let selected_game = guard.selected_game_id();
+            // New results invalidate the pool odds and any open path analysis.
+            let follow_ups: Vec<NetworkRequest> = if rescored {
+                [guard.pool_simulation_request(), guard.paths_request()]
+                    .into_iter()
+                    .flatten()
+                    .collect()
+            } else {
+                Vec::new()
+            };
             drop(guard);
             if let Some((bracket_id, espn_id)) = selected_game {
                 let _ = network_requests
                     .send(NetworkRequest::LoadGameDetail { bracket_id, espn_id })
                     .await;
             }
+            for request in follow_ups {
+                let _ = network_requests.send(request).await;
+            }
         }
         NetworkResponse::GameDetailLoaded { detail } => {
             let mut guard = app.lock().await;
-            guard.on_game_detail_loaded(detail);
+            guard.on_game_detail_loaded(*detail);
+        }
+        NetworkResponse::CompareSourcesLoaded { entries, errors } => {
+            let mut guard = app.lock().await;
+            guard.on_compare_sources_loaded(entries, errors);
+            let simulation = guard.pool_simulation_request();
+            drop(guard);
+            if let Some(request) = simulation {
+                let _ = network_requests.send(request).await;
+            }
+        }
+        NetworkResponse::PoolSimulated { generation, odds } => {
+            let mut guard = app.lock().await;
+            let rerun = guard.on_pool_simulated(generation, odds);
+            drop(guard);
+            if let Some(request) = rerun {
+                let _ = network_requests.send(request).await;
+            }
+        }
+        NetworkResponse::PicksSubmitted { message } => {
+            let mut guard = app.lock().await;
+            guard.state.chat.push_system(message);
+        }
+        NetworkResponse::PathsAnalyzed { generation, paths } => {
+            let mut guard = app.lock().await;
+            let rerun = guard.on_paths_analyzed(generation, paths);
+            drop(guard);
+            if let Some(request) = rerun {
+                let _ = network_requests.send(request).await;
+            }
         }
         NetworkResponse::PrizePoolBalanceUpdated { balance_sat } => {
             let mut guard = app.lock().await;
@@ -268,6 +492,17 @@ async fn handle_network_response(
             guard.on_error(message);
         }
     }
+    // Game announcements to broadcast to the room, and followed-team alerts.
+    let mut guard = app.lock().await;
+    let outbox = std::mem::take(&mut guard.state.chat.outbox);
+    let notifications = std::mem::take(&mut guard.state.notifier.outbox);
+    for notification in &notifications {
+        follow::deliver(&guard.settings.follow, notification);
+    }
+    drop(guard);
+    for command in outbox {
+        let _ = chat_commands.send(command).await;
+    }
     !loading.is_loading
 }
 
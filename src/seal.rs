@@ -0,0 +1,377 @@
+//! Tamper-evident picks files.
+//!
+//! Saving picks stamps them with `submitted_at`, a SHA-256 `content_hash`
+//! over everything that matters, and optionally a secp256k1 signature of that
+//! hash (`MMTUI_PICKS_KEY`). Compare checks the seal before scoring: a broken
+//! hash or signature rejects the entry, and picks for rounds that had already
+//! tipped off when the entry was sealed are dropped. Files from before seals
+//! existed are scored and flagged as unsealed; only the pool server refuses
+//! them.
+//!
+//! The entrant writes `submitted_at` and the hash is unkeyed, so a seal only
+//! means something when the signature comes from a key the pool already
+//! trusts for that user. [`SealPolicy::signers`] holds those keys. Even then
+//! the signature proves who sealed the picks, not when: the signer can put any
+//! time in `submitted_at`, so it is always reported as unverified. Only a third
+//! party noting the time on receipt could vouch for it, and the pool server
+//! does that by refusing entries once the bracket locks.
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::Path;
+use std::str::FromStr;
+
+use bitcoin::hashes::{Hash, sha256};
+use bitcoin::hex::{DisplayHex, FromHex};
+use bitcoin::secp256k1::{Message, PublicKey, Secp256k1, SecretKey, ecdsa::Signature};
+use chrono::{DateTime, Utc};
+use ncaa_api::{BracketPicks, PicksSignature, RoundKind, Tournament};
+
+/// Bumped if the hashed layout ever changes.
+const HASH_DOMAIN: &str = "mmtui-picks-v1";
+
+/// How strictly [`check`] treats entries.
+#[derive(Debug, Clone)]
+pub struct SealPolicy {
+    /// Trusted signing key per `user_id`. When set, every entry must be
+    /// signed by its user's key.
+    pub signers: Option<HashMap<String, PublicKey>>,
+    /// Score entries without a seal (flagged) instead of rejecting them.
+    /// On by default so older picks files still load.
+    pub allow_unsealed: bool,
+}
+
+impl Default for SealPolicy {
+    fn default() -> Self {
+        Self {
+            signers: None,
+            allow_unsealed: true,
+        }
+    }
+}
+
+/// Read a signers file: one `user_id pubkey` pair per line, `#` comments.
+pub fn load_signers(path: &Path) -> Result<HashMap<String, PublicKey>, String> {
+    let text = std::fs::read_to_string(path)
+        .map_err(|e| format!("could not read {}: {e}", path.display()))?;
+    parse_signers(&text).map_err(|e| format!("{}: {e}", path.display()))
+}
+
+fn parse_signers(text: &str) -> Result<HashMap<String, PublicKey>, String> {
+    let mut signers = HashMap::new();
+    for (n, line) in text.lines().enumerate() {
+        let line = line.split('#').next().unwrap_or_default().trim();
+        if line.is_empty() {
+            continue;
+        }
+        let mut parts = line.split_whitespace();
+        let (Some(user_id), Some(pubkey), None) = (parts.next(), parts.next(), parts.next()) else {
+            return Err(format!("line {}: expected `user_id pubkey`", n + 1));
+        };
+        let pubkey = PublicKey::from_str(pubkey)
+            .map_err(|_| format!("line {}: invalid pubkey for {user_id}", n + 1))?;
+        signers.insert(user_id.to_string(), pubkey);
+    }
+    Ok(signers)
+}
+
+/// Stamp, hash and (with a key) sign `picks` as of `now`.
+pub fn seal(picks: &mut BracketPicks, now: DateTime<Utc>, key: Option<&SecretKey>) {
+    picks.submitted_at = Some(now);
+    let hash = content_hash(picks);
+    picks.signature = key.map(|key| {
+        let secp = Secp256k1::new();
+        let sig = secp.sign_ecdsa(&Message::from_digest(hash.to_byte_array()), key);
+        PicksSignature {
+            pubkey: key.public_key(&secp).to_string(),
+            signature: sig.serialize_compact().to_lower_hex_string(),
+        }
+    });
+    picks.content_hash = Some(hash.to_string());
+}
+
+/// Whether `saved` already seals exactly these selections under `key` (or
+/// unsigned, without one), so re-saving can keep its original timestamp.
+pub fn is_sealed_copy(saved: &BracketPicks, picks: &BracketPicks, key: Option<&SecretKey>) -> bool {
+    let pubkey = key.map(|key| key.public_key(&Secp256k1::new()).to_string());
+    saved.content_hash.is_some()
+        && saved.signature.as_ref().map(|s| &s.pubkey) == pubkey.as_ref()
+        && saved.user_id == picks.user_id
+        && saved.year == picks.year
+        && saved.selections == picks.selections
+}
+
+/// Check an entry's seal against the bracket.
+///
+/// `Err` means the entry was altered, is unsealed when the policy forbids it,
+/// or isn't signed by its user's trusted key, and must not be scored. `Ok`
+/// carries the picks that count and a note for `source_errors`; as nobody but
+/// the entrant vouches for `submitted_at`, there always is one.
+pub fn check(
+    mut picks: BracketPicks,
+    tournament: &Tournament,
+    policy: &SealPolicy,
+) -> Result<(BracketPicks, Option<String>), String> {
+    let (Some(stored), Some(submitted_at)) = (picks.content_hash.as_deref(), picks.submitted_at)
+    else {
+        if policy.allow_unsealed {
+            return Ok((picks, Some("unsealed; submission time unknown".into())));
+        }
+        return Err("unsealed".into());
+    };
+
+    let hash = content_hash(&picks);
+    if !stored.eq_ignore_ascii_case(&hash.to_string()) {
+        return Err("picks changed after they were sealed".into());
+    }
+    let mut notes = Vec::new();
+    match (&policy.signers, &picks.signature) {
+        (Some(signers), signature) => {
+            let key = signers
+                .get(&picks.user_id)
+                .ok_or_else(|| format!("no trusted key for {}", picks.user_id))?;
+            let signature = signature.as_ref().ok_or("not signed")?;
+            verify_signature(signature, hash, key)?;
+            // A trusted key proves who sealed the picks, not when.
+            notes.push("submission time unverified".to_string());
+        }
+        (None, Some(signature)) => {
+            let key = PublicKey::from_str(&signature.pubkey)
+                .map_err(|_| "invalid signer pubkey".to_string())?;
+            verify_signature(signature, hash, &key)?;
+            notes.push("signer not in a trusted list; submission time unverified".to_string());
+        }
+        (None, None) => notes.push("unsigned; submission time unverified".to_string()),
+    }
+
+    let tip_offs = round_tip_offs(tournament);
+    let rounds = game_rounds(tournament);
+    let mut late = BTreeSet::new();
+    picks.selections.retain(|game_id, _| {
+        let Some(round) = rounds.get(game_id.as_str()) else {
+            return true;
+        };
+        let started = tip_offs.get(round).is_some_and(|at| submitted_at >= *at);
+        if started {
+            late.insert(*round);
+        }
+        !started
+    });
+
+    if !late.is_empty() {
+        let labels: Vec<_> = late.iter().map(|r| r.label()).collect();
+        notes.push(format!(
+            "sealed {} after tip-off; {} picks ignored",
+            submitted_at.format("%b %-d %H:%M UTC"),
+            labels.join(", ")
+        ));
+    }
+    let note = (!notes.is_empty()).then(|| notes.join("; "));
+    Ok((picks, note))
+}
+
+fn content_hash(picks: &BracketPicks) -> sha256::Hash {
+    let mut selections: Vec<_> = picks.selections.iter().collect();
+    selections.sort();
+
+    let mut data = format!(
+        "{HASH_DOMAIN}\n{}\n{}\n{}\n",
+        picks.user_id,
+        picks.year,
+        picks
+            .submitted_at
+            .map(|t| t.to_rfc3339())
+            .unwrap_or_default()
+    );
+    for (game_id, team_id) in selections {
+        data.push_str(&format!("{game_id}={team_id}\n"));
+    }
+    sha256::Hash::hash(data.as_bytes())
+}
+
+fn verify_signature(
+    signature: &PicksSignature,
+    hash: sha256::Hash,
+    pubkey: &PublicKey,
+) -> Result<(), String> {
+    let sig = Vec::<u8>::from_hex(&signature.signature)
+        .ok()
+        .and_then(|bytes| Signature::from_compact(&bytes).ok())
+        .ok_or("invalid signature encoding")?;
+    Secp256k1::verification_only()
+        .verify_ecdsa(&Message::from_digest(hash.to_byte_array()), &sig, pubkey)
+        .map_err(|_| "signature does not match picks".to_string())
+}
+
+/// First scheduled start of each round.
+fn round_tip_offs(tournament: &Tournament) -> BTreeMap<RoundKind, DateTime<Utc>> {
+    let mut out: BTreeMap<RoundKind, DateTime<Utc>> = BTreeMap::new();
+    for round in tournament.regions.iter().flat_map(|r| &r.rounds) {
+        for start in round.games.iter().filter_map(|g| g.start_time) {
+            out.entry(round.kind)
+                .and_modify(|at| *at = (*at).min(start))
+                .or_insert(start);
+        }
+    }
+    out
+}
+
+fn game_rounds(tournament: &Tournament) -> HashMap<&str, RoundKind> {
+    tournament
+        .regions
+        .iter()
+        .flat_map(|r| &r.rounds)
+        .flat_map(|round| round.games.iter().map(|g| (g.id.as_str(), round.kind)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ncaa_api::{Game, Region, Round};
+
+    fn at(s: &str) -> DateTime<Utc> {
+        s.parse().unwrap()
+    }
+
+    fn tournament() -> Tournament {
+        let game = |id: &str, start: &str| Game {
+            id: id.into(),
+            start_time: Some(at(start)),
+            ..Default::default()
+        };
+        Tournament {
+            year: 2025,
+            regions: vec![Region {
+                name: "East".into(),
+                rounds: vec![
+                    Round {
+                        kind: RoundKind::First,
+                        games: vec![game("r1", "2025-03-20T16:00:00Z")],
+                    },
+                    Round {
+                        kind: RoundKind::Second,
+                        games: vec![game("r2", "2025-03-22T16:00:00Z")],
+                    },
+                ],
+                ..Default::default()
+            }],
+            ..Default::default()
+        }
+    }
+
+    fn trusting(key: &SecretKey) -> SealPolicy {
+        let pubkey = key.public_key(&Secp256k1::new());
+        SealPolicy {
+            signers: Some([("alice".to_string(), pubkey)].into()),
+            allow_unsealed: false,
+        }
+    }
+
+    fn picks() -> BracketPicks {
+        BracketPicks {
+            user_id: "alice".into(),
+            year: 2025,
+            selections: [("r1", "duke"), ("r2", "duke")]
+                .map(|(g, t)| (g.to_string(), t.to_string()))
+                .into(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn sealed_before_tip_off_passes_untouched() {
+        let key = SecretKey::from_slice(&[7; 32]).unwrap();
+        let mut p = picks();
+        seal(&mut p, at("2025-03-19T12:00:00Z"), Some(&key));
+
+        // Round-trip through JSON like a shared picks file.
+        let p: BracketPicks = serde_json::from_str(&serde_json::to_string(&p).unwrap()).unwrap();
+        let (checked, note) = check(p, &tournament(), &trusting(&key)).unwrap();
+        assert_eq!(checked.selections.len(), 2);
+        assert_eq!(note.as_deref(), Some("submission time unverified"));
+    }
+
+    #[test]
+    fn edited_or_forged_entries_are_rejected() {
+        let mut p = picks();
+        seal(&mut p, at("2025-03-19T12:00:00Z"), None);
+        let mut edited = p.clone();
+        edited.selections.insert("r2".into(), "unc".into());
+        assert!(check(edited, &tournament(), &SealPolicy::default()).is_err());
+
+        let key = SecretKey::from_slice(&[7; 32]).unwrap();
+        let other = SecretKey::from_slice(&[9; 32]).unwrap();
+        let mut signed = picks();
+        seal(&mut signed, at("2025-03-19T12:00:00Z"), Some(&key));
+        let mut forged = picks();
+        seal(&mut forged, at("2025-03-19T12:00:00Z"), Some(&other));
+        let mut swapped = signed.clone();
+        swapped.signature.as_mut().unwrap().signature = forged.signature.clone().unwrap().signature;
+        assert!(check(swapped, &tournament(), &SealPolicy::default()).is_err());
+
+        // Swapping pubkey and signature together yields a valid signature by
+        // the wrong key: only the trusted signer list catches it.
+        signed.signature = forged.signature;
+        assert!(check(signed.clone(), &tournament(), &SealPolicy::default()).is_ok());
+        assert!(check(signed, &tournament(), &trusting(&key)).is_err());
+    }
+
+    #[test]
+    fn unsealed_entries_are_flagged_unless_refused() {
+        let policy = SealPolicy::default();
+        let (checked, note) = check(picks(), &tournament(), &policy).unwrap();
+        assert_eq!(checked.selections.len(), 2);
+        assert!(note.unwrap().contains("unsealed"));
+        let policy = SealPolicy {
+            allow_unsealed: false,
+            ..policy
+        };
+        assert_eq!(
+            check(picks(), &tournament(), &policy).unwrap_err(),
+            "unsealed"
+        );
+
+        let key = SecretKey::from_slice(&[7; 32]).unwrap();
+        let mut unsigned = picks();
+        seal(&mut unsigned, at("2025-03-19T12:00:00Z"), None);
+        assert_eq!(
+            check(unsigned, &tournament(), &trusting(&key)).unwrap_err(),
+            "not signed"
+        );
+    }
+
+    #[test]
+    fn picks_sealed_under_another_key_are_not_a_sealed_copy() {
+        let key = SecretKey::from_slice(&[7; 32]).unwrap();
+        let other = SecretKey::from_slice(&[9; 32]).unwrap();
+        let mut saved = picks();
+        seal(&mut saved, at("2025-03-19T12:00:00Z"), Some(&other));
+        assert!(is_sealed_copy(&saved, &picks(), Some(&other)));
+        assert!(!is_sealed_copy(&saved, &picks(), Some(&key)));
+        assert!(!is_sealed_copy(&saved, &picks(), None));
+
+        // Sealed before a key was configured.
+        let mut unsigned = picks();
+        seal(&mut unsigned, at("2025-03-19T12:00:00Z"), None);
+        assert!(is_sealed_copy(&unsigned, &picks(), None));
+        assert!(!is_sealed_copy(&unsigned, &picks(), Some(&key)));
+    }
+
+    #[test]
+    fn signers_file_maps_users_to_keys() {
+        let key = SecretKey::from_slice(&[7; 32]).unwrap();
+        let pubkey = key.public_key(&Secp256k1::new());
+        let signers = parse_signers(&format!("# pool\nalice {pubkey}\n\n")).unwrap();
+        assert_eq!(signers.get("alice"), Some(&pubkey));
+        assert!(parse_signers("alice").is_err());
+        assert!(parse_signers("alice nope").is_err());
+    }
+
+    #[test]
+    fn picks_for_rounds_already_started_are_dropped() {
+        let mut p = picks();
+        seal(&mut p, at("2025-03-21T12:00:00Z"), None);
+        let (checked, note) = check(p, &tournament(), &SealPolicy::default()).unwrap();
+        assert_eq!(checked.selections.keys().collect::<Vec<_>>(), ["r2"]);
+        assert!(note.unwrap().contains("1st Round"));
+    }
+}
@@ -0,0 +1,380 @@
+//! Big moments posted into chat: tip-offs, halftimes, finals, upsets and
+//! buzzer-beaters.
+//!
+//! Scoreboard refreshes are diffed game by game; buzzer-beaters come from the
+//! play-by-play of the game open in the live feed. [`Announcer`] makes sure
+//! each event is announced once per game. With broadcasting on, an
+//! announcement is sent to the room as one of your own messages instead.
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+
+use ncaa_api::{Game, GameDetail, GameStatus, Gender, Team, TeamSeed, Tournament};
+
+use crate::state::app_state::LivePlay;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EventKind {
+    TipOff,
+    Halftime,
+    Final,
+    Upset,
+    BuzzerBeater,
+}
+
+impl EventKind {
+    pub const ALL: [EventKind; 5] = [
+        EventKind::TipOff,
+        EventKind::Halftime,
+        EventKind::Final,
+        EventKind::Upset,
+        EventKind::BuzzerBeater,
+    ];
+
+    pub fn slug(self) -> &'static str {
+        match self {
+            EventKind::TipOff => "tipoff",
+            EventKind::Halftime => "halftime",
+            EventKind::Final => "final",
+            EventKind::Upset => "upset",
+            EventKind::BuzzerBeater => "buzzer",
+        }
+    }
+}
+
+impl FromStr for EventKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim().to_lowercase().replace(['-', '_'], "");
+        EventKind::ALL
+            .into_iter()
+            .find(|kind| kind.slug() == s)
+            .ok_or_else(|| format!("unknown announcement {s}"))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnnounceSettings {
+    pub events: HashSet<EventKind>,
+    /// How many seed lines apart the winner must be to call it an upset.
+    pub upset_seed_gap: u8,
+    /// A lead change with at most this much left in the second half or
+    /// overtime is a buzzer-beater.
+    pub buzzer_secs: f32,
+    /// Also send announcements to the chat room.
+    pub broadcast: bool,
+}
+
+impl Default for AnnounceSettings {
+    fn default() -> Self {
+        Self {
+            events: EventKind::ALL.into(),
+            upset_seed_gap: 1,
+            buzzer_secs: 5.0,
+            broadcast: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GameEvent {
+    pub game_id: String,
+    pub kind: EventKind,
+    pub text: String,
+}
+
+/// Events already posted, so each is announced once per game.
+#[derive(Debug, Default)]
+pub struct Announcer {
+    announced: HashSet<(String, EventKind)>,
+}
+
+impl Announcer {
+    /// Drop disabled and already-posted events.
+    pub fn fresh(&mut self, events: Vec<GameEvent>, settings: &AnnounceSettings) -> Vec<GameEvent> {
+        events
+            .into_iter()
+            .filter(|e| settings.events.contains(&e.kind))
+            .filter(|e| self.announced.insert((e.game_id.clone(), e.kind)))
+            .collect()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Scoreboard
+// ---------------------------------------------------------------------------
+
+/// Compare each game with how it looked before the refresh.
+pub fn scoreboard_events(
+    before: &HashMap<String, Game>,
+    tournament: &Tournament,
+    settings: &AnnounceSettings,
+) -> Vec<GameEvent> {
+    let mut out = Vec::new();
+    for game in tournament.games() {
+        let Some(old) = before.get(&game.id) else {
+            continue;
+        };
+        let event = |kind, text| GameEvent {
+            game_id: game.id.clone(),
+            kind,
+            text,
+        };
+        if old.status == GameStatus::Scheduled && game.status == GameStatus::InProgress {
+            out.push(event(
+                EventKind::TipOff,
+                format!("Tip-off: {} vs {}", seeded(&game.top), seeded(&game.bottom)),
+            ));
+        }
+        if is_halftime(game, tournament.gender) && !is_halftime(old, tournament.gender) {
+            out.push(event(
+                EventKind::Halftime,
+                format!("Halftime: {}", score(game)),
+            ));
+        }
+        if old.status != GameStatus::Final && game.status == GameStatus::Final {
+            out.push(event(EventKind::Final, format!("Final: {}", score(game))));
+            if let Some(text) = upset(game, settings.upset_seed_gap) {
+                out.push(event(EventKind::Upset, text));
+            }
+        }
+    }
+    out
+}
+
+/// End of the first half (the second quarter in the women's game);
+/// scoreboards show the clock run out or "Halftime".
+fn is_halftime(game: &Game, gender: Gender) -> bool {
+    let clock = game.clock.as_deref().unwrap_or_default();
+    game.status == GameStatus::InProgress
+        && game.period == Some(gender.halftime_period())
+        && (clock_secs(clock) == Some(0.0) || clock.to_lowercase().contains("half"))
+}
+
+fn upset(game: &Game, seed_gap: u8) -> Option<String> {
+    let winner_id = game.winner_id.as_deref()?;
+    let (winner, loser, winner_score, loser_score) =
+        if game.top.team.as_ref().map(|t| t.id.as_str()) == Some(winner_id) {
+            let (w, l) = game.score.unwrap_or_default();
+            (&game.top, &game.bottom, w, l)
+        } else {
+            let (l, w) = game.score.unwrap_or_default();
+            (&game.bottom, &game.top, w, l)
+        };
+    (winner.seed >= loser.seed.saturating_add(seed_gap.max(1)) && loser.seed > 0).then(|| {
+        format!(
+            "Upset! {} beats {} {winner_score}-{loser_score}",
+            seeded(winner),
+            seeded(loser)
+        )
+    })
+}
+
+fn seeded(slot: &TeamSeed) -> String {
+    format!("({}) {}", slot.seed, name(slot.team.as_ref()))
+}
+
+fn score(game: &Game) -> String {
+    let (top, bottom) = game.score.unwrap_or_default();
+    format!(
+        "{} {top} - {bottom} {}",
+        name(game.top.team.as_ref()),
+        name(game.bottom.team.as_ref())
+    )
+}
+
+fn name(team: Option<&Team>) -> &str {
+    team.map(|t| t.short_name.as_str()).unwrap_or("TBD")
+}
+
+// ---------------------------------------------------------------------------
+// Play-by-play
+// ---------------------------------------------------------------------------
+
+/// A newly arrived play that flips the lead late in the game: the last
+/// period of regulation or overtime. `plays` is the live feed in game order.
+pub fn buzzer_beater(
+    detail: &GameDetail,
+    plays: &[LivePlay],
+    gender: Gender,
+    settings: &AnnounceSettings,
+) -> Option<GameEvent> {
+    let leader = |p: &LivePlay| p.home_score.cmp(&p.away_score);
+    plays.windows(2).rev().find_map(|pair| {
+        let (prev, play) = (&pair[0], &pair[1]);
+        let late = play.period >= gender.regulation_periods()
+            && clock_secs(&play.clock).is_some_and(|secs| secs <= settings.buzzer_secs);
+        let flipped = leader(play) != leader(prev) && leader(play).is_ne();
+        (play.is_new && late && flipped).then(|| {
+            let (team, lead, trail) = if play.home_score > play.away_score {
+                (&detail.home_box.team, play.home_score, play.away_score)
+            } else {
+                (&detail.away_box.team, play.away_score, play.home_score)
+            };
+            GameEvent {
+                game_id: detail.game_id.clone(),
+                kind: EventKind::BuzzerBeater,
+                text: format!(
+                    "Buzzer-beater! {} lead {lead}-{trail} with {} left: {}",
+                    name(team.as_ref()),
+                    play.clock,
+                    play.description
+                ),
+            }
+        })
+    })
+}
+
+/// `"1:05"` or `"4.2"` as seconds.
+pub fn clock_secs(clock: &str) -> Option<f32> {
+    match clock.trim().split_once(':') {
+        Some((mins, secs)) => Some(mins.parse::<f32>().ok()? * 60.0 + secs.parse::<f32>().ok()?),
+        None => clock.trim().parse().ok(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ncaa_api::{BoxScore, Region, Round, RoundKind};
+
+    fn seeded_team(seed: u8, id: &str) -> TeamSeed {
+        TeamSeed {
+            seed,
+            team: Some(Team {
+                id: id.into(),
+                short_name: id.to_uppercase(),
+                ..Default::default()
+            }),
+            placeholder: None,
+        }
+    }
+
+    fn tournament(game: Game) -> Tournament {
+        Tournament {
+            regions: vec![Region {
+                rounds: vec![Round {
+                    kind: RoundKind::First,
+                    games: vec![game],
+                }],
+                ..Default::default()
+            }],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn scoreboard_changes_become_events() {
+        let scheduled = Game {
+            id: "g1".into(),
+            top: seeded_team(5, "clem"),
+            bottom: seeded_team(12, "mcn"),
+            ..Default::default()
+        };
+        let halftime = Game {
+            status: GameStatus::InProgress,
+            period: Some(1),
+            clock: Some("0:00".into()),
+            score: Some((30, 35)),
+            ..scheduled.clone()
+        };
+        let final_game = Game {
+            status: GameStatus::Final,
+            score: Some((67, 69)),
+            winner_id: Some("mcn".into()),
+            ..halftime.clone()
+        };
+        let settings = AnnounceSettings::default();
+        let before = HashMap::from([("g1".to_string(), scheduled)]);
+        let kinds = |events: Vec<GameEvent>| events.into_iter().map(|e| e.kind).collect::<Vec<_>>();
+
+        let events = scoreboard_events(&before, &tournament(halftime.clone()), &settings);
+        assert_eq!(kinds(events), [EventKind::TipOff, EventKind::Halftime]);
+
+        let before = HashMap::from([("g1".to_string(), halftime)]);
+        let events = scoreboard_events(&before, &tournament(final_game.clone()), &settings);
+        assert_eq!(events[1].text, "Upset! (12) MCN beats (5) CLEM 69-67");
+
+        let picky = AnnounceSettings {
+            upset_seed_gap: 8,
+            ..Default::default()
+        };
+        let events = scoreboard_events(&before, &tournament(final_game), &picky);
+        assert_eq!(kinds(events), [EventKind::Final]);
+    }
+
+    #[test]
+    fn late_lead_change_is_a_buzzer_beater_once() {
+        let play = |clock: &str, home, away, is_new| LivePlay {
+            period: 2,
+            clock: clock.into(),
+            description: "Jumper".into(),
+            home_score: home,
+            away_score: away,
+            is_new,
+            ..Default::default()
+        };
+        let detail = GameDetail {
+            game_id: "g1".into(),
+            home_box: BoxScore {
+                team: seeded_team(1, "duke").team,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let settings = AnnounceSettings::default();
+
+        let early = [play("0:30", 68, 70, false), play("0:20", 71, 70, true)];
+        assert_eq!(buzzer_beater(&detail, &early, Gender::Men, &settings), None);
+
+        let late = [play("0:09", 68, 70, false), play("2.1", 71, 70, true)];
+        let event = buzzer_beater(&detail, &late, Gender::Men, &settings).unwrap();
+        assert!(event.text.starts_with("Buzzer-beater! DUKE lead 71-70"));
+
+        let mut announcer = Announcer::default();
+        assert_eq!(announcer.fresh(vec![event.clone()], &settings).len(), 1);
+        assert!(announcer.fresh(vec![event], &settings).is_empty());
+    }
+
+    #[test]
+    fn womens_games_break_at_the_second_quarter_and_end_in_the_fourth() {
+        let quarter = |period, clock: &str| Game {
+            id: "g1".into(),
+            status: GameStatus::InProgress,
+            period: Some(period),
+            clock: Some(clock.into()),
+            top: seeded_team(1, "scar"),
+            bottom: seeded_team(4, "tex"),
+            ..Default::default()
+        };
+        let womens = |game| Tournament {
+            gender: Gender::Women,
+            ..tournament(game)
+        };
+        let settings = AnnounceSettings::default();
+        let before = HashMap::from([("g1".to_string(), quarter(1, "0:05"))]);
+        assert!(scoreboard_events(&before, &womens(quarter(1, "0:00")), &settings).is_empty());
+        let events = scoreboard_events(&before, &womens(quarter(2, "0:00")), &settings);
+        assert_eq!(events[0].kind, EventKind::Halftime);
+
+        let detail = GameDetail {
+            game_id: "g1".into(),
+            ..Default::default()
+        };
+        let play = |period, home, away| LivePlay {
+            period,
+            clock: "1.5".into(),
+            home_score: home,
+            away_score: away,
+            is_new: true,
+            ..Default::default()
+        };
+        let second = [play(2, 30, 32), play(2, 33, 32)];
+        assert_eq!(
+            buzzer_beater(&detail, &second, Gender::Women, &settings),
+            None
+        );
+        let fourth = [play(4, 60, 62), play(4, 63, 62)];
+        assert!(buzzer_beater(&detail, &fourth, Gender::Women, &settings).is_some());
+    }
+}
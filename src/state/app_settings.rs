@@ -1,14 +1,573 @@
+//! Settings resolved once at startup: environment variables override
+//! `config.toml`, which overrides the defaults.
+//!
+//! The config file lives at `~/.config/mmtui/config.toml` (or `--config`).
+//! Every key is optional and unknown keys are rejected, so a typo is reported
+//! instead of silently ignored.
+use crate::components::banner_frames::BannerTheme;
+use crate::state::announce::{AnnounceSettings, EventKind};
+use crate::state::follow::{Alert, FollowSettings, Follows};
+use bitcoin::secp256k1::SecretKey;
 use log::LevelFilter;
+use mmtui::chat_protocol::DEFAULT_ROOM;
+use mmtui::seal::{self, SealPolicy};
+use ncaa_api::Gender;
+use ncaa_api::client::NcaaApi;
+use ncaa_api::scoring::ScoringRule;
+use ncaa_api::source::{ESPN_URL, HENRYGD_URL, SourceKind};
+use serde::{Deserialize, Deserializer};
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
 
-#[derive(Debug, Default, Clone)]
+/// Scores are polled no more often than this.
+const MIN_REFRESH_SECS: u64 = 5;
+
+#[derive(Debug, Clone)]
 pub struct AppSettings {
     pub full_screen: bool,
     pub log_level: Option<LevelFilter>,
+    /// Men's or women's tournament shown at startup.
+    pub gender: Gender,
+    /// Local tournament snapshot loaded instead of the live bracket.
+    pub bracket_json: Option<String>,
+    /// Where brackets and scores come from.
+    pub sources: SourceSettings,
+    /// How often scores are refreshed.
+    pub refresh: Duration,
+    pub theme: BannerTheme,
+    /// Relay connection and identity for the Chat tab.
+    pub chat: ChatSettings,
+    /// Extra Compare sources: files or URLs.
+    pub compare_sources: Vec<String>,
+    /// Prize pool custodian public keys, used when `custodians.json` has
+    /// fewer than two.
+    pub prize_pool_keys: Vec<String>,
+    /// Points system used by the Compare leaderboard.
+    pub scoring_rule: ScoringRule,
+    /// Optional team ratings for pool simulation (`MMTUI_RATINGS`); seeds are
+    /// used when unset.
+    pub ratings_path: Option<PathBuf>,
+    /// Pool server base URL (`MMTUI_POOL_URL`) for submitting picks; its
+    /// entries are also added to Compare.
+    pub pool_url: Option<String>,
+    /// Hex secp256k1 key (`MMTUI_PICKS_KEY`) used to sign saved picks.
+    pub picks_key: Option<SecretKey>,
+    /// Trusted signers (`MMTUI_PICKS_SIGNERS`) and whether unsealed Compare
+    /// entries are scored (`MMTUI_ALLOW_UNSEALED_PICKS`, default true).
+    pub seal_policy: SealPolicy,
+    /// Which game events are posted to chat, and when (`MMTUI_ANNOUNCE*`).
+    pub announce: AnnounceSettings,
+    /// Followed teams and how their games notify (`MMTUI_FOLLOW`,
+    /// `MMTUI_NOTIFY*`).
+    pub follow: FollowSettings,
+}
+
+impl Default for AppSettings {
+    fn default() -> Self {
+        Self {
+            full_screen: false,
+            log_level: None,
+            gender: Gender::default(),
+            bracket_json: None,
+            sources: SourceSettings::default(),
+            refresh: Duration::from_secs(30),
+            theme: BannerTheme::default(),
+            chat: ChatSettings::default(),
+            compare_sources: Vec::new(),
+            prize_pool_keys: Vec::new(),
+            scoring_rule: ScoringRule::default(),
+            ratings_path: None,
+            pool_url: None,
+            picks_key: None,
+            seal_policy: SealPolicy::default(),
+            announce: AnnounceSettings::default(),
+            follow: FollowSettings::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChatSettings {
+    pub endpoint: String,
+    pub room: String,
+    pub username: String,
+    /// Shared room password.
+    pub password: Option<String>,
+    /// Relay admin token; enables `/mute` etc.
+    pub admin_token: Option<String>,
+}
+
+impl Default for ChatSettings {
+    fn default() -> Self {
+        Self {
+            endpoint: "ws://127.0.0.1:8787".to_string(),
+            room: DEFAULT_ROOM.to_string(),
+            username: std::env::var("USER")
+                .ok()
+                .filter(|u| !u.trim().is_empty())
+                .unwrap_or_else(|| "fan".to_string()),
+            password: None,
+            admin_token: None,
+        }
+    }
 }
 
+/// Bracket fallback order and API base URLs, for mirrors and local
+/// stand-in servers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceSettings {
+    pub bracket_order: Vec<SourceKind>,
+    pub henrygd_url: String,
+    pub espn_url: String,
+}
+
+impl Default for SourceSettings {
+    fn default() -> Self {
+        Self {
+            bracket_order: SourceKind::DEFAULT_ORDER.to_vec(),
+            henrygd_url: HENRYGD_URL.to_string(),
+            espn_url: ESPN_URL.to_string(),
+        }
+    }
+}
+
+/// Looks up an environment variable, treating blank values as unset.
+type Env<'a> = &'a dyn Fn(&str) -> Option<String>;
+
 impl AppSettings {
-    pub fn load() -> Self {
-        // Simple defaults — log level can be overridden via env var RUST_LOG in the future.
-        Self { full_screen: false, log_level: None }
+    /// Read `path`, or `config.toml` in the config directory when it exists,
+    /// and apply the environment on top. Every invalid value is reported, one
+    /// per line.
+    pub fn load(path: Option<&Path>) -> Result<Self, String> {
+        let file = match path {
+            Some(path) => ConfigFile::read(path, true)?,
+            None => ConfigFile::read(&config_file("config.toml"), false)?,
+        };
+        Self::resolve(file, &|name| {
+            std::env::var(name).ok().filter(|v| !v.trim().is_empty())
+        })
+    }
+
+    /// API client for `gender`'s tournament. The bracket snapshot, if any,
+    /// belongs to the startup tournament.
+    pub fn api(&self, gender: Gender) -> NcaaApi {
+        let sources = &self.sources;
+        NcaaApi::with_sources(
+            gender,
+            &sources.bracket_order,
+            &sources.henrygd_url,
+            &sources.espn_url,
+        )
+        .with_bracket_json(self.bracket_json.clone().filter(|_| gender == self.gender))
+    }
+
+    fn resolve(file: ConfigFile, env: Env) -> Result<Self, String> {
+        let mut errors = Vec::new();
+        let defaults = Self::default();
+
+        let refresh = match env_parse(env, "MMTUI_REFRESH_SECS", &mut errors).or(file.refresh_secs)
+        {
+            Some(secs) if secs < MIN_REFRESH_SECS => {
+                errors.push(format!(
+                    "refresh interval must be at least {MIN_REFRESH_SECS} seconds, got {secs}"
+                ));
+                defaults.refresh
+            }
+            Some(secs) => Duration::from_secs(secs),
+            None => defaults.refresh,
+        };
+
+        let chat = ChatSettings {
+            endpoint: env("MMTUI_CHAT_WS")
+                .or(file.chat.url)
+                .unwrap_or(defaults.chat.endpoint),
+            room: env("MMTUI_CHAT_ROOM")
+                .or(file.chat.room)
+                .unwrap_or(defaults.chat.room),
+            username: env("MMTUI_USERNAME")
+                .or(file.username)
+                .unwrap_or(defaults.chat.username),
+            password: env("MMTUI_CHAT_PASSWORD").or(file.chat.password),
+            admin_token: env("MMTUI_CHAT_ADMIN_TOKEN").or(file.chat.admin_token),
+        };
+
+        let bracket_order = match list(env("MMTUI_BRACKET_SOURCES")) {
+            Some(names) => Some(("MMTUI_BRACKET_SOURCES", names)),
+            None => file.sources.order.map(|names| ("sources.order", names)),
+        };
+        let sources = SourceSettings {
+            bracket_order: match bracket_order {
+                Some((name, names)) => names
+                    .iter()
+                    .filter_map(|n| {
+                        n.parse()
+                            .map_err(|e| errors.push(format!("{name}: {e}")))
+                            .ok()
+                    })
+                    .collect(),
+                None => defaults.sources.bracket_order,
+            },
+            henrygd_url: env("MMTUI_HENRYGD_URL")
+                .or(file.sources.henrygd_url)
+                .unwrap_or(defaults.sources.henrygd_url),
+            espn_url: env("MMTUI_ESPN_URL")
+                .or(file.sources.espn_url)
+                .unwrap_or(defaults.sources.espn_url),
+        };
+
+        let teams = list(env("MMTUI_FOLLOW"))
+            .or(file.follow)
+            .unwrap_or_default();
+        let follow = FollowSettings {
+            teams: Follows::new(teams.into_iter().chain(file.follow_txt)),
+            alert: env_parse(env, "MMTUI_NOTIFY", &mut errors)
+                .or(file.notify.alert)
+                .unwrap_or_default(),
+            hook: env("MMTUI_NOTIFY_HOOK").or(file.notify.hook),
+            close_margin: env_parse(env, "MMTUI_NOTIFY_CLOSE_MARGIN", &mut errors)
+                .or(file.notify.close_margin)
+                .unwrap_or(defaults.follow.close_margin),
+        };
+
+        let announce_events = match list(env("MMTUI_ANNOUNCE")) {
+            Some(names) => Some(("MMTUI_ANNOUNCE", names)),
+            None => file.announce.events.map(|names| ("announce.events", names)),
+        };
+        let announce = AnnounceSettings {
+            events: match announce_events {
+                Some((name, names)) => names
+                    .iter()
+                    .filter(|n| !n.eq_ignore_ascii_case("none"))
+                    .filter_map(|n| {
+                        n.parse::<EventKind>()
+                            .map_err(|e| errors.push(format!("{name}: {e}")))
+                            .ok()
+                    })
+                    .collect(),
+                None => defaults.announce.events,
+            },
+            upset_seed_gap: env_parse(env, "MMTUI_ANNOUNCE_UPSET_SEEDS", &mut errors)
+                .or(file.announce.upset_seeds)
+                .unwrap_or(defaults.announce.upset_seed_gap),
+            buzzer_secs: env_parse(env, "MMTUI_ANNOUNCE_BUZZER_SECS", &mut errors)
+                .or(file.announce.buzzer_secs)
+                .unwrap_or(defaults.announce.buzzer_secs),
+            broadcast: env_flag(env, "MMTUI_ANNOUNCE_BROADCAST", &mut errors)
+                .or(file.announce.broadcast)
+                .unwrap_or(defaults.announce.broadcast),
+        };
+
+        let signers = env("MMTUI_PICKS_SIGNERS")
+            .map(PathBuf::from)
+            .or(file.picks_signers)
+            .and_then(|path| {
+                seal::load_signers(&path)
+                    .map_err(|e| errors.push(format!("picks signers: {e}")))
+                    .ok()
+            });
+        let seal_policy = SealPolicy {
+            signers,
+            allow_unsealed: env_parse(env, "MMTUI_ALLOW_UNSEALED_PICKS", &mut errors)
+                .or(file.allow_unsealed_picks)
+                .unwrap_or(defaults.seal_policy.allow_unsealed),
+        };
+
+        let settings = Self {
+            log_level: env_parse(env, "MMTUI_LOG_LEVEL", &mut errors).or(file.log_level),
+            gender: env_parse(env, "MMTUI_TOURNAMENT", &mut errors)
+                .or(file.tournament)
+                .unwrap_or_default(),
+            bracket_json: env("MMTUI_BRACKET_JSON").or(file.bracket_json),
+            sources,
+            refresh,
+            theme: env_parse(env, "MMTUI_THEME", &mut errors)
+                .or(file.theme)
+                .unwrap_or_default(),
+            chat,
+            compare_sources: list(env("MMTUI_COMPARE_SOURCES"))
+                .or(file.compare_sources)
+                .unwrap_or_default(),
+            prize_pool_keys: list(env("MMTUI_PRIZE_POOL_KEYS"))
+                .or(file.prize_pool_keys)
+                .unwrap_or_default(),
+            scoring_rule: env_parse(env, "MMTUI_SCORING_RULE", &mut errors)
+                .or(file.scoring_rule)
+                .unwrap_or_default(),
+            ratings_path: env("MMTUI_RATINGS").map(PathBuf::from).or(file.ratings),
+            pool_url: env("MMTUI_POOL_URL")
+                .or(file.pool_url)
+                .map(|u| u.trim().trim_end_matches('/').to_string())
+                .filter(|u| !u.is_empty()),
+            picks_key: env_parse(env, "MMTUI_PICKS_KEY", &mut errors),
+            seal_policy,
+            announce,
+            follow,
+            ..defaults
+        };
+
+        if errors.is_empty() {
+            Ok(settings)
+        } else {
+            Err(errors.join("\n"))
+        }
+    }
+}
+
+/// `name` from the environment, parsed; a bad value is added to `errors`.
+fn env_parse<T>(env: Env, name: &str, errors: &mut Vec<String>) -> Option<T>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    let raw = env(name)?;
+    raw.trim()
+        .parse()
+        .map_err(|e| errors.push(format!("{name}: {e}")))
+        .ok()
+}
+
+/// An on/off switch from the environment: `1`, `true`, `yes` or `on`, or
+/// their opposites; anything else is added to `errors`.
+fn env_flag(env: Env, name: &str, errors: &mut Vec<String>) -> Option<bool> {
+    let raw = env(name)?;
+    match raw.trim().to_lowercase().as_str() {
+        "1" | "true" | "yes" | "on" => Some(true),
+        "0" | "false" | "no" | "off" => Some(false),
+        other => {
+            errors.push(format!("{name}: expected on or off, got {other}"));
+            None
+        }
+    }
+}
+
+/// A comma-separated list, without blanks.
+fn list(raw: Option<String>) -> Option<Vec<String>> {
+    raw.map(|raw| {
+        raw.split(',')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(ToString::to_string)
+            .collect()
+    })
+}
+
+// ---------------------------------------------------------------------------
+// config.toml
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ConfigFile {
+    #[serde(deserialize_with = "parsed")]
+    log_level: Option<LevelFilter>,
+    #[serde(deserialize_with = "parsed")]
+    tournament: Option<Gender>,
+    bracket_json: Option<String>,
+    refresh_secs: Option<u64>,
+    #[serde(deserialize_with = "parsed")]
+    theme: Option<BannerTheme>,
+    username: Option<String>,
+    #[serde(deserialize_with = "parsed")]
+    scoring_rule: Option<ScoringRule>,
+    ratings: Option<PathBuf>,
+    pool_url: Option<String>,
+    picks_signers: Option<PathBuf>,
+    allow_unsealed_picks: Option<bool>,
+    compare_sources: Option<Vec<String>>,
+    prize_pool_keys: Option<Vec<String>>,
+    follow: Option<Vec<String>>,
+    chat: ChatSection,
+    notify: NotifySection,
+    announce: AnnounceSection,
+    sources: SourcesSection,
+    /// Teams from `follow.txt`, always added to the follow list.
+    #[serde(skip)]
+    follow_txt: Vec<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ChatSection {
+    url: Option<String>,
+    room: Option<String>,
+    password: Option<String>,
+    admin_token: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct SourcesSection {
+    order: Option<Vec<String>>,
+    henrygd_url: Option<String>,
+    espn_url: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct NotifySection {
+    #[serde(deserialize_with = "parsed")]
+    alert: Option<Alert>,
+    hook: Option<String>,
+    close_margin: Option<u16>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct AnnounceSection {
+    events: Option<Vec<String>>,
+    upset_seeds: Option<u8>,
+    buzzer_secs: Option<f32>,
+    broadcast: Option<bool>,
+}
+
+impl ConfigFile {
+    /// A missing file is only an error when it was asked for by name.
+    fn read(path: &Path, required: bool) -> Result<Self, String> {
+        let mut file: Self = match std::fs::read_to_string(path) {
+            Ok(text) => toml::from_str(&text).map_err(|e| format!("{}: {e}", path.display()))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound && !required => Self::default(),
+            Err(e) => return Err(format!("could not read {}: {e}", path.display())),
+        };
+        if let Ok(content) = std::fs::read_to_string(config_file("follow.txt")) {
+            file.follow_txt = content
+                .lines()
+                .map(|line| line.split('#').next().unwrap_or_default().to_string())
+                .collect();
+        }
+        Ok(file)
+    }
+}
+
+/// A string read with the type's `FromStr`, so the file accepts the same
+/// spellings as the environment.
+fn parsed<'de, D, T>(de: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr,
+    T::Err: Display,
+{
+    let raw = String::deserialize(de)?;
+    raw.parse().map(Some).map_err(serde::de::Error::custom)
+}
+
+/// `~/.config/mmtui/<name>`, honouring `XDG_CONFIG_HOME`; the working
+/// directory when neither is set.
+pub fn config_file(name: &str) -> PathBuf {
+    if let Ok(config_dir) = std::env::var("XDG_CONFIG_HOME")
+        && !config_dir.trim().is_empty()
+    {
+        return PathBuf::from(config_dir).join("mmtui").join(name);
+    }
+    if let Ok(home) = std::env::var("HOME")
+        && !home.trim().is_empty()
+    {
+        return PathBuf::from(home).join(".config").join("mmtui").join(name);
+    }
+    PathBuf::from(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn env(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        move |name| vars.get(name).cloned()
+    }
+
+    const CONFIG: &str = r#"
+        refresh_secs = 60
+        tournament = "women"
+        theme = "light"
+        username = "alice"
+        scoring_rule = "fibonacci"
+        compare_sources = ["brackets/", "https://example.com/picks.json"]
+        follow = ["Duke", "UNC"]
+
+        [chat]
+        url = "wss://chat.example.com"
+        room = "office-pool"
+
+        [notify]
+        alert = "osc9"
+
+        [announce]
+        events = ["final", "upset"]
+        buzzer_secs = 3.0
+
+        [sources]
+        order = ["espn", "embedded"]
+        espn_url = "http://127.0.0.1:9000"
+    "#;
+
+    #[test]
+    fn environment_overrides_file_overrides_defaults() {
+        let file: ConfigFile = toml::from_str(CONFIG).unwrap();
+        let settings = AppSettings::resolve(
+            file,
+            &env(&[
+                ("MMTUI_CHAT_ROOM", "east"),
+                ("MMTUI_FOLLOW", "gonzaga"),
+                ("MMTUI_ANNOUNCE", "final"),
+                ("MMTUI_ANNOUNCE_BROADCAST", "1"),
+            ]),
+        )
+        .unwrap();
+
+        assert_eq!(settings.refresh, Duration::from_secs(60));
+        assert_eq!(settings.gender, Gender::Women);
+        assert_eq!(settings.theme, BannerTheme::Light);
+        assert_eq!(settings.scoring_rule, ScoringRule::Fibonacci);
+        assert_eq!(settings.compare_sources.len(), 2);
+        assert_eq!(settings.chat.endpoint, "wss://chat.example.com");
+        assert_eq!(settings.chat.room, "east");
+        assert_eq!(settings.chat.username, "alice");
+        assert_eq!(settings.follow.alert, Alert::Osc9);
+        assert_eq!(settings.follow.teams, Follows::new(["gonzaga".to_string()]));
+        assert_eq!(settings.follow.close_margin, 5);
+        assert_eq!(
+            settings.sources.bracket_order,
+            [SourceKind::Espn, SourceKind::Embedded]
+        );
+        assert_eq!(settings.sources.espn_url, "http://127.0.0.1:9000");
+        assert_eq!(settings.sources.henrygd_url, HENRYGD_URL);
+        assert_eq!(settings.announce.events, [EventKind::Final].into());
+        assert_eq!(settings.announce.buzzer_secs, 3.0);
+        assert_eq!(settings.announce.upset_seed_gap, 1);
+        assert!(settings.announce.broadcast);
+    }
+
+    #[test]
+    fn invalid_values_are_all_reported() {
+        let typo = toml::from_str::<ConfigFile>("refresh_sec = 60").unwrap_err();
+        assert!(typo.to_string().contains("refresh_sec"));
+        let bad_rule = toml::from_str::<ConfigFile>(r#"scoring_rule = "vibes""#).unwrap_err();
+        assert!(bad_rule.to_string().contains("unknown scoring rule"));
+
+        let file: ConfigFile = toml::from_str("refresh_secs = 1").unwrap();
+        let errors = AppSettings::resolve(
+            file,
+            &env(&[
+                ("MMTUI_THEME", "neon"),
+                ("MMTUI_BRACKET_SOURCES", "espn,mirror"),
+                ("MMTUI_ANNOUNCE", "final,dunk"),
+                ("MMTUI_ANNOUNCE_BROADCAST", "maybe"),
+                ("MMTUI_PICKS_KEY", "not-hex"),
+            ]),
+        )
+        .unwrap_err();
+        assert_eq!(errors.lines().count(), 6);
+        assert!(errors.contains("MMTUI_PICKS_KEY: "));
+        assert!(errors.contains("MMTUI_ANNOUNCE: unknown announcement dunk"));
+        assert!(errors.contains("MMTUI_ANNOUNCE_BROADCAST: expected on or off"));
+        assert!(errors.contains("MMTUI_BRACKET_SOURCES: unknown bracket source mirror"));
+        assert!(errors.contains("MMTUI_THEME: unknown theme"));
+        assert!(errors.contains("at least 5 seconds"));
     }
 }
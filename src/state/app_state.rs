@@ -1,9 +1,19 @@
 use crate::app::MenuItem;
+use crate::state::announce::Announcer;
+use crate::state::app_settings::ChatSettings;
+use crate::state::chat::{ChatCommand, ChatWireMessage};
 use crate::state::custodian::{CustodianEntry, CustodianWizardState};
+use crate::state::follow::Notifier;
+use bitcoin::secp256k1::SecretKey;
 use chrono::Local;
-use ncaa_api::{GameDetail, RoundKind, TeamSeed, Tournament};
-use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet};
+use mmtui::chat_protocol::Authenticity;
+use ncaa_api::scenarios::EntrantPath;
+use ncaa_api::{
+    GameDetail, Gender, PlayerLine, RoundKind, Team, TeamSeed, Tournament, made_attempted,
+};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+pub use ncaa_api::BracketPicks;
 
 // ---------------------------------------------------------------------------
 // Banner animation state
@@ -41,6 +51,9 @@ pub struct BracketState {
     pub selected_game: usize,
     /// Vertical scroll offset for when games exceed terminal height.
     pub scroll_offset: u16,
+    /// Show all four regions and the Final Four as one tree instead of a
+    /// single round.
+    pub full_view: bool,
 }
 
 impl BracketState {
@@ -72,13 +85,24 @@ impl BracketState {
     }
 
     pub fn navigate_round_prev(&mut self) {
-        if let Some(prev) = self.view_round.prev() {
+        if let Some(prev) = self.view_round.prev()
+            // The First Four isn't part of the full-bracket tree.
+            && !(self.full_view && prev == RoundKind::FirstFour)
+        {
             self.view_round = prev;
             self.selected_game = 0;
             self.scroll_offset = 0;
         }
     }
 
+    pub fn toggle_full_view(&mut self) {
+        self.full_view = !self.full_view;
+        if self.full_view && self.view_round == RoundKind::FirstFour {
+            self.view_round = RoundKind::First;
+            self.selected_game = 0;
+        }
+    }
+
     pub fn navigate_game_down(&mut self) {
         let max = self.games_in_view().saturating_sub(1);
         if self.selected_game < max {
@@ -190,6 +214,147 @@ fn detect_active_round(tournament: &Tournament) -> RoundKind {
 pub struct GameDetailState {
     pub detail: Option<GameDetail>,
     pub scroll_offset: u16,
+    pub tab: DetailTab,
+    /// Column the Box Score player tables are sorted by.
+    pub sort: PlayerSort,
+}
+
+impl GameDetailState {
+    /// Step through the tabs, wrapping at either end.
+    pub fn cycle_tab(&mut self, forward: bool) {
+        self.tab = cycle(&DetailTab::ALL, self.tab, forward);
+        self.scroll_offset = 0;
+    }
+
+    pub fn cycle_sort(&mut self, forward: bool) {
+        self.sort = cycle(&PlayerSort::ALL, self.sort, forward);
+    }
+}
+
+fn cycle<T: Copy + PartialEq>(all: &[T], current: T, forward: bool) -> T {
+    let idx = all.iter().position(|t| *t == current).unwrap_or(0);
+    let next = if forward {
+        (idx + 1) % all.len()
+    } else {
+        (idx + all.len() - 1) % all.len()
+    };
+    all[next]
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DetailTab {
+    #[default]
+    BoxScore,
+    PlayByPlay,
+    TeamStats,
+}
+
+impl DetailTab {
+    pub const ALL: [DetailTab; 3] = [
+        DetailTab::BoxScore,
+        DetailTab::PlayByPlay,
+        DetailTab::TeamStats,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            DetailTab::BoxScore => "Box Score",
+            DetailTab::PlayByPlay => "Play-by-Play",
+            DetailTab::TeamStats => "Team Stats",
+        }
+    }
+}
+
+/// Box Score sort column. `Lineup` keeps ESPN's order; every other column
+/// sorts highest first.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PlayerSort {
+    #[default]
+    Lineup,
+    Minutes,
+    Points,
+    FieldGoals,
+    Threes,
+    FreeThrows,
+    OffensiveRebounds,
+    DefensiveRebounds,
+    Rebounds,
+    Assists,
+    Steals,
+    Blocks,
+    Turnovers,
+    Fouls,
+    PlusMinus,
+}
+
+impl PlayerSort {
+    pub const ALL: [PlayerSort; 15] = [
+        PlayerSort::Lineup,
+        PlayerSort::Minutes,
+        PlayerSort::Points,
+        PlayerSort::FieldGoals,
+        PlayerSort::Threes,
+        PlayerSort::FreeThrows,
+        PlayerSort::OffensiveRebounds,
+        PlayerSort::DefensiveRebounds,
+        PlayerSort::Rebounds,
+        PlayerSort::Assists,
+        PlayerSort::Steals,
+        PlayerSort::Blocks,
+        PlayerSort::Turnovers,
+        PlayerSort::Fouls,
+        PlayerSort::PlusMinus,
+    ];
+
+    /// Column header.
+    pub fn label(self) -> &'static str {
+        match self {
+            PlayerSort::Lineup => "Lineup",
+            PlayerSort::Minutes => "MIN",
+            PlayerSort::Points => "PTS",
+            PlayerSort::FieldGoals => "FG",
+            PlayerSort::Threes => "3PT",
+            PlayerSort::FreeThrows => "FT",
+            PlayerSort::OffensiveRebounds => "OREB",
+            PlayerSort::DefensiveRebounds => "DREB",
+            PlayerSort::Rebounds => "REB",
+            PlayerSort::Assists => "AST",
+            PlayerSort::Steals => "STL",
+            PlayerSort::Blocks => "BLK",
+            PlayerSort::Turnovers => "TO",
+            PlayerSort::Fouls => "PF",
+            PlayerSort::PlusMinus => "+/-",
+        }
+    }
+
+    /// Shooting columns sort by makes.
+    fn key(self, p: &PlayerLine) -> i32 {
+        let made = |stat: &str| made_attempted(stat).map_or(0, |(m, _)| m as i32);
+        match self {
+            PlayerSort::Lineup => 0,
+            PlayerSort::Minutes => p.minutes.parse().unwrap_or(0),
+            PlayerSort::Points => p.points.into(),
+            PlayerSort::FieldGoals => made(&p.fg),
+            PlayerSort::Threes => made(&p.fg3),
+            PlayerSort::FreeThrows => made(&p.ft),
+            PlayerSort::OffensiveRebounds => p.offensive_rebounds.into(),
+            PlayerSort::DefensiveRebounds => p.defensive_rebounds.into(),
+            PlayerSort::Rebounds => p.rebounds.into(),
+            PlayerSort::Assists => p.assists.into(),
+            PlayerSort::Steals => p.steals.into(),
+            PlayerSort::Blocks => p.blocks.into(),
+            PlayerSort::Turnovers => p.turnovers.into(),
+            PlayerSort::Fouls => p.fouls.into(),
+            PlayerSort::PlusMinus => p.plus_minus.map_or(i32::MIN, i32::from),
+        }
+    }
+
+    /// Ties keep box score order.
+    pub fn sorted<'a>(self, players: impl Iterator<Item = &'a PlayerLine>) -> Vec<&'a PlayerLine> {
+        let mut players: Vec<_> = players.collect();
+        players.sort_by_key(|p| std::cmp::Reverse(self.key(p)));
+        players
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -203,12 +368,17 @@ pub struct ChatMessage {
     pub body: String,
     pub timestamp: String,
     pub is_system: bool,
+    /// Signed by the key this author first signed with.
+    pub verified: bool,
+    /// Where one of our own messages is on its way to the relay.
+    pub delivery: Option<Delivery>,
 }
 
-#[derive(Debug, Clone)]
-pub struct OutboundChatMessage {
-    pub id: String,
-    pub body: String,
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Delivery {
+    Sending,
+    Delivered,
+    Failed,
 }
 
 #[derive(Debug)]
@@ -221,15 +391,41 @@ pub struct ChatState {
     pub room: String,
     pub connected: bool,
     pub endpoint: String,
+    /// Who the relay says is in the room.
+    pub members: Vec<String>,
+    /// Signs outgoing messages (`MMTUI_CHAT_KEY`, hex secp256k1).
+    pub signing_key: Option<SecretKey>,
+    /// Shared room password.
+    pub password: Option<String>,
+    /// Relay admin token; enables `/mute` etc.
+    pub admin_token: Option<String>,
+    /// Game announcements waiting to be broadcast to the room.
+    pub outbox: Vec<ChatCommand>,
+    /// Author → first public key seen signing as them.
+    known_keys: HashMap<String, String>,
     seen_ids: HashSet<String>,
 }
 
+/// Invalid keys are ignored and messages go out unsigned.
+fn chat_key_from_env() -> Option<SecretKey> {
+    let raw = std::env::var("MMTUI_CHAT_KEY").ok()?;
+    match raw.trim().parse() {
+        Ok(key) => Some(key),
+        Err(e) => {
+            log::warn!("MMTUI_CHAT_KEY ignored: {e}");
+            None
+        }
+    }
+}
+
 impl Default for ChatState {
     fn default() -> Self {
-        let username = std::env::var("USER")
-            .ok()
-            .filter(|u| !u.trim().is_empty())
-            .unwrap_or_else(|| "fan".to_string());
+        Self::new(&ChatSettings::default())
+    }
+}
+
+impl ChatState {
+    pub fn new(settings: &ChatSettings) -> Self {
         Self {
             messages: vec![ChatMessage {
                 id: "system-init".to_string(),
@@ -237,49 +433,91 @@ impl Default for ChatState {
                 body: "Chat starting... connecting to relay.".to_string(),
                 timestamp: Local::now().format("%H:%M").to_string(),
                 is_system: true,
+                verified: false,
+                delivery: None,
             }],
             input: String::new(),
             composing: false,
             scroll_offset: 0,
-            username,
-            room: std::env::var("MMTUI_CHAT_ROOM").unwrap_or_else(|_| "march-madness".to_string()),
+            username: settings.username.clone(),
+            room: settings.room.clone(),
             connected: false,
-            endpoint: std::env::var("MMTUI_CHAT_WS")
-                .unwrap_or_else(|_| "ws://127.0.0.1:8787".to_string()),
+            endpoint: settings.endpoint.clone(),
+            members: Vec::new(),
+            signing_key: chat_key_from_env(),
+            password: settings.password.clone(),
+            admin_token: settings.admin_token.clone(),
+            outbox: Vec::new(),
+            known_keys: HashMap::new(),
             seen_ids: HashSet::new(),
         }
     }
-}
 
-impl ChatState {
-    pub fn submit_input(&mut self) -> Option<OutboundChatMessage> {
-        let msg = self.input.trim();
-        if msg.is_empty() {
-            self.composing = false;
-            self.input.clear();
-            return None;
-        }
-        let message = OutboundChatMessage {
-            id: format!(
-                "{}-{}",
-                self.username,
-                Local::now()
-                    .timestamp_nanos_opt()
-                    .unwrap_or_else(|| Local::now().timestamp_micros() * 1000)
-            ),
-            body: msg.to_string(),
-        };
+    /// Whether a relayed message is authentic, pinning the author's key the
+    /// first time they sign. `None` means the signature failed.
+    pub fn verify(&mut self, msg: &ChatWireMessage) -> Option<bool> {
+        match (msg.authenticity(), &msg.pubkey) {
+            (Authenticity::Invalid, _) => None,
+            (Authenticity::Valid, Some(key)) => {
+                let pinned = self
+                    .known_keys
+                    .entry(msg.author.clone())
+                    .or_insert_with(|| key.clone());
+                Some(pinned == key)
+            }
+            _ => Some(false),
+        }
+    }
+
+    /// Close the composer and hand back what was typed, if anything.
+    pub fn take_input(&mut self) -> Option<String> {
+        let input = self.input.trim().to_string();
+        self.composing = false;
+        self.input.clear();
+        (!input.is_empty()).then_some(input)
+    }
+
+    /// Echo `body` locally and build the command that sends it.
+    pub fn say(&mut self, body: String) -> ChatCommand {
+        let message_id = format!(
+            "{}-{}",
+            self.username,
+            Local::now()
+                .timestamp_nanos_opt()
+                .unwrap_or_else(|| Local::now().timestamp_micros() * 1000)
+        );
         self.ingest_message(ChatMessage {
-            id: message.id.clone(),
+            id: message_id.clone(),
             author: self.username.clone(),
-            body: message.body.clone(),
+            body: body.clone(),
             timestamp: Local::now().format("%H:%M").to_string(),
             is_system: false,
+            verified: self.signing_key.is_some(),
+            delivery: Some(Delivery::Sending),
         });
         self.scroll_offset = 0;
-        self.composing = false;
-        self.input.clear();
-        Some(message)
+        ChatCommand::Send { body, message_id }
+    }
+
+    /// Switch room and/or name; the relay replays the new room's history.
+    pub fn rejoin(&mut self, room: String, username: String) -> ChatCommand {
+        if room != self.room {
+            self.members.clear();
+            self.push_system(format!("joining {room}"));
+        }
+        if username != self.username {
+            self.push_system(format!("you are now {username}"));
+        }
+        self.room = room.clone();
+        self.username = username.clone();
+        ChatCommand::Join { room, username }
+    }
+
+    /// Record what the relay did with one of our messages.
+    pub fn set_delivery(&mut self, id: &str, delivery: Delivery) {
+        if let Some(msg) = self.messages.iter_mut().rev().find(|m| m.id == id) {
+            msg.delivery = Some(delivery);
+        }
     }
 
     pub fn ingest_message(&mut self, msg: ChatMessage) {
@@ -289,6 +527,10 @@ impl ChatState {
         if !msg.id.is_empty() {
             self.seen_ids.insert(msg.id.clone());
         }
+        self.push(msg);
+    }
+
+    fn push(&mut self, msg: ChatMessage) {
         self.messages.push(msg);
         if self.messages.len() > 200 {
             let remove_count = self.messages.len() - 200;
@@ -296,6 +538,20 @@ impl ChatState {
         }
     }
 
+    /// Post a game announcement locally. The announcer has already made sure
+    /// each one appears once; ids from the relay play no part in it.
+    pub fn announce(&mut self, body: String) {
+        self.push(ChatMessage {
+            id: format!("announce-{}", Local::now().timestamp_millis()),
+            author: "📣".to_string(),
+            body,
+            timestamp: Local::now().format("%H:%M").to_string(),
+            is_system: true,
+            verified: false,
+            delivery: None,
+        });
+    }
+
     pub fn push_system(&mut self, body: impl Into<String>) {
         let body = body.into();
         if let Some(last) = self.messages.last()
@@ -310,6 +566,8 @@ impl ChatState {
             body,
             timestamp: Local::now().format("%H:%M").to_string(),
             is_system: true,
+            verified: false,
+            delivery: None,
         });
     }
 }
@@ -325,13 +583,57 @@ pub struct LivePlay {
     pub description: String,
     pub away_score: u16,
     pub home_score: u16,
+    pub team_id: Option<String>,
+    pub scoring: bool,
+    /// The lead passed from one team to the other on this play.
+    pub lead_change: bool,
     pub is_new: bool,
 }
 
+/// Fewest unanswered points that make a run.
+pub const RUN_MIN_POINTS: u16 = 8;
+
+/// Points scored by one side while the other didn't score.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Run {
+    pub home: bool,
+    pub team_id: Option<String>,
+    pub points: u16,
+    /// Indices into `LiveFeedState::plays` of the run's first and last baskets.
+    pub start: usize,
+    pub end: usize,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FeedView {
+    #[default]
+    All,
+    Scoring,
+    LeadChanges,
+}
+
+impl FeedView {
+    pub const ALL: [FeedView; 3] = [FeedView::All, FeedView::Scoring, FeedView::LeadChanges];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            FeedView::All => "all plays",
+            FeedView::Scoring => "scoring plays",
+            FeedView::LeadChanges => "lead changes",
+        }
+    }
+}
+
 #[derive(Debug, Default)]
 pub struct LiveFeedState {
     pub game_id: Option<String>,
     pub plays: Vec<LivePlay>,
+    /// The game's teams, top slot first, for the team filter.
+    pub teams: Vec<Team>,
+    /// Only show plays by this team ID.
+    pub team_filter: Option<String>,
+    pub view: FeedView,
+    pub runs: Vec<Run>,
 }
 
 impl LiveFeedState {
@@ -342,23 +644,158 @@ impl LiveFeedState {
             .map(|p| play_key(p.period, &p.clock, &p.description, p.away_score, p.home_score))
             .collect();
 
+        if self.game_id.as_deref() != Some(detail.game_id.as_str()) {
+            self.team_filter = None;
+        }
         self.game_id = Some(detail.game_id.clone());
+        self.teams = [detail.top_box(), detail.bottom_box()]
+            .into_iter()
+            .filter_map(|b| b.team.clone())
+            .collect();
+
+        let mut score = (0, 0);
+        let mut leader = None;
         self.plays = detail
             .plays
             .iter()
             .map(|p| {
-                let key = play_key(p.period, &p.clock, &p.description, p.away_score, p.home_score);
+                let key = play_key(
+                    p.period,
+                    &p.clock,
+                    &p.description,
+                    p.away_score,
+                    p.home_score,
+                );
+                // Older recordings have no scoring flag; the score moving says as much.
+                let scoring = p.scoring || (p.home_score, p.away_score) != score;
+                score = (p.home_score, p.away_score);
+                let now = match p.home_score.cmp(&p.away_score) {
+                    std::cmp::Ordering::Greater => Some(true),
+                    std::cmp::Ordering::Less => Some(false),
+                    std::cmp::Ordering::Equal => None,
+                };
+                let lead_change = now.is_some() && leader.is_some() && now != leader;
+                if now.is_some() {
+                    leader = now;
+                }
                 LivePlay {
                     period: p.period,
                     clock: p.clock.clone(),
                     description: p.description.clone(),
                     away_score: p.away_score,
                     home_score: p.home_score,
+                    team_id: p.team_id.clone(),
+                    scoring,
+                    lead_change,
                     is_new: !prev_keys.contains(&key),
                 }
             })
             .collect();
+        self.runs = detect_runs(&self.plays);
+    }
+
+    pub fn cycle_view(&mut self) {
+        self.view = cycle(&FeedView::ALL, self.view, true);
+    }
+
+    /// All teams → top team → bottom team → all teams.
+    pub fn cycle_team_filter(&mut self) {
+        let idx = self
+            .team_filter
+            .as_ref()
+            .and_then(|id| self.teams.iter().position(|t| &t.id == id));
+        let next = match idx {
+            None => 0,
+            Some(i) => i + 1,
+        };
+        self.team_filter = self.teams.get(next).map(|t| t.id.clone());
+    }
+
+    pub fn team_name(&self, team_id: Option<&str>) -> Option<&str> {
+        let id = team_id?;
+        self.teams
+            .iter()
+            .find(|t| t.id == id)
+            .map(|t| t.short_name.as_str())
+    }
+
+    /// Plays the view and team filter let through, with their index in `plays`.
+    pub fn visible(&self) -> impl DoubleEndedIterator<Item = (usize, &LivePlay)> {
+        self.plays.iter().enumerate().filter(|(_, p)| {
+            let by_team = self
+                .team_filter
+                .as_ref()
+                .is_none_or(|id| p.team_id.as_ref() == Some(id));
+            let by_view = match self.view {
+                FeedView::All => true,
+                FeedView::Scoring => p.scoring,
+                FeedView::LeadChanges => p.lead_change,
+            };
+            by_team && by_view
+        })
+    }
+
+    /// The run whose last basket is play `idx`.
+    pub fn run_ending_at(&self, idx: usize) -> Option<&Run> {
+        self.runs.iter().find(|r| r.end == idx)
+    }
+
+    pub fn in_run(&self, idx: usize) -> bool {
+        self.runs.iter().any(|r| (r.start..=r.end).contains(&idx))
+    }
+}
+
+/// Find every stretch of at least [`RUN_MIN_POINTS`] unanswered points.
+/// Who scored is read from the running score, so plays without a team ID
+/// still count.
+fn detect_runs(plays: &[LivePlay]) -> Vec<Run> {
+    let mut runs = Vec::new();
+    let mut current: Option<Run> = None;
+    let mut flush = |run: Option<Run>| {
+        if let Some(run) = run.filter(|r| r.points >= RUN_MIN_POINTS) {
+            runs.push(run);
+        }
+    };
+
+    let (mut home, mut away) = (0u16, 0u16);
+    for (idx, play) in plays.iter().enumerate() {
+        let scored = (
+            play.home_score.saturating_sub(home),
+            play.away_score.saturating_sub(away),
+        );
+        (home, away) = (play.home_score, play.away_score);
+        let (side, points) = match scored {
+            (0, 0) => continue,
+            (pts, 0) => (true, pts),
+            (0, pts) => (false, pts),
+            // Both moved at once (a score correction): no run survives it.
+            _ => {
+                flush(current.take());
+                continue;
+            }
+        };
+        match current.as_mut() {
+            Some(run) if run.home == side => {
+                run.points += points;
+                run.end = idx;
+                if run.team_id.is_none() {
+                    run.team_id = play.team_id.clone();
+                }
+            }
+            _ => {
+                flush(current.take());
+                current = Some(Run {
+                    home: side,
+                    team_id: play.team_id.clone(),
+                    points,
+                    start: idx,
+                    end: idx,
+                });
+            }
+        }
     }
+    flush(current);
+    runs
 }
 
 fn play_key(period: u8, clock: &str, desc: &str, away: u16, home: u16) -> String {
@@ -366,7 +803,7 @@ fn play_key(period: u8, clock: &str, desc: &str, away: u16, home: u16) -> String
 }
 
 // ---------------------------------------------------------------------------
-// Pick wizard state (currently pinned to 2025 bracket template)
+// Pick wizard state (built from whichever tournament year is loaded)
 // ---------------------------------------------------------------------------
 
 #[derive(Debug, Clone)]
@@ -377,39 +814,45 @@ pub struct WizardGame {
     pub bottom_label: String,
     pub top_team_id: Option<String>,
     pub bottom_team_id: Option<String>,
+    /// Earlier-round game whose picked winner fills the top slot.
+    pub top_feeder: Option<String>,
+    /// Earlier-round game whose picked winner fills the bottom slot.
+    pub bottom_feeder: Option<String>,
+    /// Slot contents from the loaded bracket, shown until the feeder is picked.
+    top_seed: TeamSeed,
+    bottom_seed: TeamSeed,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct BracketPicks {
-    pub user_id: String,
-    pub year: u16,
-    pub selections: HashMap<String, String>,
+impl WizardGame {
+    /// Selection value recorded when the top slot is picked. Slots without a
+    /// known team fall back to a positional `top:{game_id}` key.
+    pub fn top_pick_id(&self) -> String {
+        self.top_team_id
+            .clone()
+            .unwrap_or_else(|| format!("top:{}", self.game_id))
+    }
+
+    pub fn bottom_pick_id(&self) -> String {
+        self.bottom_team_id
+            .clone()
+            .unwrap_or_else(|| format!("bottom:{}", self.game_id))
+    }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct PickWizardState {
     pub year: u16,
+    pub gender: Gender,
     pub games: Vec<WizardGame>,
     pub current_index: usize,
     pub selections: HashMap<String, String>,
     pub completed: bool,
 }
 
-impl Default for PickWizardState {
-    fn default() -> Self {
-        Self {
-            year: 2025,
-            games: Vec::new(),
-            current_index: 0,
-            selections: HashMap::new(),
-            completed: false,
-        }
-    }
-}
-
 impl PickWizardState {
-    pub fn load_from_tournament_2025_template(&mut self, tournament: &Tournament) {
-        self.year = 2025;
+    pub fn load_from_tournament(&mut self, tournament: &Tournament) {
+        self.year = tournament.year;
+        self.gender = tournament.gender;
         self.games.clear();
         self.current_index = 0;
         self.selections.clear();
@@ -426,39 +869,30 @@ impl PickWizardState {
         ];
 
         for round in round_order {
-            if round.is_final_four() {
-                if let Some(national) = tournament.regions.iter().find(|r| r.name == "National") {
-                    for r in &national.rounds {
-                        if r.kind == round {
-                            for g in &r.games {
-                                self.games.push(WizardGame {
-                                    game_id: g.id.clone(),
-                                    round,
-                                    top_label: format_seed_team(&g.top),
-                                    bottom_label: format_seed_team(&g.bottom),
-                                    top_team_id: g.top.team.as_ref().map(|t| t.id.clone()),
-                                    bottom_team_id: g.bottom.team.as_ref().map(|t| t.id.clone()),
-                                });
-                            }
-                        }
-                    }
-                }
-            } else {
-                for region in tournament.regions.iter().filter(|r| r.name != "National") {
-                    for r in &region.rounds {
-                        if r.kind == round {
-                            for g in &r.games {
-                                self.games.push(WizardGame {
-                                    game_id: g.id.clone(),
-                                    round,
-                                    top_label: format_seed_team(&g.top),
-                                    bottom_label: format_seed_team(&g.bottom),
-                                    top_team_id: g.top.team.as_ref().map(|t| t.id.clone()),
-                                    bottom_team_id: g.bottom.team.as_ref().map(|t| t.id.clone()),
-                                });
-                            }
-                        }
-                    }
+            let regions = tournament
+                .regions
+                .iter()
+                .filter(|r| (r.name == "National") == round.is_final_four());
+            for region in regions {
+                for g in region
+                    .rounds
+                    .iter()
+                    .filter(|r| r.kind == round)
+                    .flat_map(|r| &r.games)
+                {
+                    let (top_feeder, bottom_feeder) = tournament.slot_feeders(g);
+                    self.games.push(WizardGame {
+                        game_id: g.id.clone(),
+                        round,
+                        top_label: format_seed_team(&g.top),
+                        bottom_label: format_seed_team(&g.bottom),
+                        top_team_id: g.top.team.as_ref().map(|t| t.id.clone()),
+                        bottom_team_id: g.bottom.team.as_ref().map(|t| t.id.clone()),
+                        top_feeder,
+                        bottom_feeder,
+                        top_seed: g.top.clone(),
+                        bottom_seed: g.bottom.clone(),
+                    });
                 }
             }
         }
@@ -469,23 +903,25 @@ impl PickWizardState {
     }
 
     pub fn select_top(&mut self) {
-        if let Some(g) = self.current_game().cloned() {
-            let winner = g.top_team_id.unwrap_or_else(|| format!("top:{}", g.game_id));
-            self.selections.insert(g.game_id, winner);
-            self.advance();
+        if let Some(g) = self.current_game() {
+            let (game_id, winner) = (g.game_id.clone(), g.top_pick_id());
+            self.select(game_id, winner);
         }
     }
 
     pub fn select_bottom(&mut self) {
-        if let Some(g) = self.current_game().cloned() {
-            let winner = g
-                .bottom_team_id
-                .unwrap_or_else(|| format!("bottom:{}", g.game_id));
-            self.selections.insert(g.game_id, winner);
-            self.advance();
+        if let Some(g) = self.current_game() {
+            let (game_id, winner) = (g.game_id.clone(), g.bottom_pick_id());
+            self.select(game_id, winner);
         }
     }
 
+    fn select(&mut self, game_id: String, winner: String) {
+        self.selections.insert(game_id, winner);
+        self.cascade();
+        self.advance();
+    }
+
     pub fn advance(&mut self) {
         if self.current_index + 1 < self.games.len() {
             self.current_index += 1;
@@ -504,18 +940,91 @@ impl PickWizardState {
             user_id,
             year: self.year,
             selections: self.selections.clone(),
+            ..Default::default()
         }
     }
 
     pub fn apply_saved_selections(&mut self, selections: HashMap<String, String>) {
         self.selections = selections;
-        self.completed = self.games.iter().all(|g| self.selections.contains_key(&g.game_id));
+        self.cascade();
+        self.completed = self
+            .games
+            .iter()
+            .all(|g| self.selections.contains_key(&g.game_id));
         self.current_index = self
             .games
             .iter()
             .position(|g| !self.selections.contains_key(&g.game_id))
             .unwrap_or_else(|| self.games.len().saturating_sub(1));
     }
+
+    /// Carry picked winners forward into later rounds and drop picks that no
+    /// longer name one of the game's two teams.
+    ///
+    /// Games are stored in round order, so every feeder is resolved before the
+    /// game it feeds; a changed early pick therefore clears each downstream
+    /// pick that depended on the old team.
+    fn cascade(&mut self) {
+        let index: HashMap<String, usize> = self
+            .games
+            .iter()
+            .enumerate()
+            .map(|(i, g)| (g.game_id.clone(), i))
+            .collect();
+
+        for i in 0..self.games.len() {
+            let game = &self.games[i];
+            let top = self.resolve_slot(&index, game.top_feeder.as_deref(), &game.top_seed);
+            let bottom =
+                self.resolve_slot(&index, game.bottom_feeder.as_deref(), &game.bottom_seed);
+
+            let game = &mut self.games[i];
+            (game.top_label, game.top_team_id) = top;
+            (game.bottom_label, game.bottom_team_id) = bottom;
+
+            let valid = [game.top_pick_id(), game.bottom_pick_id()];
+            if let Some(pick) = self.selections.get(&game.game_id)
+                && !valid.contains(pick)
+            {
+                self.selections.remove(&game.game_id);
+            }
+        }
+
+        if self
+            .games
+            .iter()
+            .any(|g| !self.selections.contains_key(&g.game_id))
+        {
+            self.completed = false;
+        }
+    }
+
+    /// Label and team ID for one slot: the feeder game's picked side when the
+    /// user has picked it, otherwise whatever the loaded bracket shows.
+    fn resolve_slot(
+        &self,
+        index: &HashMap<String, usize>,
+        feeder: Option<&str>,
+        seed: &TeamSeed,
+    ) -> (String, Option<String>) {
+        let picked = feeder.and_then(|id| {
+            let feeder = &self.games[*index.get(id)?];
+            let pick = self.selections.get(id)?;
+            if *pick == feeder.top_pick_id() {
+                Some((feeder.top_label.clone(), feeder.top_team_id.clone()))
+            } else if *pick == feeder.bottom_pick_id() {
+                Some((feeder.bottom_label.clone(), feeder.bottom_team_id.clone()))
+            } else {
+                None
+            }
+        });
+        picked.unwrap_or_else(|| {
+            (
+                format_seed_team(seed),
+                seed.team.as_ref().map(|t| t.id.clone()),
+            )
+        })
+    }
 }
 
 fn format_seed_team(seed: &TeamSeed) -> String {
@@ -545,14 +1054,67 @@ pub struct CompareRow {
     pub max_points: u32,
     pub correct: u32,
     pub total: u32,
+    /// Points earned per round under the active scoring rule.
+    pub round_points: BTreeMap<RoundKind, u32>,
+    /// Chance of finishing first, from the last pool simulation.
+    pub win_probability: Option<f64>,
+    /// Expected final points, from the last pool simulation.
+    pub expected_points: Option<f64>,
+    /// The picks carry no seal, so their submission time is unknown.
+    pub unsealed: bool,
 }
 
 #[derive(Debug, Default)]
 pub struct CompareState {
     pub rows: Vec<CompareRow>,
+    /// Loaded picks by source, kept for pool simulation.
+    pub entries: Vec<(String, BracketPicks)>,
+    /// Sources are being fetched by the network worker.
+    pub loading: bool,
+    pub simulating: bool,
+    /// Bumped on every rescore; odds or paths computed for an older one are
+    /// stale.
+    pub generation: u64,
+    /// Drill-down panel for the selected row.
+    pub detail_open: bool,
+    pub analyzing: bool,
+    /// Path analysis by source, or why it is unavailable.
+    pub paths: Option<Result<Vec<(String, EntrantPath)>, String>>,
     pub last_loaded_at: Option<String>,
     pub source_errors: Vec<String>,
-    pub scroll_offset: u16,
+    /// Index into `rows` of the highlighted entry.
+    pub selected: usize,
+}
+
+impl CompareState {
+    pub fn selected_row(&self) -> Option<&CompareRow> {
+        self.rows.get(self.selected)
+    }
+
+    pub fn select_next(&mut self) {
+        if self.selected + 1 < self.rows.len() {
+            self.selected += 1;
+        }
+    }
+
+    pub fn select_previous(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    /// Replace the rows, keeping the selection on the same source when it is
+    /// still present.
+    pub fn set_rows(&mut self, rows: Vec<CompareRow>) {
+        let source = self.selected_row().map(|row| row.source.clone());
+        self.selected = source
+            .and_then(|source| rows.iter().position(|row| row.source == source))
+            .unwrap_or(0);
+        self.rows = rows;
+    }
+
+    /// First row to draw so the selection fits in `visible` rows.
+    pub fn first_visible(&self, visible: usize) -> usize {
+        (self.selected + 1).saturating_sub(visible.max(1))
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -585,6 +1147,11 @@ pub struct AppState {
     pub show_intro: bool,
     pub show_logs: bool,
     pub last_error: Option<String>,
+    /// Tournament on screen.
+    pub gender: Gender,
+    /// Tournament asked for but not yet confirmed by the network worker;
+    /// data for the old one is dropped meanwhile.
+    pub pending_gender: Option<Gender>,
     pub bracket: BracketState,
     pub game_detail: GameDetailState,
     pub live_feed: LiveFeedState,
@@ -594,6 +1161,8 @@ pub struct AppState {
     pub prize_pool: PrizePoolState,
     pub custodian_wizard: CustodianWizardState,
     pub animation: AnimationState,
+    pub announcer: Announcer,
+    pub notifier: Notifier,
 }
 
 impl AppState {
@@ -604,3 +1173,252 @@ impl AppState {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ncaa_api::{Game, Region, Round, Team};
+
+    fn seeded(seed: u8, id: &str) -> TeamSeed {
+        TeamSeed {
+            seed,
+            team: Some(Team {
+                id: id.into(),
+                short_name: id.to_uppercase(),
+                ..Default::default()
+            }),
+            placeholder: None,
+        }
+    }
+
+    fn game(id: &str, top: TeamSeed, bottom: TeamSeed) -> Game {
+        Game {
+            id: id.into(),
+            top,
+            bottom,
+            ..Default::default()
+        }
+    }
+
+    fn tournament() -> Tournament {
+        let tbd = TeamSeed::default;
+        let mut t = Tournament {
+            year: 2026,
+            regions: vec![Region {
+                name: "East".into(),
+                rounds: vec![
+                    Round {
+                        kind: RoundKind::First,
+                        games: vec![
+                            game("g1", seeded(1, "duke"), seeded(16, "amer")),
+                            game("g2", seeded(8, "msu"), seeded(9, "bay")),
+                        ],
+                    },
+                    Round {
+                        kind: RoundKind::Second,
+                        games: vec![game("g3", tbd(), tbd())],
+                    },
+                ],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        t.link_games();
+        t
+    }
+
+    #[test]
+    fn wizard_takes_year_from_loaded_tournament() {
+        let mut wizard = PickWizardState::default();
+        wizard.load_from_tournament(&tournament());
+        assert_eq!(wizard.year, 2026);
+        assert_eq!(wizard.games.len(), 3);
+    }
+
+    #[test]
+    fn picks_cascade_into_later_rounds() {
+        let mut wizard = PickWizardState::default();
+        wizard.load_from_tournament(&tournament());
+        assert_eq!(wizard.games[2].top_label, "(-) TBD");
+
+        wizard.select_top(); // g1: Duke
+        wizard.select_bottom(); // g2: Baylor
+        let g3 = wizard.current_game().unwrap();
+        assert_eq!(g3.top_label, "(1) DUKE");
+        assert_eq!(g3.bottom_label, "(9) BAY");
+        assert_eq!(g3.bottom_team_id.as_deref(), Some("bay"));
+    }
+
+    #[test]
+    fn changing_an_earlier_pick_clears_dependent_picks() {
+        let mut wizard = PickWizardState::default();
+        wizard.load_from_tournament(&tournament());
+        wizard.select_top(); // g1: Duke
+        wizard.select_bottom(); // g2: Baylor
+        wizard.select_top(); // g3: Duke
+        assert!(wizard.completed);
+
+        wizard.current_index = 0;
+        wizard.select_bottom(); // g1: American
+        assert_eq!(wizard.games[2].top_label, "(16) AMER");
+        assert_eq!(wizard.selections.get("g3"), None);
+        assert_eq!(wizard.selections.get("g2").map(String::as_str), Some("bay"));
+        assert!(!wizard.completed);
+    }
+
+    #[test]
+    fn saved_picks_for_another_bracket_are_dropped() {
+        let mut wizard = PickWizardState::default();
+        wizard.load_from_tournament(&tournament());
+        let saved = HashMap::from([
+            ("g1".to_string(), "duke".to_string()),
+            ("g3".to_string(), "gonzaga".to_string()),
+        ]);
+        wizard.apply_saved_selections(saved);
+        assert_eq!(wizard.selections.len(), 1);
+        assert_eq!(wizard.current_index, 1);
+    }
+
+    #[test]
+    fn chat_pins_the_first_key_an_author_signs_with() {
+        let signed_by = |seed: u8| {
+            let mut msg = ChatWireMessage {
+                id: format!("m{seed}"),
+                room: "march-madness".into(),
+                author: "alice".into(),
+                body: "go heels".into(),
+                timestamp: "19:05".into(),
+                ..Default::default()
+            };
+            msg.sign(&SecretKey::from_slice(&[seed; 32]).unwrap());
+            msg
+        };
+        let mut chat = ChatState::default();
+        assert_eq!(chat.verify(&signed_by(1)), Some(true));
+        assert_eq!(chat.verify(&signed_by(2)), Some(false));
+
+        let mut forged = signed_by(1);
+        forged.body = "go duke".into();
+        assert_eq!(chat.verify(&forged), None);
+    }
+
+    #[test]
+    fn player_sort_orders_highest_first_and_keeps_ties_in_lineup_order() {
+        let line = |name: &str, points: u16, fg: &str| PlayerLine {
+            name: name.into(),
+            points,
+            fg: fg.into(),
+            ..Default::default()
+        };
+        let players = [
+            line("a", 8, "3-9"),
+            line("b", 12, "5-7"),
+            line("c", 8, "4-4"),
+        ];
+        let names = |sort: PlayerSort| -> Vec<String> {
+            sort.sorted(players.iter())
+                .iter()
+                .map(|p| p.name.clone())
+                .collect()
+        };
+        assert_eq!(names(PlayerSort::Lineup), ["a", "b", "c"]);
+        assert_eq!(names(PlayerSort::Points), ["b", "a", "c"]);
+        assert_eq!(names(PlayerSort::FieldGoals), ["b", "c", "a"]);
+
+        let mut state = GameDetailState::default();
+        state.cycle_sort(false);
+        assert_eq!(state.sort, PlayerSort::PlusMinus);
+        state.scroll_offset = 5;
+        state.cycle_tab(false);
+        assert_eq!((state.tab, state.scroll_offset), (DetailTab::TeamStats, 0));
+    }
+
+    #[test]
+    fn live_feed_marks_runs_lead_changes_and_filters_by_team() {
+        use ncaa_api::{BoxScore, Play};
+        let play = |home_score: u16, away_score: u16, team: &str| Play {
+            home_score,
+            away_score,
+            team_id: Some(team.into()),
+            description: format!("{home_score}-{away_score}"),
+            ..Default::default()
+        };
+        let side = |id: &str| BoxScore {
+            team: Some(Team {
+                id: id.into(),
+                short_name: id.to_uppercase(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let detail = GameDetail {
+            game_id: "g1".into(),
+            home_box: side("duke"),
+            away_box: side("unc"),
+            plays: vec![
+                play(0, 2, "unc"),
+                play(3, 2, "duke"),
+                play(5, 2, "duke"),
+                play(5, 2, "unc"), // a miss
+                play(8, 2, "duke"),
+                play(11, 2, "duke"),
+                play(11, 4, "unc"),
+                play(11, 13, "unc"),
+            ],
+            ..Default::default()
+        };
+        let mut feed = LiveFeedState::default();
+        feed.update_from_detail(&detail);
+
+        assert_eq!(feed.runs.len(), 2);
+        assert_eq!((feed.runs[0].home, feed.runs[0].points), (true, 11));
+        assert_eq!((feed.runs[0].start, feed.runs[0].end), (1, 5));
+        assert_eq!(feed.runs[1].points, 11);
+        assert_eq!(
+            feed.run_ending_at(5).map(|r| r.team_id.as_deref()),
+            Some(Some("duke"))
+        );
+        assert!(feed.in_run(3) && !feed.in_run(0));
+
+        let lead_changes: Vec<usize> = feed
+            .plays
+            .iter()
+            .enumerate()
+            .filter(|(_, p)| p.lead_change)
+            .map(|(i, _)| i)
+            .collect();
+        assert_eq!(lead_changes, [1, 7]);
+
+        feed.cycle_view();
+        assert_eq!(feed.visible().count(), 7);
+        feed.cycle_team_filter();
+        assert_eq!(feed.team_filter.as_deref(), Some("duke"));
+        assert_eq!(feed.visible().count(), 4);
+        feed.cycle_view();
+        assert_eq!(feed.visible().map(|(i, _)| i).collect::<Vec<_>>(), [1]);
+        feed.cycle_team_filter();
+        feed.cycle_team_filter();
+        assert_eq!(feed.team_filter, None);
+    }
+
+    #[test]
+    fn compare_selection_follows_its_entry_through_a_rescore() {
+        let row = |source: &str| CompareRow {
+            user_id: source.into(),
+            source: source.into(),
+            ..Default::default()
+        };
+        let mut compare = CompareState::default();
+        compare.set_rows(vec![row("a"), row("b"), row("c")]);
+        compare.select_next();
+        compare.select_next();
+        compare.select_next();
+        assert_eq!(compare.selected_row().map(|r| r.source.as_str()), Some("c"));
+        assert_eq!(compare.first_visible(2), 1);
+
+        compare.set_rows(vec![row("c"), row("a"), row("b")]);
+        assert_eq!(compare.selected, 0);
+        compare.select_previous();
+        assert_eq!(compare.selected_row().map(|r| r.source.as_str()), Some("c"));
+    }
+}
@@ -0,0 +1,254 @@
+//! Recording and replay of live data.
+//!
+//! With `--record <archive>`, every bracket, scoreboard and game detail the
+//! network worker loads is appended to the archive as one JSON line, stamped
+//! with the milliseconds since recording started. `--replay <archive>` feeds
+//! them back through the worker on the same schedule, optionally sped up:
+//! scoreboards arrive when they did, and opening a game shows its detail as
+//! of the replay clock. Nothing is fetched for those while replaying.
+use ncaa_api::{Game, GameDetail, Tournament};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use crate::state::messages::NetworkResponse;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Frame {
+    /// Milliseconds since recording started.
+    pub at_ms: u64,
+    #[serde(flatten)]
+    pub entry: Entry,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Entry {
+    Bracket(Tournament),
+    Scoreboard(Vec<Game>),
+    GameDetail(Box<GameDetail>),
+}
+
+impl Entry {
+    /// The recordable part of a worker response.
+    pub fn from_response(response: &NetworkResponse) -> Option<Self> {
+        match response {
+            NetworkResponse::BracketLoaded { tournament } => {
+                Some(Entry::Bracket(tournament.clone()))
+            }
+            NetworkResponse::BracketUpdated { games } => Some(Entry::Scoreboard(games.clone())),
+            NetworkResponse::GameDetailLoaded { detail } => Some(Entry::GameDetail(detail.clone())),
+            _ => None,
+        }
+    }
+}
+
+/// `10x`, `10` or `0.5x`.
+pub fn parse_speed(raw: &str) -> Result<f64, String> {
+    let raw = raw.trim();
+    raw.strip_suffix(['x', 'X'])
+        .unwrap_or(raw)
+        .parse::<f64>()
+        .ok()
+        .filter(|s| s.is_finite() && *s > 0.0)
+        .ok_or_else(|| format!("invalid replay speed {raw:?} (e.g. 10x)"))
+}
+
+// ---------------------------------------------------------------------------
+// Recording
+// ---------------------------------------------------------------------------
+
+#[derive(Debug)]
+pub struct Recorder {
+    out: BufWriter<File>,
+    started: Instant,
+}
+
+impl Recorder {
+    pub fn create(path: &Path) -> Result<Self, String> {
+        let file =
+            File::create(path).map_err(|e| format!("could not create {}: {e}", path.display()))?;
+        Ok(Self {
+            out: BufWriter::new(file),
+            started: Instant::now(),
+        })
+    }
+
+    /// Append one frame; flushed right away so an interrupted session still
+    /// leaves a usable archive.
+    pub fn record(&mut self, entry: Entry) -> Result<(), String> {
+        let frame = Frame {
+            at_ms: self.started.elapsed().as_millis() as u64,
+            entry,
+        };
+        serde_json::to_writer(&mut self.out, &frame).map_err(|e| e.to_string())?;
+        self.out
+            .write_all(b"\n")
+            .and_then(|_| self.out.flush())
+            .map_err(|e| e.to_string())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Replay
+// ---------------------------------------------------------------------------
+
+#[derive(Debug)]
+pub struct Replay {
+    frames: Vec<Frame>,
+    speed: f64,
+    started: Instant,
+}
+
+impl Replay {
+    pub fn open(path: &Path, speed: f64) -> Result<Self, String> {
+        let file =
+            File::open(path).map_err(|e| format!("could not open {}: {e}", path.display()))?;
+        let mut frames = Vec::new();
+        for (i, line) in BufReader::new(file).lines().enumerate() {
+            let line = line.map_err(|e| format!("{}: {e}", path.display()))?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let frame = serde_json::from_str(&line)
+                .map_err(|e| format!("{}:{}: {e}", path.display(), i + 1))?;
+            frames.push(frame);
+        }
+        Ok(Self::new(frames, speed))
+    }
+
+    /// The replay clock starts now.
+    pub fn new(mut frames: Vec<Frame>, speed: f64) -> Self {
+        frames.sort_by_key(|f| f.at_ms);
+        Self {
+            frames,
+            speed,
+            started: Instant::now(),
+        }
+    }
+
+    pub fn started(&self) -> Instant {
+        self.started
+    }
+
+    /// Archive time the replay has reached.
+    pub fn now_ms(&self) -> u64 {
+        (self.started.elapsed().as_millis() as f64 * self.speed) as u64
+    }
+
+    /// The first bracket recorded.
+    pub fn bracket(&self) -> Option<Tournament> {
+        self.frames.iter().find_map(|f| match &f.entry {
+            Entry::Bracket(t) => Some(t.clone()),
+            _ => None,
+        })
+    }
+
+    /// The latest scoreboard recorded by `at_ms`.
+    pub fn scoreboard_at(&self, at_ms: u64) -> Option<Vec<Game>> {
+        self.frames
+            .iter()
+            .rev()
+            .filter(|f| f.at_ms <= at_ms)
+            .find_map(|f| match &f.entry {
+                Entry::Scoreboard(games) => Some(games.clone()),
+                _ => None,
+            })
+    }
+
+    /// The latest detail recorded for `game_id` by `at_ms`.
+    pub fn game_detail_at(&self, game_id: &str, at_ms: u64) -> Option<GameDetail> {
+        self.frames
+            .iter()
+            .rev()
+            .filter(|f| f.at_ms <= at_ms)
+            .find_map(|f| match &f.entry {
+                Entry::GameDetail(d) if d.game_id == game_id => Some((**d).clone()),
+                _ => None,
+            })
+    }
+
+    /// Each scoreboard with how long after the start of the replay it is due.
+    pub fn schedule(&self) -> Vec<(Duration, Vec<Game>)> {
+        self.frames
+            .iter()
+            .filter_map(|f| match &f.entry {
+                Entry::Scoreboard(games) => Some((
+                    Duration::from_secs_f64(f.at_ms as f64 / 1000.0 / self.speed),
+                    games.clone(),
+                )),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ncaa_api::GameStatus;
+
+    fn game(id: &str, status: GameStatus, score: (u16, u16)) -> Game {
+        Game {
+            id: id.into(),
+            status,
+            score: Some(score),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn recorded_frames_replay_in_order_and_scaled() {
+        let path = std::env::temp_dir().join(format!("mmtui-archive-{}.jsonl", std::process::id()));
+        let mut recorder = Recorder::create(&path).unwrap();
+        recorder
+            .record(Entry::Bracket(Tournament {
+                year: 2025,
+                ..Default::default()
+            }))
+            .unwrap();
+        recorder
+            .record(Entry::GameDetail(Box::new(GameDetail {
+                game_id: "g1".into(),
+                ..Default::default()
+            })))
+            .unwrap();
+        drop(recorder);
+
+        let mut frames = Replay::open(&path, 1.0).unwrap().frames;
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(frames.len(), 2);
+        frames.extend([
+            Frame {
+                at_ms: 60_000,
+                entry: Entry::Scoreboard(vec![game("g1", GameStatus::Final, (70, 68))]),
+            },
+            Frame {
+                at_ms: 30_000,
+                entry: Entry::Scoreboard(vec![game("g1", GameStatus::InProgress, (30, 35))]),
+            },
+        ]);
+
+        let replay = Replay::new(frames, 10.0);
+        assert_eq!(replay.bracket().unwrap().year, 2025);
+        let schedule = replay.schedule();
+        assert_eq!(schedule[0].0, Duration::from_secs(3));
+        assert_eq!(schedule[1].0, Duration::from_secs(6));
+
+        assert!(replay.scoreboard_at(10_000).is_none());
+        let halftime = replay.scoreboard_at(45_000).unwrap();
+        assert_eq!(halftime[0].score, Some((30, 35)));
+        assert!(replay.game_detail_at("g1", 0).is_some());
+        assert!(replay.game_detail_at("g2", 90_000).is_none());
+    }
+
+    #[test]
+    fn speeds_read_like_multipliers() {
+        assert_eq!(parse_speed("10x"), Ok(10.0));
+        assert_eq!(parse_speed("0.5"), Ok(0.5));
+        assert!(parse_speed("0x").is_err());
+        assert!(parse_speed("fast").is_err());
+    }
+}
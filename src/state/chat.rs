@@ -1,13 +1,28 @@
+use bitcoin::secp256k1::SecretKey;
 use chrono::Local;
 use futures_util::{SinkExt, StreamExt};
-use serde::{Deserialize, Serialize};
+pub use mmtui::chat_protocol::ChatWireMessage;
+use mmtui::chat_protocol::{ClientFrame, RelayFrame};
+use std::collections::HashMap;
 use tokio::sync::mpsc;
 use tokio::time::{Duration, sleep};
 use tokio_tungstenite::{connect_async, tungstenite::Message};
 
 #[derive(Debug, Clone)]
 pub enum ChatCommand {
-    Send { body: String, message_id: String },
+    Send {
+        body: String,
+        message_id: String,
+    },
+    /// Move to another room and/or name.
+    Join {
+        room: String,
+        username: String,
+    },
+    /// Moderation command line, sent with the admin token.
+    Admin {
+        command: String,
+    },
 }
 
 #[derive(Debug, Clone)]
@@ -15,23 +30,30 @@ pub enum ChatEvent {
     Connected,
     Disconnected,
     Message(ChatWireMessage),
+    /// Backlog replayed by the relay on join, oldest first.
+    History(Vec<ChatWireMessage>),
+    /// Everyone in the room right now.
+    Presence(Vec<String>),
+    /// Relay announcement for the room, such as a moderation action.
+    System(String),
+    /// The relay stored one of our messages.
+    Acked(String),
+    /// The relay refused one of our messages.
+    Rejected {
+        id: String,
+        reason: String,
+    },
     Error(String),
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ChatWireMessage {
-    pub id: String,
-    pub room: String,
-    pub author: String,
-    pub body: String,
-    pub timestamp: String,
-}
-
 #[derive(Debug)]
 pub struct ChatWorker {
     pub url: String,
     pub room: String,
     pub username: String,
+    pub password: Option<String>,
+    pub admin_token: Option<String>,
+    pub signing_key: Option<SecretKey>,
     pub commands: mpsc::Receiver<ChatCommand>,
     pub events: mpsc::Sender<ChatEvent>,
 }
@@ -39,6 +61,11 @@ pub struct ChatWorker {
 impl ChatWorker {
     pub async fn run(mut self) {
         let mut pending: Vec<ChatCommand> = Vec::new();
+        // Sent but not yet acked; resent after a reconnect; the relay
+        // ignores copies it already has. Joining elsewhere fails them instead.
+        let mut unacked: Vec<ClientFrame> = Vec::new();
+        // Last sequence number seen per room, to ask for only what we missed.
+        let mut last_seq: HashMap<String, u64> = HashMap::new();
         let mut retry_delay_secs: u64 = 2;
         loop {
             let mut established_connection = false;
@@ -49,9 +76,34 @@ impl ChatWorker {
                     let _ = self.events.send(ChatEvent::Connected).await;
                     let (mut write, mut read) = stream.split();
 
+                    let since = last_seq.get(&self.room).copied();
+                    if let Err(e) = send_frame(&mut write, &self.join_frame(since)).await {
+                        let _ = self
+                            .events
+                            .send(ChatEvent::Error(format!("chat join failed: {e}")))
+                            .await;
+                    }
+
+                    for frame in &unacked {
+                        if let Err(e) = send_frame(&mut write, frame).await {
+                            let _ = self
+                                .events
+                                .send(ChatEvent::Error(format!("chat send failed: {e}")))
+                                .await;
+                        }
+                    }
+                    // Whatever was typed while offline, admin commands
+                    // included, goes out in the order it was typed.
                     for cmd in pending.drain(..) {
-                        if let Err(e) = send_command(&mut write, &self.room, &self.username, cmd).await {
-                            let _ = self.events.send(ChatEvent::Error(format!("chat send failed: {e}"))).await;
+                        let frame = self.frame(cmd);
+                        if let Err(e) = send_frame(&mut write, &frame).await {
+                            let _ = self
+                                .events
+                                .send(ChatEvent::Error(format!("chat send failed: {e}")))
+                                .await;
+                        }
+                        if matches!(frame, ClientFrame::Message(_)) {
+                            unacked.push(frame);
                         }
                     }
 
@@ -61,8 +113,13 @@ impl ChatWorker {
                                 let Some(cmd) = maybe_cmd else {
                                     return;
                                 };
-                                if let Err(e) = send_command(&mut write, &self.room, &self.username, cmd.clone()).await {
-                                    pending.push(cmd);
+                                let frame = self.frame(cmd);
+                                match frame {
+                                    ClientFrame::Message(_) => unacked.push(frame.clone()),
+                                    ClientFrame::Join { .. } => self.fail_stale(&mut unacked).await,
+                                    _ => {}
+                                }
+                                if let Err(e) = send_frame(&mut write, &frame).await {
                                     let _ = self.events.send(ChatEvent::Error(format!("chat send failed: {e}"))).await;
                                     let _ = self.events.send(ChatEvent::Disconnected).await;
                                     break;
@@ -71,14 +128,35 @@ impl ChatWorker {
                             inbound = read.next() => {
                                 match inbound {
                                     Some(Ok(Message::Text(text))) => {
-                                        match serde_json::from_str::<ChatWireMessage>(&text) {
-                                            Ok(msg) if msg.room == self.room => {
-                                                let _ = self.events.send(ChatEvent::Message(msg)).await;
+                                        let frame = RelayFrame::parse(&text);
+                                        if let Ok(RelayFrame::Ack { id, .. } | RelayFrame::Error { message_id: Some(id), .. }) = &frame {
+                                            unacked.retain(|f| !matches!(f, ClientFrame::Message(m) if m.id == *id));
+                                        }
+                                        let event = match frame {
+                                            Ok(RelayFrame::Message(msg)) if msg.room == self.room => {
+                                                note_seq(&mut last_seq, &msg);
+                                                Some(ChatEvent::Message(msg))
+                                            }
+                                            Ok(RelayFrame::History { room, messages }) if room == self.room => {
+                                                messages.iter().for_each(|m| note_seq(&mut last_seq, m));
+                                                Some(ChatEvent::History(messages))
+                                            }
+                                            Ok(RelayFrame::Presence { room, users }) if room == self.room => {
+                                                Some(ChatEvent::Presence(users))
+                                            }
+                                            Ok(RelayFrame::System { room, message }) if room == self.room => {
+                                                Some(ChatEvent::System(message))
                                             }
-                                            Ok(_) => {}
-                                            Err(e) => {
-                                                let _ = self.events.send(ChatEvent::Error(format!("chat parse error: {e}"))).await;
+                                            Ok(RelayFrame::Ack { id, .. }) => Some(ChatEvent::Acked(id)),
+                                            Ok(RelayFrame::Error { message, message_id: Some(id) }) => {
+                                                Some(ChatEvent::Rejected { id, reason: message })
                                             }
+                                            Ok(RelayFrame::Error { message, .. }) => Some(ChatEvent::Error(message)),
+                                            Ok(_) => None,
+                                            Err(e) => Some(ChatEvent::Error(format!("chat parse error: {e}"))),
+                                        };
+                                        if let Some(event) = event {
+                                            let _ = self.events.send(event).await;
                                         }
                                     }
                                     Some(Ok(Message::Close(_))) | None => {
@@ -107,6 +185,19 @@ impl ChatWorker {
 
             loop {
                 match self.commands.try_recv() {
+                    // Applied now so the reconnect joins the right room.
+                    Ok(cmd @ ChatCommand::Join { .. }) => {
+                        // Messages typed so far belong to the room being left.
+                        let (sends, rest) = pending
+                            .drain(..)
+                            .partition(|cmd| matches!(cmd, ChatCommand::Send { .. }));
+                        pending = rest;
+                        for send in sends {
+                            unacked.push(self.frame(send));
+                        }
+                        self.frame(cmd);
+                        self.fail_stale(&mut unacked).await;
+                    }
                     Ok(cmd) => pending.push(cmd),
                     Err(tokio::sync::mpsc::error::TryRecvError::Empty) => break,
                     Err(tokio::sync::mpsc::error::TryRecvError::Disconnected) => return,
@@ -120,31 +211,78 @@ impl ChatWorker {
             }
         }
     }
+
+    /// After a join, messages meant for the old room or name would be posted
+    /// into the new one; mark them failed instead of sending them.
+    async fn fail_stale(&self, unacked: &mut Vec<ClientFrame>) {
+        let mut stale = Vec::new();
+        unacked.retain(|frame| match frame {
+            ClientFrame::Message(m) if m.room != self.room || m.author != self.username => {
+                stale.push(m.id.clone());
+                false
+            }
+            _ => true,
+        });
+        for id in stale {
+            let reason = "you switched room or name before it was delivered".to_string();
+            let _ = self.events.send(ChatEvent::Rejected { id, reason }).await;
+        }
+    }
+
+    fn join_frame(&self, since: Option<u64>) -> ClientFrame {
+        ClientFrame::Join {
+            room: self.room.clone(),
+            user: self.username.clone(),
+            password: self.password.clone(),
+            since,
+        }
+    }
+
+    /// Build the frame for `cmd`. A join also switches the room and name
+    /// used from then on.
+    fn frame(&mut self, cmd: ChatCommand) -> ClientFrame {
+        match cmd {
+            ChatCommand::Send { body, message_id } => {
+                let mut payload = ChatWireMessage {
+                    id: message_id,
+                    room: self.room.clone(),
+                    author: self.username.clone(),
+                    body,
+                    timestamp: Local::now().format("%H:%M").to_string(),
+                    ..Default::default()
+                };
+                if let Some(key) = &self.signing_key {
+                    payload.sign(key);
+                }
+                ClientFrame::Message(payload)
+            }
+            ChatCommand::Join { room, username } => {
+                self.room = room;
+                self.username = username;
+                self.join_frame(None)
+            }
+            ChatCommand::Admin { command } => ClientFrame::Admin {
+                token: self.admin_token.clone(),
+                command,
+            },
+        }
+    }
+}
+
+fn note_seq(last_seq: &mut HashMap<String, u64>, msg: &ChatWireMessage) {
+    if let Some(seq) = msg.seq {
+        let last = last_seq.entry(msg.room.clone()).or_default();
+        *last = (*last).max(seq);
+    }
 }
 
-async fn send_command<S>(
-    write: &mut S,
-    room: &str,
-    username: &str,
-    cmd: ChatCommand,
-) -> Result<(), String>
+async fn send_frame<S>(write: &mut S, frame: &ClientFrame) -> Result<(), String>
 where
     S: futures_util::sink::Sink<Message, Error = tokio_tungstenite::tungstenite::Error> + Unpin,
 {
-    match cmd {
-        ChatCommand::Send { body, message_id } => {
-            let payload = ChatWireMessage {
-                id: message_id,
-                room: room.to_string(),
-                author: username.to_string(),
-                body,
-                timestamp: Local::now().format("%H:%M").to_string(),
-            };
-            let text = serde_json::to_string(&payload).map_err(|e| e.to_string())?;
-            write
-                .send(Message::Text(text.into()))
-                .await
-                .map_err(|e| e.to_string())
-        }
-    }
+    let text = serde_json::to_string(frame).map_err(|e| e.to_string())?;
+    write
+        .send(Message::Text(text.into()))
+        .await
+        .map_err(|e| e.to_string())
 }
@@ -69,7 +69,7 @@ impl CustodianConfig {
 // BIP67 sort helper
 // ---------------------------------------------------------------------------
 
-pub fn bip67_sort(entries: &mut Vec<CustodianEntry>) {
+pub fn bip67_sort(entries: &mut [CustodianEntry]) {
     entries.sort_by(|a, b| a.pubkey.cmp(&b.pubkey));
 }
 
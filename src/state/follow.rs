@@ -0,0 +1,346 @@
+//! Followed teams and the notifications raised for their games.
+//!
+//! The follow list comes from `MMTUI_FOLLOW` (comma separated) or `follow` in
+//! `config.toml`, plus `~/.config/mmtui/follow.txt` (one team per line, `#`
+//! comments). A team matches on its ID, short name, abbreviation or full
+//! name, ignoring case.
+//!
+//! Notifications fire from the scoreboard refresh, whatever tab is open: when
+//! a followed team's game tips off, is within `close_margin` points in the
+//! last five minutes, and goes final.
+use std::collections::{HashMap, HashSet};
+use std::io::Write;
+use std::str::FromStr;
+
+use ncaa_api::{Game, GameStatus, Gender, Team, Tournament};
+
+use crate::state::announce::clock_secs;
+
+/// "Close" is judged over the last five minutes of the second half or OT.
+const CLOSE_WINDOW_SECS: f32 = 5.0 * 60.0;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Follows(Vec<String>);
+
+impl Follows {
+    pub fn new(teams: impl IntoIterator<Item = String>) -> Self {
+        let mut teams: Vec<String> = teams
+            .into_iter()
+            .map(|t| t.trim().to_lowercase())
+            .filter(|t| !t.is_empty())
+            .collect();
+        teams.sort();
+        teams.dedup();
+        Self(teams)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn contains(&self, team: &Team) -> bool {
+        [&team.id, &team.short_name, &team.abbrev, &team.name]
+            .into_iter()
+            .any(|name| self.0.contains(&name.to_lowercase()))
+    }
+
+    /// Either side of `game` is followed.
+    pub fn in_game(&self, game: &Game) -> bool {
+        [&game.top.team, &game.bottom.team]
+            .into_iter()
+            .flatten()
+            .any(|t| self.contains(t))
+    }
+}
+
+/// How a notification reaches you.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Alert {
+    #[default]
+    Bell,
+    /// `OSC 9` desktop notification (iTerm2, WezTerm, Windows Terminal).
+    Osc9,
+    /// `OSC 777` desktop notification (rxvt-unicode, foot, Ghostty).
+    Osc777,
+    Off,
+}
+
+impl FromStr for Alert {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "bell" => Ok(Alert::Bell),
+            "osc9" => Ok(Alert::Osc9),
+            "osc777" => Ok(Alert::Osc777),
+            "off" | "none" => Ok(Alert::Off),
+            other => Err(format!(
+                "unknown alert {other}; use bell, osc9, osc777 or off"
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FollowSettings {
+    pub teams: Follows,
+    pub alert: Alert,
+    /// Shell command run for every notification, with `MMTUI_NOTIFY_TITLE`
+    /// and `MMTUI_NOTIFY_BODY` set.
+    pub hook: Option<String>,
+    /// Largest margin that counts as a close game.
+    pub close_margin: u16,
+}
+
+impl Default for FollowSettings {
+    fn default() -> Self {
+        Self {
+            teams: Follows::default(),
+            alert: Alert::default(),
+            hook: None,
+            close_margin: 5,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum NotifyKind {
+    Start,
+    Close,
+    End,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Notification {
+    pub title: String,
+    pub body: String,
+}
+
+/// Notifications already raised, and those waiting to be delivered.
+#[derive(Debug, Default)]
+pub struct Notifier {
+    sent: HashSet<(String, NotifyKind)>,
+    pub outbox: Vec<Notification>,
+}
+
+impl Notifier {
+    /// Queue notifications for followed games that changed in a refresh.
+    pub fn check(
+        &mut self,
+        before: &HashMap<String, Game>,
+        tournament: &Tournament,
+        settings: &FollowSettings,
+    ) {
+        if settings.teams.is_empty() {
+            return;
+        }
+        for game in tournament.games().filter(|g| settings.teams.in_game(g)) {
+            let Some(old) = before.get(&game.id) else {
+                continue;
+            };
+            let matchup = matchup(game);
+            let mut raise = |kind, title: &str, body: String| {
+                if self.sent.insert((game.id.clone(), kind)) {
+                    self.outbox.push(Notification {
+                        title: title.to_string(),
+                        body,
+                    });
+                }
+            };
+            if old.status == GameStatus::Scheduled && game.status == GameStatus::InProgress {
+                raise(
+                    NotifyKind::Start,
+                    "Tip-off",
+                    format!("{matchup} has started"),
+                );
+            }
+            if is_close(game, tournament.gender, settings.close_margin) {
+                let clock = game.clock.as_deref().unwrap_or_default();
+                raise(
+                    NotifyKind::Close,
+                    "Close game",
+                    format!("{} with {clock} left", score(game)),
+                );
+            }
+            if old.status != GameStatus::Final && game.status == GameStatus::Final {
+                raise(NotifyKind::End, "Final", score(game));
+            }
+        }
+    }
+}
+
+/// Within `margin` late in the last period of regulation, or in overtime.
+fn is_close(game: &Game, gender: Gender, margin: u16) -> bool {
+    let (top, bottom) = game.score.unwrap_or_default();
+    game.status == GameStatus::InProgress
+        && game.period.unwrap_or_default() >= gender.regulation_periods()
+        && game
+            .clock
+            .as_deref()
+            .and_then(clock_secs)
+            .is_some_and(|secs| secs <= CLOSE_WINDOW_SECS)
+        && top.abs_diff(bottom) <= margin
+}
+
+fn matchup(game: &Game) -> String {
+    format!(
+        "{} vs {}",
+        name(game.top.team.as_ref()),
+        name(game.bottom.team.as_ref())
+    )
+}
+
+fn score(game: &Game) -> String {
+    let (top, bottom) = game.score.unwrap_or_default();
+    format!(
+        "{} {top} - {bottom} {}",
+        name(game.top.team.as_ref()),
+        name(game.bottom.team.as_ref())
+    )
+}
+
+fn name(team: Option<&Team>) -> &str {
+    team.map(|t| t.short_name.as_str()).unwrap_or("TBD")
+}
+
+/// Raise `notification` in the terminal and run the hook, if any.
+pub fn deliver(settings: &FollowSettings, notification: &Notification) {
+    let (title, body) = (clean(&notification.title), clean(&notification.body));
+    if let Some(sequence) = escape_sequence(settings.alert, &title, &body) {
+        let mut stdout = std::io::stdout();
+        let _ = stdout.write_all(sequence.as_bytes());
+        let _ = stdout.flush();
+    }
+
+    if let Some(hook) = &settings.hook {
+        let spawned = tokio::process::Command::new("sh")
+            .arg("-c")
+            .arg(hook)
+            .env("MMTUI_NOTIFY_TITLE", &title)
+            .env("MMTUI_NOTIFY_BODY", &body)
+            .stdin(std::process::Stdio::null())
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .spawn();
+        if let Err(e) = spawned {
+            log::warn!("notify hook failed: {e}");
+        }
+    }
+}
+
+/// Team names come from the network: drop control characters (BEL, ESC,
+/// C1 codes) that would end the escape sequence early or start another.
+fn clean(text: &str) -> String {
+    text.chars().filter(|c| !c.is_control()).collect()
+}
+
+/// The bytes that raise `alert`, from already cleaned text.
+fn escape_sequence(alert: Alert, title: &str, body: &str) -> Option<String> {
+    match alert {
+        Alert::Bell => Some("\x07".to_string()),
+        Alert::Osc9 => Some(format!("\x1b]9;{title}: {body}\x07")),
+        // `;` separates the title from the body here.
+        Alert::Osc777 => Some(format!(
+            "\x1b]777;notify;{};{body}\x07",
+            title.replace(';', ",")
+        )),
+        Alert::Off => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ncaa_api::{Region, Round, RoundKind, TeamSeed};
+
+    fn slot(id: &str, name: &str) -> TeamSeed {
+        TeamSeed {
+            seed: 1,
+            team: Some(Team {
+                id: id.into(),
+                short_name: name.into(),
+                abbrev: id.to_uppercase(),
+                ..Default::default()
+            }),
+            placeholder: None,
+        }
+    }
+
+    fn tournament(game: Game) -> Tournament {
+        Tournament {
+            regions: vec![Region {
+                rounds: vec![Round {
+                    kind: RoundKind::Sweet16,
+                    games: vec![game],
+                }],
+                ..Default::default()
+            }],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn follows_match_any_team_name() {
+        let follows = Follows::new(["  Duke ".to_string(), "UNC".to_string()]);
+        let duke = slot("150", "Duke").team.unwrap();
+        let unc = slot("unc", "North Carolina").team.unwrap();
+        let uk = slot("96", "Kentucky").team.unwrap();
+        assert!(follows.contains(&duke));
+        assert!(follows.contains(&unc));
+        assert!(!follows.contains(&uk));
+    }
+
+    #[test]
+    fn followed_games_notify_on_start_close_finish_once() {
+        let settings = FollowSettings {
+            teams: Follows::new(["duke".to_string()]),
+            ..Default::default()
+        };
+        let scheduled = Game {
+            id: "g1".into(),
+            top: slot("duke", "Duke"),
+            bottom: slot("unc", "UNC"),
+            ..Default::default()
+        };
+        let close = Game {
+            status: GameStatus::InProgress,
+            period: Some(2),
+            clock: Some("4:12".into()),
+            score: Some((70, 68)),
+            ..scheduled.clone()
+        };
+        let mut notifier = Notifier::default();
+        let before = HashMap::from([("g1".to_string(), scheduled)]);
+        notifier.check(&before, &tournament(close.clone()), &settings);
+        notifier.check(&before, &tournament(close.clone()), &settings);
+        let titles: Vec<_> = notifier.outbox.iter().map(|n| n.title.as_str()).collect();
+        assert_eq!(titles, ["Tip-off", "Close game"]);
+        assert_eq!(notifier.outbox[1].body, "Duke 70 - 68 UNC with 4:12 left");
+
+        let final_game = Game {
+            status: GameStatus::Final,
+            ..close.clone()
+        };
+        let before = HashMap::from([("g1".to_string(), close)]);
+        notifier.check(&before, &tournament(final_game), &settings);
+        assert_eq!(notifier.outbox.len(), 3);
+
+        let unfollowed = FollowSettings::default();
+        let mut quiet = Notifier::default();
+        quiet.check(&before, &tournament(Game::default()), &unfollowed);
+        assert!(quiet.outbox.is_empty());
+    }
+
+    #[test]
+    fn notification_text_cannot_break_out_of_the_escape_sequence() {
+        let title = clean("Tip-off\x07\x1b]0;pwned\x1b\\");
+        let body = clean("Duke\u{9b}2J vs UNC");
+        assert_eq!(title, "Tip-off]0;pwned\\");
+        for alert in [Alert::Osc9, Alert::Osc777] {
+            let sequence = escape_sequence(alert, &title, &body).unwrap();
+            assert_eq!(sequence.matches('\x1b').count(), 1);
+            assert!(sequence.ends_with('\x07'));
+            assert_eq!(sequence.matches('\x07').count(), 1);
+        }
+    }
+}
@@ -1,9 +1,17 @@
 use crate::state::network::LoadingState;
 use crossterm::event::KeyEvent;
-use ncaa_api::{Game, GameDetail, Tournament};
+use ncaa_api::scenarios::EntrantPath;
+use ncaa_api::scoring::ScoringRule;
+use ncaa_api::simulate::EntrantOdds;
+use ncaa_api::{BracketPicks, Game, GameDetail, Gender, Tournament};
+use std::path::PathBuf;
 
 #[derive(Debug, Clone)]
 pub enum NetworkRequest {
+    /// Switch every later request to the men's or women's tournament.
+    SelectTournament {
+        gender: Gender,
+    },
     LoadBracket,
     RefreshScores,
     RefreshPrizePoolBalance {
@@ -15,17 +23,79 @@ pub enum NetworkRequest {
         /// for NCAA-sourced games; game detail is skipped gracefully when absent.
         espn_id: Option<String>,
     },
+    /// Monte Carlo run over the remaining games; CPU-bound, so the worker moves
+    /// it onto a blocking thread.
+    SimulatePool {
+        /// Compare generation the odds are for.
+        generation: u64,
+        tournament: Box<Tournament>,
+        /// (source, picks) pairs from the Compare tab.
+        entries: Vec<(String, BracketPicks)>,
+        rule: ScoringRule,
+        ratings_path: Option<PathBuf>,
+    },
+    /// Fetch Compare picks files and links concurrently.
+    LoadCompareSources {
+        sources: Vec<String>,
+    },
+    /// Send the user's saved picks to the pool server.
+    SubmitPicks {
+        pool_url: String,
+        picks: BracketPicks,
+    },
+    /// Exhaustive "what needs to happen" enumeration for the Compare drill-down.
+    AnalyzePaths {
+        /// Compare generation the paths are for.
+        generation: u64,
+        tournament: Box<Tournament>,
+        entries: Vec<(String, BracketPicks)>,
+        rule: ScoringRule,
+    },
 }
 
 #[derive(Debug)]
 pub enum NetworkResponse {
-    LoadingStateChanged { loading_state: LoadingState },
-    BracketLoaded { tournament: Tournament },
+    LoadingStateChanged {
+        loading_state: LoadingState,
+    },
+    /// Everything after this is for `gender`'s tournament.
+    TournamentSelected {
+        gender: Gender,
+    },
+    BracketLoaded {
+        tournament: Tournament,
+    },
     /// Partial update: only changed Game objects, merged into the bracket tree.
-    BracketUpdated { games: Vec<Game> },
-    GameDetailLoaded { detail: GameDetail },
-    PrizePoolBalanceUpdated { balance_sat: u64 },
-    Error { message: String },
+    BracketUpdated {
+        games: Vec<Game>,
+    },
+    GameDetailLoaded {
+        detail: Box<GameDetail>,
+    },
+    /// Pool odds keyed by Compare source, or why the run failed.
+    PoolSimulated {
+        generation: u64,
+        odds: Result<Vec<(String, EntrantOdds)>, String>,
+    },
+    /// Entries by source label, plus sources that failed to load.
+    CompareSourcesLoaded {
+        entries: Vec<(String, BracketPicks)>,
+        errors: Vec<String>,
+    },
+    PicksSubmitted {
+        message: String,
+    },
+    /// Per-entrant paths keyed by Compare source, or why none were computed.
+    PathsAnalyzed {
+        generation: u64,
+        paths: Result<Vec<(String, EntrantPath)>, String>,
+    },
+    PrizePoolBalanceUpdated {
+        balance_sat: u64,
+    },
+    Error {
+        message: String,
+    },
 }
 
 #[derive(Debug, Clone)]
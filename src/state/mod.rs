@@ -1,7 +1,11 @@
+pub mod announce;
 pub mod app_settings;
 pub mod app_state;
+pub mod archive;
 pub mod chat;
 pub mod custodian;
+pub mod follow;
 pub mod messages;
 pub mod network;
 pub mod refresher;
+pub mod slash;
@@ -1,11 +1,21 @@
+use crate::state::archive::{Entry, Recorder, Replay};
 use crate::state::messages::{NetworkRequest, NetworkResponse};
+use futures_util::future::join_all;
 use log::{debug, error};
 use ncaa_api::client::NcaaApi;
+use ncaa_api::scenarios::{EntrantPath, analyze_paths};
+use ncaa_api::scoring::ScoringRule;
+use ncaa_api::simulate::{DEFAULT_ITERATIONS, EntrantOdds, Ratings, WinModel, simulate_pool};
+use ncaa_api::{BracketPicks, Game, Gender, Tournament};
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::Duration;
 use tokio::sync::mpsc;
 
+/// Each Compare source gets this long before it is reported as failed.
+const COMPARE_SOURCE_TIMEOUT: Duration = Duration::from_secs(8);
+
 const SPINNER_CHARS: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
 pub const ERROR_CHAR: char = '!';
 
@@ -23,45 +33,81 @@ impl Default for LoadingState {
 
 pub struct NetworkWorker {
     client: NcaaApi,
+    gender: Gender,
+    api_for: Box<dyn Fn(Gender) -> NcaaApi + Send + Sync>,
     requests: mpsc::Receiver<NetworkRequest>,
     responses: mpsc::Sender<NetworkResponse>,
     is_loading: Arc<AtomicBool>,
+    recorder: Option<Recorder>,
+    replay: Option<Replay>,
 }
 
 impl NetworkWorker {
     pub fn new(
         requests: mpsc::Receiver<NetworkRequest>,
         responses: mpsc::Sender<NetworkResponse>,
+        gender: Gender,
+        api_for: impl Fn(Gender) -> NcaaApi + Send + Sync + 'static,
     ) -> Self {
         Self {
-            client: NcaaApi::new(),
+            client: api_for(gender),
+            gender,
+            api_for: Box::new(api_for),
             requests,
             responses,
             is_loading: Arc::new(AtomicBool::new(false)),
+            recorder: None,
+            replay: None,
         }
     }
 
+    /// Append every bracket, scoreboard and game detail loaded to an archive.
+    pub fn with_recorder(mut self, recorder: Recorder) -> Self {
+        self.recorder = Some(recorder);
+        self
+    }
+
+    /// Serve brackets, scoreboards and game details from an archive instead
+    /// of the network.
+    pub fn with_replay(mut self, replay: Replay) -> Self {
+        self.replay = Some(replay);
+        self
+    }
+
     pub async fn run(mut self) {
+        if let Some(replay) = &self.replay {
+            tokio::spawn(push_replayed_scores(
+                replay.started().into(),
+                replay.schedule(),
+                self.responses.clone(),
+            ));
+        }
         while let Some(request) = self.requests.recv().await {
             self.start_loading_animation().await;
 
-            let result = match request {
-                NetworkRequest::LoadBracket => self.handle_load_bracket().await,
-                NetworkRequest::RefreshScores => self.handle_refresh_scores().await,
-                NetworkRequest::RefreshPrizePoolBalance { address } => {
-                    self.handle_refresh_prize_pool_balance(address).await
-                }
-                NetworkRequest::LoadGameDetail { bracket_id, espn_id } => {
-                    self.handle_load_game_detail(bracket_id, espn_id).await
-                }
+            let result = match self.replayed(&request) {
+                Some(response) => Ok(Some(response)),
+                None => self.handle(request).await,
             };
 
             debug!("network request complete");
             self.stop_loading_animation(result.is_ok()).await;
 
-            let response = result.unwrap_or_else(|err| NetworkResponse::Error {
-                message: err.to_string(),
-            });
+            let response = match result {
+                Ok(Some(response)) => response,
+                // A spawned task sends the response when it finishes.
+                Ok(None) => continue,
+                Err(err) => NetworkResponse::Error {
+                    message: err.to_string(),
+                },
+            };
+
+            if let Some(recorder) = &mut self.recorder
+                && let Some(entry) = Entry::from_response(&response)
+                && let Err(e) = recorder.record(entry)
+            {
+                error!("Failed to record network response: {e}");
+            }
 
             if let Err(e) = self.responses.send(response).await {
                 error!("Failed to send network response: {e}");
@@ -70,6 +116,109 @@ impl NetworkWorker {
         }
     }
 
+    /// Answer `request`, or return `None` when the work was handed to a
+    /// spawned task that will send its own response.
+    async fn handle(
+        &mut self,
+        request: NetworkRequest,
+    ) -> Result<Option<NetworkResponse>, ncaa_api::client::ApiError> {
+        let response = match request {
+            NetworkRequest::SelectTournament { gender } => {
+                if gender != self.gender {
+                    self.client = (self.api_for)(gender);
+                    self.gender = gender;
+                }
+                NetworkResponse::TournamentSelected { gender }
+            }
+            NetworkRequest::LoadBracket => self.handle_load_bracket().await?,
+            NetworkRequest::RefreshScores => self.handle_refresh_scores().await?,
+            NetworkRequest::RefreshPrizePoolBalance { address } => {
+                self.handle_refresh_prize_pool_balance(address).await?
+            }
+            NetworkRequest::LoadGameDetail {
+                bracket_id,
+                espn_id,
+            } => self.handle_load_game_detail(bracket_id, espn_id).await?,
+            NetworkRequest::SimulatePool {
+                generation,
+                tournament,
+                entries,
+                rule,
+                ratings_path,
+            } => {
+                // A simulation takes seconds; run it beside the worker so score
+                // refreshes and other requests don't queue up behind it.
+                let responses = self.responses.clone();
+                tokio::spawn(async move {
+                    let odds = simulate_pool_odds(*tournament, entries, rule, ratings_path).await;
+                    let response = NetworkResponse::PoolSimulated { generation, odds };
+                    if let Err(e) = responses.send(response).await {
+                        error!("Failed to send pool simulation: {e}");
+                    }
+                });
+                return Ok(None);
+            }
+            NetworkRequest::LoadCompareSources { sources } => {
+                self.handle_load_compare_sources(sources).await
+            }
+            NetworkRequest::SubmitPicks { pool_url, picks } => {
+                self.handle_submit_picks(pool_url, picks).await?
+            }
+            NetworkRequest::AnalyzePaths {
+                generation,
+                tournament,
+                entries,
+                rule,
+            } => {
+                // Enumerating paths can take a while; like a simulation, it
+                // must not hold up the requests queued behind it.
+                let responses = self.responses.clone();
+                tokio::spawn(async move {
+                    let paths = analyze_entrant_paths(*tournament, entries, rule).await;
+                    let response = NetworkResponse::PathsAnalyzed { generation, paths };
+                    if let Err(e) = responses.send(response).await {
+                        error!("Failed to send path analysis: {e}");
+                    }
+                });
+                return Ok(None);
+            }
+        };
+        Ok(Some(response))
+    }
+
+    /// The archive's answer to `request` when replaying, as of the replay
+    /// clock. Requests the archive does not cover go to the network.
+    fn replayed(&self, request: &NetworkRequest) -> Option<NetworkResponse> {
+        let replay = self.replay.as_ref()?;
+        let response = match request {
+            // An archive holds one tournament.
+            NetworkRequest::SelectTournament { .. } => NetworkResponse::TournamentSelected {
+                gender: self.gender,
+            },
+            NetworkRequest::LoadBracket => match replay.bracket() {
+                Some(tournament) => NetworkResponse::BracketLoaded { tournament },
+                None => NetworkResponse::Error {
+                    message: "Replay archive has no bracket.".into(),
+                },
+            },
+            NetworkRequest::RefreshScores => NetworkResponse::BracketUpdated {
+                games: replay.scoreboard_at(replay.now_ms()).unwrap_or_default(),
+            },
+            NetworkRequest::LoadGameDetail {
+                espn_id: Some(eid), ..
+            } => match replay.game_detail_at(eid, replay.now_ms()) {
+                Some(detail) => NetworkResponse::GameDetailLoaded {
+                    detail: Box::new(detail),
+                },
+                None => NetworkResponse::Error {
+                    message: "No recorded detail for this game yet.".into(),
+                },
+            },
+            _ => return None,
+        };
+        Some(response)
+    }
+
     async fn handle_load_bracket(&self) -> Result<NetworkResponse, ncaa_api::client::ApiError> {
         debug!("loading tournament bracket");
         let tournament = self.client.fetch_tournament().await?;
@@ -120,7 +269,50 @@ impl NetworkWorker {
         };
         debug!("loading game detail for bracket pos {bracket_id} (espn {eid})");
         let detail = self.client.fetch_game_detail(&eid).await?;
-        Ok(NetworkResponse::GameDetailLoaded { detail })
+        Ok(NetworkResponse::GameDetailLoaded {
+            detail: Box::new(detail),
+        })
+    }
+
+    /// Failed sources are listed in the response, so this always answers.
+    async fn handle_load_compare_sources(&self, sources: Vec<String>) -> NetworkResponse {
+        debug!("loading {} compare sources", sources.len());
+        let (entries, errors) = load_compare_sources(sources).await;
+        NetworkResponse::CompareSourcesLoaded { entries, errors }
+    }
+
+    async fn handle_submit_picks(
+        &self,
+        pool_url: String,
+        picks: BracketPicks,
+    ) -> Result<NetworkResponse, ncaa_api::client::ApiError> {
+        let url = format!("{pool_url}/picks");
+        debug!("submitting picks for {} to {url}", picks.user_id);
+
+        #[derive(serde::Deserialize)]
+        struct PoolError {
+            error: String,
+        }
+
+        let resp = reqwest::Client::new()
+            .post(&url)
+            .json(&picks)
+            .send()
+            .await
+            .map_err(|e| ncaa_api::client::ApiError::Other(format!("{url}: {e}")))?;
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let reason = match resp.json::<PoolError>().await {
+                Ok(body) => body.error,
+                Err(_) => status.to_string(),
+            };
+            return Err(ncaa_api::client::ApiError::Other(format!(
+                "pool rejected picks: {reason}"
+            )));
+        }
+        Ok(NetworkResponse::PicksSubmitted {
+            message: format!("Picks for {} submitted to {pool_url}.", picks.user_id),
+        })
     }
 
     async fn start_loading_animation(&self) {
@@ -166,3 +358,121 @@ impl NetworkWorker {
             .await;
     }
 }
+
+/// Run a pool simulation on the blocking pool, returning odds by source.
+async fn simulate_pool_odds(
+    tournament: Tournament,
+    entries: Vec<(String, BracketPicks)>,
+    rule: ScoringRule,
+    ratings_path: Option<PathBuf>,
+) -> Result<Vec<(String, EntrantOdds)>, String> {
+    debug!("simulating pool for {} entries", entries.len());
+    tokio::task::spawn_blocking(move || {
+        let model = match ratings_path {
+            Some(path) => WinModel::Ratings(Ratings::load(&path)?),
+            None => WinModel::Seed,
+        };
+        let (sources, picks): (Vec<String>, Vec<BracketPicks>) = entries.into_iter().unzip();
+        let seed = chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default() as u64;
+        let odds = simulate_pool(&tournament, &picks, rule, &model, DEFAULT_ITERATIONS, seed);
+        Ok::<_, String>(sources.into_iter().zip(odds).collect())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Enumerate each entrant's paths on the blocking pool, keyed by source.
+async fn analyze_entrant_paths(
+    tournament: Tournament,
+    entries: Vec<(String, BracketPicks)>,
+    rule: ScoringRule,
+) -> Result<Vec<(String, EntrantPath)>, String> {
+    debug!("analyzing paths for {} entries", entries.len());
+    tokio::task::spawn_blocking(move || {
+        let (sources, picks): (Vec<String>, Vec<BracketPicks>) = entries.into_iter().unzip();
+        analyze_paths(&tournament, &picks, rule).map(|p| sources.into_iter().zip(p).collect())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Deliver each recorded scoreboard when it is due, as if refreshed live.
+async fn push_replayed_scores(
+    started: tokio::time::Instant,
+    schedule: Vec<(Duration, Vec<Game>)>,
+    responses: mpsc::Sender<NetworkResponse>,
+) {
+    for (due, games) in schedule {
+        tokio::time::sleep_until(started + due).await;
+        if responses
+            .send(NetworkResponse::BracketUpdated { games })
+            .await
+            .is_err()
+        {
+            break;
+        }
+    }
+}
+
+/// Fetch every Compare source concurrently: entries by source label, plus
+/// the sources that failed.
+pub async fn load_compare_sources(
+    sources: Vec<String>,
+) -> (Vec<(String, BracketPicks)>, Vec<String>) {
+    let client = reqwest::Client::new();
+    let client = &client;
+    let results = join_all(sources.iter().map(|source| async move {
+        match tokio::time::timeout(COMPARE_SOURCE_TIMEOUT, load_picks_source(client, source)).await
+        {
+            Ok(result) => result,
+            Err(_) => Err(format!(
+                "{source}: timed out after {}s",
+                COMPARE_SOURCE_TIMEOUT.as_secs()
+            )),
+        }
+    }))
+    .await;
+
+    let mut entries = Vec::new();
+    let mut errors = Vec::new();
+    for (source, result) in sources.into_iter().zip(results) {
+        match result {
+            Ok(PicksSource::One(picks)) => entries.push((source, picks)),
+            // Label each entry of a list so rows stay distinct per user.
+            Ok(PicksSource::Many(list)) => entries.extend(
+                list.into_iter()
+                    .map(|picks| (format!("{source}#{}", picks.user_id), picks)),
+            ),
+            Err(e) => errors.push(e),
+        }
+    }
+    (entries, errors)
+}
+
+/// A picks file or link holds one entry, or a list of them (a pool server's
+/// `/picks`).
+#[derive(serde::Deserialize)]
+#[serde(untagged)]
+enum PicksSource {
+    One(BracketPicks),
+    Many(Vec<BracketPicks>),
+}
+
+async fn load_picks_source(client: &reqwest::Client, source: &str) -> Result<PicksSource, String> {
+    let body = if source.starts_with("http://") || source.starts_with("https://") {
+        client
+            .get(source)
+            .send()
+            .await
+            .and_then(|resp| resp.error_for_status())
+            .map_err(|e| format!("{source}: fetch failed: {e}"))?
+            .text()
+            .await
+            .map_err(|e| format!("{source}: read body failed: {e}"))?
+    } else {
+        tokio::fs::read_to_string(source)
+            .await
+            .map_err(|e| format!("{source}: read failed: {e}"))?
+    };
+    serde_json::from_str(&body).map_err(|e| format!("{source}: invalid picks json: {e}"))
+}
@@ -3,19 +3,23 @@ use std::time::Duration;
 use tokio::sync::mpsc;
 use tokio::time::interval;
 
-/// Periodic score refresh — every 30 seconds during the tournament.
+/// Periodic score refresh — every 30 seconds by default during the tournament.
 /// Only sends RefreshScores; bracket structure is loaded once on startup.
 pub struct PeriodicRefresher {
     network_requests: mpsc::Sender<NetworkRequest>,
+    period: Duration,
 }
 
 impl PeriodicRefresher {
-    pub fn new(network_requests: mpsc::Sender<NetworkRequest>) -> Self {
-        Self { network_requests }
+    pub fn new(network_requests: mpsc::Sender<NetworkRequest>, period: Duration) -> Self {
+        Self {
+            network_requests,
+            period,
+        }
     }
 
     pub async fn run(self) {
-        let mut scores_interval = interval(Duration::from_secs(30));
+        let mut scores_interval = interval(self.period);
         // Skip the immediate first tick so startup loading isn't double-triggered.
         scores_interval.tick().await;
 
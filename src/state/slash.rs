@@ -0,0 +1,277 @@
+//! Slash commands typed into the chat composer.
+//!
+//! Anything that is not a command is sent as a message. `/me` travels as a
+//! plain message whose body starts with `/me `, so older clients still show
+//! it, just without the emote formatting.
+use ncaa_api::{BracketPicks, Game, GameStatus, RoundKind, Team, Tournament};
+
+/// Moderation commands the relay runs for holders of its admin token.
+pub const ADMIN_COMMANDS: [&str; 5] = ["/mute", "/unmute", "/kick", "/ban", "/slow"];
+
+pub const HELP: &str = "commands: /nick <name>, /join <room>, /me <action>, \
+                        /score <team>, /bracket [user]";
+
+const MAX_NAME_LEN: usize = 32;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChatInput {
+    Say(String),
+    /// `/me waves`, kept verbatim as the message body.
+    Emote(String),
+    Nick(String),
+    Join(String),
+    Score(String),
+    /// Whose bracket to share; `None` shares your own.
+    Bracket(Option<String>),
+    /// Full command line for the relay, e.g. `/mute troll 5`.
+    Admin(String),
+    Help,
+    /// Malformed command, with what to tell the user.
+    Invalid(String),
+}
+
+pub fn parse(input: &str) -> ChatInput {
+    let input = input.trim();
+    if !input.starts_with('/') {
+        return ChatInput::Say(input.to_string());
+    }
+    let (verb, rest) = input.split_once(' ').unwrap_or((input, ""));
+    let rest = rest.trim();
+    match verb {
+        "/me" if !rest.is_empty() => ChatInput::Emote(input.to_string()),
+        "/nick" => {
+            validate_name(verb, rest, "name").map_or_else(ChatInput::Invalid, ChatInput::Nick)
+        }
+        "/join" => validate_name(verb, rest.trim_start_matches('#'), "room")
+            .map_or_else(ChatInput::Invalid, ChatInput::Join),
+        "/score" if !rest.is_empty() => ChatInput::Score(rest.to_string()),
+        "/bracket" => ChatInput::Bracket((!rest.is_empty()).then(|| rest.to_string())),
+        "/help" => ChatInput::Help,
+        v if ADMIN_COMMANDS.contains(&v) => ChatInput::Admin(input.to_string()),
+        "/me" => ChatInput::Invalid("usage: /me <action>".into()),
+        "/score" => ChatInput::Invalid("usage: /score <team>".into()),
+        _ => ChatInput::Invalid(format!("unknown command {verb}; {HELP}")),
+    }
+}
+
+/// Names and rooms: one word the relay and other clients can show as-is.
+fn validate_name(verb: &str, name: &str, what: &str) -> Result<String, String> {
+    if name.is_empty() {
+        return Err(format!("usage: {verb} <{what}>"));
+    }
+    if name.chars().count() > MAX_NAME_LEN || name.chars().any(char::is_whitespace) {
+        return Err(format!(
+            "{what} must be one word of at most {MAX_NAME_LEN} characters"
+        ));
+    }
+    Ok(name.to_string())
+}
+
+/// The action text of a `/me` message body.
+pub fn emote(body: &str) -> Option<&str> {
+    body.strip_prefix("/me ")
+        .map(str::trim)
+        .filter(|a| !a.is_empty())
+}
+
+// ---------------------------------------------------------------------------
+// /score
+// ---------------------------------------------------------------------------
+
+/// One-line score for `query`'s live game, else their latest result, else
+/// their next game.
+pub fn score_line(tournament: &Tournament, query: &str) -> Result<String, String> {
+    let games: Vec<&Game> = tournament.games().filter(|g| team_in(g, query)).collect();
+    let live = games.iter().find(|g| g.is_live());
+    let last_final = games
+        .iter()
+        .filter(|g| g.status == GameStatus::Final)
+        .max_by_key(|g| g.start_time);
+    let next = games
+        .iter()
+        .filter(|g| g.status == GameStatus::Scheduled)
+        .min_by_key(|g| g.start_time);
+    let game = live
+        .or(last_final)
+        .or(next)
+        .ok_or_else(|| format!("no game found for {query}"))?;
+
+    let name = |team: &Option<Team>| {
+        team.as_ref()
+            .map(|t| t.short_name.clone())
+            .unwrap_or_else(|| "TBD".to_string())
+    };
+    let (top, bottom) = (name(&game.top.team), name(&game.bottom.team));
+    let (top_score, bottom_score) = game.score.unwrap_or_default();
+    Ok(match game.status {
+        GameStatus::InProgress => format!(
+            "{top} {top_score} - {bottom_score} {bottom} · {} {}",
+            tournament
+                .gender
+                .period_label(game.period.unwrap_or_default()),
+            game.clock.as_deref().unwrap_or_default()
+        ),
+        GameStatus::Final => format!("{top} {top_score} - {bottom_score} {bottom} · Final"),
+        GameStatus::Postponed => format!("{top} vs {bottom} · Postponed"),
+        GameStatus::Scheduled => format!(
+            "{top} vs {bottom} · {}",
+            game.start_time
+                .map(|t| t.format("%b %-d %H:%M UTC").to_string())
+                .unwrap_or_else(|| "time TBD".to_string())
+        ),
+    })
+}
+
+/// Short name or abbreviation exactly, or any part of the full name.
+fn team_in(game: &Game, query: &str) -> bool {
+    let query = query.to_lowercase();
+    [&game.top.team, &game.bottom.team]
+        .into_iter()
+        .flatten()
+        .any(|t| {
+            t.short_name.to_lowercase() == query
+                || t.abbrev.to_lowercase() == query
+                || t.name.to_lowercase().contains(&query)
+        })
+}
+
+// ---------------------------------------------------------------------------
+// /bracket
+// ---------------------------------------------------------------------------
+
+/// Champion, Final Four and pick count, e.g.
+/// `alice's bracket: champion Duke · Final Four Duke, Houston, ... · 63 picks`.
+pub fn bracket_summary(tournament: &Tournament, picks: &BracketPicks) -> String {
+    let picked = |kind: RoundKind| -> Vec<String> {
+        tournament
+            .regions
+            .iter()
+            .flat_map(|r| &r.rounds)
+            .filter(|round| round.kind == kind)
+            .flat_map(|round| &round.games)
+            .filter_map(|g| {
+                let pick = picks.selections.get(&g.id)?;
+                Some(team_name(tournament, g, pick))
+            })
+            .collect()
+    };
+    let champion = picked(RoundKind::Championship)
+        .pop()
+        .map(|team| format!("champion {team}"))
+        .unwrap_or_else(|| "no champion picked".to_string());
+    let final_four = picked(RoundKind::Elite8);
+
+    let mut parts = vec![champion];
+    if !final_four.is_empty() {
+        parts.push(format!("Final Four {}", final_four.join(", ")));
+    }
+    parts.push(format!("{} picks", picks.selections.len()));
+    format!("{}'s bracket: {}", picks.user_id, parts.join(" · "))
+}
+
+/// A picked team ID, or a positional `top:`/`bottom:` key, as a short name.
+fn team_name(tournament: &Tournament, game: &Game, pick: &str) -> String {
+    let slot = match pick.split_once(':') {
+        Some(("top", _)) => game.top.team.as_ref(),
+        Some(("bottom", _)) => game.bottom.team.as_ref(),
+        _ => tournament
+            .games()
+            .flat_map(|g| [&g.top.team, &g.bottom.team])
+            .flatten()
+            .find(|t| t.id == pick),
+    };
+    slot.map(|t| t.short_name.clone())
+        .unwrap_or_else(|| pick.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ncaa_api::{Region, Round, TeamSeed};
+
+    fn seeded(seed: u8, id: &str, name: &str) -> TeamSeed {
+        TeamSeed {
+            seed,
+            team: Some(Team {
+                id: id.into(),
+                name: format!("{name} Blue Devils"),
+                short_name: name.into(),
+                abbrev: id.to_uppercase(),
+                ..Default::default()
+            }),
+            placeholder: None,
+        }
+    }
+
+    fn tournament() -> Tournament {
+        let live = Game {
+            id: "e8".into(),
+            top: seeded(1, "duke", "Duke"),
+            bottom: seeded(2, "unc", "North Carolina"),
+            status: GameStatus::InProgress,
+            score: Some((45, 40)),
+            period: Some(2),
+            clock: Some("10:23".into()),
+            ..Default::default()
+        };
+        let title = Game {
+            id: "nc".into(),
+            ..Default::default()
+        };
+        Tournament {
+            regions: vec![Region {
+                name: "East".into(),
+                rounds: vec![
+                    Round {
+                        kind: RoundKind::Elite8,
+                        games: vec![live],
+                    },
+                    Round {
+                        kind: RoundKind::Championship,
+                        games: vec![title],
+                    },
+                ],
+                ..Default::default()
+            }],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn parses_commands_and_plain_text() {
+        assert_eq!(parse("go heels"), ChatInput::Say("go heels".into()));
+        assert_eq!(parse("/me waves"), ChatInput::Emote("/me waves".into()));
+        assert_eq!(parse("/join #east"), ChatInput::Join("east".into()));
+        assert_eq!(parse("/bracket"), ChatInput::Bracket(None));
+        assert_eq!(
+            parse("/mute troll 5"),
+            ChatInput::Admin("/mute troll 5".into())
+        );
+        assert!(matches!(parse("/nick two words"), ChatInput::Invalid(_)));
+        assert!(matches!(parse("/score"), ChatInput::Invalid(_)));
+        assert!(matches!(parse("/dance"), ChatInput::Invalid(_)));
+        assert_eq!(emote("/me waves"), Some("waves"));
+    }
+
+    #[test]
+    fn shares_scores_and_brackets() {
+        let t = tournament();
+        assert_eq!(
+            score_line(&t, "unc").unwrap(),
+            "Duke 45 - 40 North Carolina · 2H 10:23"
+        );
+        assert!(score_line(&t, "gonzaga").is_err());
+
+        let picks = BracketPicks {
+            user_id: "alice".into(),
+            selections: [("e8", "top:e8"), ("nc", "duke")]
+                .map(|(g, p)| (g.to_string(), p.to_string()))
+                .into(),
+            ..Default::default()
+        };
+        assert_eq!(
+            bracket_summary(&t, &picks),
+            "alice's bracket: champion Duke · Final Four Duke · 2 picks"
+        );
+    }
+}